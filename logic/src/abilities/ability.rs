@@ -1,4 +1,9 @@
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// An ability is written as its lowercase name, and read by [`Ability::from_name`], so that
+/// `strength`, `Strength` and `STR` are all the same ability wherever abilities are read.
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Ability {
     Strength,
     Dexterity,
@@ -7,3 +12,59 @@ pub enum Ability {
     Wisdom,
     Charisma,
 }
+
+impl Ability {
+    /// Every ability, in the traditional order.
+    pub fn all() -> [Ability; 6] {
+        [
+            Ability::Strength,
+            Ability::Dexterity,
+            Ability::Constitution,
+            Ability::Intelligence,
+            Ability::Wisdom,
+            Ability::Charisma,
+        ]
+    }
+
+    /// The three letter abbreviation used for the ability in expressions, e.g. `CON`.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Ability::Strength => "STR",
+            Ability::Dexterity => "DEX",
+            Ability::Constitution => "CON",
+            Ability::Intelligence => "INT",
+            Ability::Wisdom => "WIS",
+            Ability::Charisma => "CHA",
+        }
+    }
+
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Ability> {
+        Ability::all()
+            .into_iter()
+            .find(|ability| ability.abbreviation().eq_ignore_ascii_case(abbreviation))
+    }
+
+    /// Find an ability by its full name, e.g. `dexterity`, or by its abbreviation, e.g. `DEX`.
+    pub fn from_name(name: &str) -> Option<Ability> {
+        Ability::all()
+            .into_iter()
+            .find(|ability| format!("{:?}", ability).eq_ignore_ascii_case(name))
+            .or_else(|| Ability::from_abbreviation(name))
+    }
+}
+
+impl<'de> Deserialize<'de> for Ability {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const NAMES: &[&str] = &[
+            "strength",
+            "dexterity",
+            "constitution",
+            "intelligence",
+            "wisdom",
+            "charisma",
+        ];
+
+        let name = String::deserialize(deserializer)?;
+        Ability::from_name(&name).ok_or_else(|| de::Error::unknown_variant(&name, NAMES))
+    }
+}
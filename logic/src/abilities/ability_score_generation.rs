@@ -0,0 +1,411 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+use crate::utilities::{DiceGroup, DiceModifier, DiceRng, DiceSelection};
+
+/// The scores a player assigns with the standard array method.
+pub const STANDARD_ARRAY: [i32; 6] = [15, 14, 13, 12, 10, 8];
+
+/// The scores a player assigns with the heroic array method.
+pub const HEROIC_ARRAY: [i32; 6] = [17, 16, 14, 13, 12, 10];
+
+/// The number of points a player spends with the point-buy method, unless the world says
+/// otherwise.
+pub const DEFAULT_POINT_BUY_BUDGET: i32 = 27;
+
+/// ## default_point_buy_costs
+/// The cost of each score that can be bought with the point-buy method.  Every ability starts
+/// at 8 for free, and 15 is the highest score that can be bought.
+pub fn default_point_buy_costs() -> BTreeMap<i32, i32> {
+    BTreeMap::from([
+        (8, 0),
+        (9, 1),
+        (10, 2),
+        (11, 3),
+        (12, 4),
+        (13, 5),
+        (14, 7),
+        (15, 9),
+    ])
+}
+
+/// ## AbilityScoreMethod
+/// How a new character's ability scores are produced.  Each world chooses one, so it can be
+/// read from world data, e.g. `{ "method": "point_buy", "budget": 25, "costs": [[8, 0], ...] }`.
+///
+/// ### Example
+/// ```
+/// use std::collections::HashMap;
+/// use crate::logic::abilities::{Ability, AbilityScoreMethod};
+///
+/// let method = AbilityScoreMethod::standard_array();
+/// let pool = method.score_pool(&mut rand::thread_rng());
+///
+/// let scores: HashMap<Ability, i32> = Ability::all().into_iter().zip(pool.clone()).collect();
+/// assert!(method.validate(&scores, &pool).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum AbilityScoreMethod {
+    /// Assign the [`STANDARD_ARRAY`] to the abilities in any order.
+    StandardArray,
+    /// Buy each score from `costs`, spending no more than `budget` points.  The costs are
+    /// written as `[score, cost]` pairs.
+    PointBuy {
+        budget: i32,
+        #[serde(with = "cost_table")]
+        costs: BTreeMap<i32, i32>,
+    },
+    /// Roll 4d6 six times, dropping the lowest die each time, and assign the totals in any
+    /// order.
+    #[serde(rename = "4d6_drop_lowest")]
+    FourDiceDropLowest,
+    /// Assign a fixed set of scores, [`HEROIC_ARRAY`] by default, in any order.
+    HeroicArray { scores: Vec<i32> },
+}
+
+impl Default for AbilityScoreMethod {
+    fn default() -> Self {
+        Self::point_buy(DEFAULT_POINT_BUY_BUDGET)
+    }
+}
+
+impl AbilityScoreMethod {
+    pub fn standard_array() -> Self {
+        AbilityScoreMethod::StandardArray
+    }
+
+    /// Point-buy with the [`default_point_buy_costs`] and the given budget.
+    pub fn point_buy(budget: i32) -> Self {
+        AbilityScoreMethod::PointBuy {
+            budget,
+            costs: default_point_buy_costs(),
+        }
+    }
+
+    pub fn four_dice_drop_lowest() -> Self {
+        AbilityScoreMethod::FourDiceDropLowest
+    }
+
+    pub fn heroic_array() -> Self {
+        AbilityScoreMethod::HeroicArray {
+            scores: HEROIC_ARRAY.to_vec(),
+        }
+    }
+
+    /// The code a world stores to select the method, e.g. `point_buy`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AbilityScoreMethod::StandardArray => "standard_array",
+            AbilityScoreMethod::PointBuy { .. } => "point_buy",
+            AbilityScoreMethod::FourDiceDropLowest => "4d6_drop_lowest",
+            AbilityScoreMethod::HeroicArray { .. } => "heroic_array",
+        }
+    }
+
+    /// ## from_code
+    /// The method a world selects by code, with the default cost table and scores.  The budget
+    /// is only used by point-buy.
+    ///
+    /// ### Arguments
+    /// * `code` - The method code, see [`AbilityScoreMethod::code`].
+    /// * `budget` - The number of points a point-buy character may spend.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::abilities::AbilityScoreMethod;
+    ///
+    /// assert_eq!(
+    ///     AbilityScoreMethod::from_code("point_buy", 20),
+    ///     Some(AbilityScoreMethod::point_buy(20))
+    /// );
+    /// assert_eq!(AbilityScoreMethod::from_code("3d6_in_order", 20), None);
+    /// ```
+    pub fn from_code(code: &str, budget: i32) -> Option<Self> {
+        match code {
+            "standard_array" => Some(Self::standard_array()),
+            "point_buy" => Some(Self::point_buy(budget)),
+            "4d6_drop_lowest" => Some(Self::four_dice_drop_lowest()),
+            "heroic_array" => Some(Self::heroic_array()),
+            _ => None,
+        }
+    }
+
+    /// ## score_pool
+    /// The scores a player assigns to their abilities: the array for the array methods, six
+    /// fresh 4d6-drop-lowest rolls, or nothing for point-buy, where every score is bought.
+    ///
+    /// ### Arguments
+    /// * `rng` - The generator used for rolled scores.
+    pub fn score_pool<R: DiceRng + ?Sized>(&self, rng: &mut R) -> Vec<i32> {
+        match self {
+            AbilityScoreMethod::StandardArray => STANDARD_ARRAY.to_vec(),
+            AbilityScoreMethod::PointBuy { .. } => vec![],
+            AbilityScoreMethod::FourDiceDropLowest => {
+                let dice = DiceGroup {
+                    count: 4,
+                    sides: 6,
+                    modifiers: vec![DiceModifier::Drop(DiceSelection::Lowest, 1)],
+                };
+                Ability::all()
+                    .iter()
+                    .map(|_| dice.roll(rng).expect("4d6 always fits in an i32").total)
+                    .collect()
+            }
+            AbilityScoreMethod::HeroicArray { scores } => scores.clone(),
+        }
+    }
+
+    /// ## validate
+    /// Check a submitted set of scores against the method.
+    ///
+    /// ### Arguments
+    /// * `scores` - The score submitted for each ability.
+    /// * `pool` - The scores the player was given by [`AbilityScoreMethod::score_pool`].  Only
+    ///   rolled scores need it; the array methods check their own arrays and point-buy ignores it.
+    ///
+    /// ### Returns
+    /// Nothing, or every rule the scores break, e.g. `point-buy spends 29 points, but the budget
+    /// is 27`.
+    ///
+    /// ### Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use crate::logic::abilities::{Ability, AbilityScoreMethod};
+    ///
+    /// let scores: HashMap<Ability, i32> =
+    ///     Ability::all().into_iter().zip([15, 15, 15, 10, 8, 8]).collect();
+    /// assert_eq!(
+    ///     AbilityScoreMethod::point_buy(27).validate(&scores, &[]),
+    ///     Err(vec!["point-buy spends 29 points, but the budget is 27".to_string()])
+    /// );
+    /// ```
+    pub fn validate(
+        &self,
+        scores: &HashMap<Ability, i32>,
+        pool: &[i32],
+    ) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = Ability::all()
+            .iter()
+            .filter(|ability| !scores.contains_key(ability))
+            .map(|ability| format!("missing a score for {}", ability.abbreviation()))
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let assigned: Vec<i32> = Ability::all()
+            .iter()
+            .map(|ability| scores[ability])
+            .collect();
+        match self {
+            AbilityScoreMethod::StandardArray => {
+                errors.extend(check_pool(&assigned, &STANDARD_ARRAY, "the standard array"))
+            }
+            AbilityScoreMethod::HeroicArray { scores } => {
+                errors.extend(check_pool(&assigned, scores, "the heroic array"))
+            }
+            AbilityScoreMethod::FourDiceDropLowest => {
+                errors.extend(check_pool(&assigned, pool, "the rolled scores"))
+            }
+            AbilityScoreMethod::PointBuy { budget, costs } => {
+                let mut spent = 0;
+                for ability in Ability::all() {
+                    let score = scores[&ability];
+                    match costs.get(&score) {
+                        Some(cost) => spent += cost,
+                        None => errors.push(format!(
+                            "{} {} cannot be bought, scores range from {} to {}",
+                            ability.abbreviation(),
+                            score,
+                            costs.keys().next().copied().unwrap_or_default(),
+                            costs.keys().next_back().copied().unwrap_or_default(),
+                        )),
+                    }
+                }
+                if errors.is_empty() && spent > *budget {
+                    errors.push(format!(
+                        "point-buy spends {} points, but the budget is {}",
+                        spent, budget
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+mod cost_table {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        costs: &BTreeMap<i32, i32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        costs.iter().collect::<Vec<_>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<i32, i32>, D::Error> {
+        Ok(Vec::<(i32, i32)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Check that the assigned scores use each score of the pool exactly once.
+fn check_pool(assigned: &[i32], pool: &[i32], name: &str) -> Vec<String> {
+    let mut assigned = assigned.to_vec();
+    let mut expected = pool.to_vec();
+    assigned.sort_unstable_by(|a, b| b.cmp(a));
+    expected.sort_unstable_by(|a, b| b.cmp(a));
+
+    if assigned == expected {
+        return vec![];
+    }
+
+    let list = |scores: &[i32]| {
+        scores
+            .iter()
+            .map(|score| score.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    vec![format!(
+        "scores must be {} ({}), but were {}",
+        name,
+        list(&expected),
+        list(&assigned)
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::ScriptedRng;
+
+    fn scores(values: [i32; 6]) -> HashMap<Ability, i32> {
+        Ability::all().into_iter().zip(values).collect()
+    }
+
+    #[test]
+    fn test_standard_and_heroic_arrays() {
+        let standard = AbilityScoreMethod::standard_array();
+        assert!(standard
+            .validate(&scores([8, 10, 12, 13, 14, 15]), &[])
+            .is_ok());
+        assert_eq!(
+            standard.validate(&scores([15, 15, 13, 12, 10, 8]), &[]),
+            Err(vec![
+                "scores must be the standard array (15, 14, 13, 12, 10, 8), but were 15, 15, 13, 12, 10, 8"
+                    .to_string()
+            ])
+        );
+
+        let heroic = AbilityScoreMethod::heroic_array();
+        assert_eq!(
+            heroic.score_pool(&mut rand::thread_rng()),
+            HEROIC_ARRAY.to_vec()
+        );
+        assert!(heroic.validate(&scores(HEROIC_ARRAY), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_point_buy() {
+        let method = AbilityScoreMethod::point_buy(27);
+        assert!(method.score_pool(&mut rand::thread_rng()).is_empty());
+        assert!(method.validate(&scores([15, 15, 15, 8, 8, 8]), &[]).is_ok());
+        assert!(method
+            .validate(&scores([14, 14, 14, 12, 10, 8]), &[])
+            .is_ok());
+
+        let errors = method
+            .validate(&scores([18, 15, 15, 8, 8, 7]), &[])
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "STR 18 cannot be bought, scores range from 8 to 15",
+                "CHA 7 cannot be bought, scores range from 8 to 15"
+            ]
+        );
+
+        let cheap = AbilityScoreMethod::point_buy(10);
+        assert_eq!(
+            cheap.validate(&scores([12, 12, 12, 8, 8, 8]), &[]),
+            Err(vec![
+                "point-buy spends 12 points, but the budget is 10".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rolled_scores_must_match_the_pool() {
+        let method = AbilityScoreMethod::four_dice_drop_lowest();
+        // each score drops its lowest die: 6+5+4, 3+3+3, 6+6+6, 2+2+2, 5+4+3, 1+1+1
+        let mut rng = ScriptedRng::new(vec![
+            6, 5, 4, 1, 3, 3, 3, 3, 6, 6, 6, 6, 2, 2, 2, 1, 5, 4, 3, 2, 1, 1, 1, 1,
+        ]);
+
+        let pool = method.score_pool(&mut rng);
+        assert_eq!(pool, vec![15, 9, 18, 6, 12, 3]);
+        assert!(method
+            .validate(&scores([18, 15, 12, 9, 6, 3]), &pool)
+            .is_ok());
+        assert!(method
+            .validate(&scores([18, 18, 12, 9, 6, 3]), &pool)
+            .is_err());
+    }
+
+    #[test]
+    fn test_missing_abilities_are_reported() {
+        let mut partial = scores(STANDARD_ARRAY);
+        partial.remove(&Ability::Wisdom);
+
+        assert_eq!(
+            AbilityScoreMethod::standard_array().validate(&partial, &[]),
+            Err(vec!["missing a score for WIS".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_world_selects_method_by_code() {
+        for method in [
+            AbilityScoreMethod::standard_array(),
+            AbilityScoreMethod::point_buy(20),
+            AbilityScoreMethod::four_dice_drop_lowest(),
+            AbilityScoreMethod::heroic_array(),
+        ] {
+            assert_eq!(
+                AbilityScoreMethod::from_code(method.code(), 20),
+                Some(method)
+            );
+        }
+
+        let json = r#"{ "method": "point_buy", "budget": 5, "costs": [[10, 0], [12, 5]] }"#;
+        let method: AbilityScoreMethod = serde_json::from_str(json).unwrap();
+        assert!(method
+            .validate(&scores([12, 10, 10, 10, 10, 10]), &[])
+            .is_ok());
+        assert!(method
+            .validate(&scores([12, 12, 10, 10, 10, 10]), &[])
+            .is_err());
+
+        let json = serde_json::to_string(&AbilityScoreMethod::point_buy(27)).unwrap();
+        assert!(json.starts_with(r#"{"method":"point_buy","budget":27,"costs":[[8,0],[9,1],"#));
+        assert_eq!(
+            serde_json::from_str::<AbilityScoreMethod>(&json).unwrap(),
+            AbilityScoreMethod::point_buy(27)
+        );
+    }
+}
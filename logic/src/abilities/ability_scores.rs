@@ -1,6 +1,23 @@
 use crate::abilities::ability::Ability;
 use crate::abilities::detailed_ability_score::DetailedAbilityScore;
 
+/// ## ability_modifier
+/// The modifier for an ability score: half the difference from 10, rounded down, so a 9 is -1
+/// and a 7 is -2.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::ability_modifier;
+///
+/// assert_eq!(ability_modifier(14), 2);
+/// assert_eq!(ability_modifier(11), 0);
+/// assert_eq!(ability_modifier(9), -1);
+/// assert_eq!(ability_modifier(7), -2);
+/// ```
+pub fn ability_modifier(score: i32) -> i32 {
+    (score - 10).div_euclid(2)
+}
+
 pub trait AbilityScores {
     fn get_ability_score(&self, ability: Ability) -> i32;
     fn get_raw_ability_score(&self, ability: Ability) -> i32;
@@ -1,9 +1,40 @@
 use crate::abilities::ability::Ability;
+use crate::bonuses::AppliedBonus;
 use std::collections::HashMap;
 
+/// ## DetailedAbilityScore
+/// An ability score with everything that went into it, so players can see why a score is what
+/// it is.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct DetailedAbilityScore {
     pub ability: Ability,
+    /// The score after every applied bonus.
     pub score: i32,
+    /// The total of the applied bonuses of each type, keyed by type name, e.g. `racial`.
     pub modifiers: HashMap<String, i32>,
+    /// The score before any bonus.
+    pub base: i32,
+    /// Every bonus to the ability, including the ones that did not stack.
+    pub bonuses: Vec<AppliedBonus>,
+}
+
+impl DetailedAbilityScore {
+    /// Build the breakdown of a score from its base and the bonuses to it.
+    pub fn new(ability: Ability, base: i32, bonuses: Vec<AppliedBonus>) -> Self {
+        let mut modifiers = HashMap::new();
+        for applied in bonuses.iter().filter(|applied| applied.applied) {
+            *modifiers
+                .entry(applied.bonus.bonus_type.name().to_string())
+                .or_insert(0) += applied.bonus.value;
+        }
+        let score = base + modifiers.values().sum::<i32>();
+
+        Self {
+            ability,
+            score,
+            modifiers,
+            base,
+            bonuses,
+        }
+    }
 }
@@ -1,7 +1,9 @@
 pub mod ability;
+pub mod ability_score_generation;
 pub mod ability_scores;
 pub mod detailed_ability_score;
 
 pub use ability::*;
+pub use ability_score_generation::*;
 pub use ability_scores::*;
 pub use detailed_ability_score::*;
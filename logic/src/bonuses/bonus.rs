@@ -0,0 +1,120 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+
+/// ## BonusType
+/// The kind of a bonus, which decides whether it stacks with others of the same kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BonusType {
+    Racial,
+    Item,
+    Enhancement,
+    Circumstance,
+    Morale,
+    Insight,
+    Luck,
+    Competence,
+    Dodge,
+    Temporary,
+    Untyped,
+}
+
+impl BonusType {
+    /// The name used for the bonus type in breakdowns and data, e.g. `enhancement`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BonusType::Racial => "racial",
+            BonusType::Item => "item",
+            BonusType::Enhancement => "enhancement",
+            BonusType::Circumstance => "circumstance",
+            BonusType::Morale => "morale",
+            BonusType::Insight => "insight",
+            BonusType::Luck => "luck",
+            BonusType::Competence => "competence",
+            BonusType::Dodge => "dodge",
+            BonusType::Temporary => "temporary",
+            BonusType::Untyped => "untyped",
+        }
+    }
+
+    /// Whether two bonuses of this type both apply.  Untyped, dodge and circumstance bonuses
+    /// stack; for every other type only the largest bonus applies.
+    pub fn stacks(&self) -> bool {
+        matches!(
+            self,
+            BonusType::Untyped | BonusType::Dodge | BonusType::Circumstance
+        )
+    }
+}
+
+impl fmt::Display for BonusType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// ## BonusTarget
+/// What a bonus changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "target", content = "name", rename_all = "snake_case")]
+pub enum BonusTarget {
+    Ability(Ability),
+    /// A skill, by code, e.g. `athletics`.
+    Skill(String),
+    /// A saving throw, by code, e.g. `fortitude`.
+    Save(String),
+    ArmorClass,
+    AttackRoll,
+    DamageRoll,
+    Initiative,
+    Speed,
+}
+
+/// ## Bonus
+/// A bonus, or a penalty when the value is negative, from a named source such as a race, an
+/// item or a spell.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::bonuses::{Bonus, BonusTarget, BonusType};
+///
+/// let belt = Bonus::new(
+///     "Belt of Giant Strength",
+///     BonusType::Enhancement,
+///     BonusTarget::Ability(Ability::Strength),
+///     4,
+/// );
+/// assert_eq!(belt.to_string(), "+4 enhancement (Belt of Giant Strength)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bonus {
+    pub source: String,
+    pub bonus_type: BonusType,
+    pub target: BonusTarget,
+    pub value: i32,
+}
+
+impl Bonus {
+    pub fn new(source: &str, bonus_type: BonusType, target: BonusTarget, value: i32) -> Self {
+        Self {
+            source: source.to_string(),
+            bonus_type,
+            target,
+            value,
+        }
+    }
+
+    pub fn is_penalty(&self) -> bool {
+        self.value < 0
+    }
+}
+
+impl fmt::Display for Bonus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+} {} ({})", self.value, self.bonus_type, self.source)
+    }
+}
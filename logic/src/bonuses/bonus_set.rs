@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Bonus, BonusTarget};
+
+/// ## AppliedBonus
+/// A bonus together with whether it counted towards the total, so a breakdown can show why a
+/// bonus was ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedBonus {
+    pub bonus: Bonus,
+    pub applied: bool,
+    /// Why the bonus did not apply, e.g. `does not stack with +4 enhancement (Belt of Giant
+    /// Strength)`.
+    pub reason: Option<String>,
+}
+
+/// ## BonusSet
+/// Every bonus and penalty affecting a creature, from any source.
+///
+/// Bonuses of the same type do not stack, so only the largest applies, unless the type stacks
+/// (see [`super::BonusType::stacks`]).  Penalties always stack.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::bonuses::{Bonus, BonusSet, BonusTarget, BonusType};
+///
+/// let strength = BonusTarget::Ability(Ability::Strength);
+/// let mut bonuses = BonusSet::new();
+/// bonuses.add(Bonus::new("Bull's Strength", BonusType::Enhancement, strength.clone(), 4));
+/// bonuses.add(Bonus::new("Belt of Might", BonusType::Enhancement, strength.clone(), 2));
+/// bonuses.add(Bonus::new("Rage", BonusType::Morale, strength.clone(), 2));
+/// bonuses.add(Bonus::new("Exhausted", BonusType::Untyped, strength.clone(), -3));
+///
+/// assert_eq!(bonuses.total(&strength), 4 + 2 - 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BonusSet {
+    bonuses: Vec<Bonus>,
+}
+
+impl BonusSet {
+    pub fn new() -> Self {
+        Self { bonuses: vec![] }
+    }
+
+    pub fn add(&mut self, bonus: Bonus) {
+        self.bonuses.push(bonus);
+    }
+
+    pub fn extend<I: IntoIterator<Item = Bonus>>(&mut self, bonuses: I) {
+        self.bonuses.extend(bonuses);
+    }
+
+    /// Remove every bonus from a source, e.g. when an item is unequipped or a spell ends.
+    /// Returns how many bonuses were removed.
+    pub fn remove_source(&mut self, source: &str) -> usize {
+        let before = self.bonuses.len();
+        self.bonuses.retain(|bonus| bonus.source != source);
+        before - self.bonuses.len()
+    }
+
+    pub fn bonuses(&self) -> &[Bonus] {
+        &self.bonuses
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bonuses.is_empty()
+    }
+
+    /// Every bonus that affects `target`, in the order they were added.
+    pub fn for_target<'a>(&'a self, target: &'a BonusTarget) -> impl Iterator<Item = &'a Bonus> {
+        self.bonuses
+            .iter()
+            .filter(move |bonus| &bonus.target == target)
+    }
+
+    /// ## applied
+    /// Every bonus that affects `target`, each marked with whether it applies under the
+    /// stacking rules.
+    pub fn applied(&self, target: &BonusTarget) -> Vec<AppliedBonus> {
+        let bonuses: Vec<&Bonus> = self.for_target(target).collect();
+
+        bonuses
+            .iter()
+            .enumerate()
+            .map(|(index, bonus)| {
+                // the first of the largest bonuses of a type wins
+                let winner = (!bonus.is_penalty() && !bonus.bonus_type.stacks())
+                    .then(|| {
+                        bonuses
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, other)| {
+                                other.bonus_type == bonus.bonus_type && !other.is_penalty()
+                            })
+                            .max_by(|(a_index, a), (b_index, b)| {
+                                a.value.cmp(&b.value).then(b_index.cmp(a_index))
+                            })
+                    })
+                    .flatten();
+
+                match winner {
+                    Some((winner_index, winner)) if winner_index != index => AppliedBonus {
+                        bonus: (*bonus).clone(),
+                        applied: false,
+                        reason: Some(format!("does not stack with {}", winner)),
+                    },
+                    _ => AppliedBonus {
+                        bonus: (*bonus).clone(),
+                        applied: true,
+                        reason: None,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// The sum of every bonus to `target` that applies under the stacking rules.
+    pub fn total(&self, target: &BonusTarget) -> i32 {
+        self.applied(target)
+            .iter()
+            .filter(|applied| applied.applied)
+            .map(|applied| applied.bonus.value)
+            .sum()
+    }
+}
+
+impl FromIterator<Bonus> for BonusSet {
+    fn from_iter<I: IntoIterator<Item = Bonus>>(iter: I) -> Self {
+        Self {
+            bonuses: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Ability;
+    use crate::bonuses::BonusType;
+
+    fn dexterity() -> BonusTarget {
+        BonusTarget::Ability(Ability::Dexterity)
+    }
+
+    #[test]
+    fn test_same_type_bonuses_do_not_stack() {
+        let bonuses: BonusSet = [
+            Bonus::new("Cat's Grace", BonusType::Enhancement, dexterity(), 4),
+            Bonus::new(
+                "Gloves of Dexterity",
+                BonusType::Enhancement,
+                dexterity(),
+                2,
+            ),
+            Bonus::new("Lucky Coin", BonusType::Luck, dexterity(), 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let applied = bonuses.applied(&dexterity());
+        assert!(applied[0].applied);
+        assert!(!applied[1].applied);
+        assert_eq!(
+            applied[1].reason.as_deref(),
+            Some("does not stack with +4 enhancement (Cat's Grace)")
+        );
+        assert!(applied[2].applied);
+        assert_eq!(bonuses.total(&dexterity()), 5);
+    }
+
+    #[test]
+    fn test_stacking_types_and_penalties_always_apply() {
+        let bonuses: BonusSet = [
+            Bonus::new("Cover", BonusType::Circumstance, dexterity(), 2),
+            Bonus::new("High Ground", BonusType::Circumstance, dexterity(), 1),
+            Bonus::new("Blessing", BonusType::Untyped, dexterity(), 1),
+            Bonus::new("Blessing", BonusType::Untyped, dexterity(), 1),
+            Bonus::new("Entangled", BonusType::Enhancement, dexterity(), -4),
+            Bonus::new("Slowed", BonusType::Enhancement, dexterity(), -2),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(bonuses
+            .applied(&dexterity())
+            .iter()
+            .all(|applied| applied.applied));
+        assert_eq!(bonuses.total(&dexterity()), 2 + 1 + 1 + 1 - 4 - 2);
+    }
+
+    #[test]
+    fn test_equal_bonuses_apply_once() {
+        let bonuses: BonusSet = [
+            Bonus::new("Heroism", BonusType::Morale, dexterity(), 2),
+            Bonus::new("Inspire Courage", BonusType::Morale, dexterity(), 2),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(bonuses.total(&dexterity()), 2);
+    }
+
+    #[test]
+    fn test_remove_source_and_targets() {
+        let mut bonuses = BonusSet::new();
+        bonuses.add(Bonus::new(
+            "Haste",
+            BonusType::Dodge,
+            BonusTarget::ArmorClass,
+            1,
+        ));
+        bonuses.add(Bonus::new(
+            "Haste",
+            BonusType::Untyped,
+            BonusTarget::AttackRoll,
+            1,
+        ));
+        bonuses.add(Bonus::new(
+            "Ring",
+            BonusType::Item,
+            BonusTarget::ArmorClass,
+            1,
+        ));
+
+        assert_eq!(bonuses.total(&BonusTarget::ArmorClass), 2);
+        assert_eq!(bonuses.total(&dexterity()), 0);
+
+        assert_eq!(bonuses.remove_source("Haste"), 2);
+        assert_eq!(bonuses.total(&BonusTarget::ArmorClass), 1);
+        assert_eq!(bonuses.total(&BonusTarget::AttackRoll), 0);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod bonus;
+pub mod bonus_set;
+
+pub use bonus::*;
+pub use bonus_set::*;
@@ -0,0 +1,72 @@
+use crate::utilities::{CompiledExpression, ExpressionError};
+
+/// ## ClassFeature
+/// A feature a class grants when a character reaches `level`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassFeature {
+    pub level: i32,
+    pub code: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// ## CharacterClass
+/// The rules of a class that levelling needs, with its expressions compiled once.
+///
+/// ### Example
+/// ```
+/// use crate::logic::classes::CharacterClass;
+///
+/// let mechanic = CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT").unwrap();
+/// assert_eq!(mechanic.skillpoint_expression.source(), "4 + INT");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterClass {
+    pub code: String,
+    pub name: String,
+    /// The hit points gained each level, and the size of the die rolled for them.
+    pub hit_points: i32,
+    pub stamina_expression: CompiledExpression,
+    pub skillpoint_expression: CompiledExpression,
+    pub features: Vec<ClassFeature>,
+}
+
+impl CharacterClass {
+    /// ## new
+    /// Create a class without features, compiling its expressions.
+    ///
+    /// ### Arguments
+    /// * `code` - The class code, e.g. `mechanic`.
+    /// * `name` - The display name, e.g. `Mechanic`.
+    /// * `hit_points` - The hit points gained each level.
+    /// * `stamina_expression` - The stamina gained each level, e.g. `10 + CON`.
+    /// * `skillpoint_expression` - The skill ranks gained each level, e.g. `4 + INT`.
+    pub fn new(
+        code: &str,
+        name: &str,
+        hit_points: i32,
+        stamina_expression: &str,
+        skillpoint_expression: &str,
+    ) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            hit_points,
+            stamina_expression: CompiledExpression::compile(stamina_expression)?,
+            skillpoint_expression: CompiledExpression::compile(skillpoint_expression)?,
+            features: vec![],
+        })
+    }
+
+    pub fn with_feature(mut self, feature: ClassFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// The features granted on reaching `level`.
+    pub fn features_at(&self, level: i32) -> impl Iterator<Item = &ClassFeature> {
+        self.features
+            .iter()
+            .filter(move |feature| feature.level == level)
+    }
+}
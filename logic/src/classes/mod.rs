@@ -0,0 +1,3 @@
+pub mod character_class;
+
+pub use character_class::*;
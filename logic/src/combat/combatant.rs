@@ -0,0 +1,443 @@
+use serde::{Deserialize, Serialize};
+
+use super::CombatError;
+use crate::abilities::{Ability, AbilityScores};
+use crate::bonuses::BonusTarget;
+use crate::damage::DamageModifier;
+use crate::enemies::Enemy;
+use crate::entities::Character;
+use crate::game_data::GameData;
+use crate::items::{Item, Weapon};
+
+/// The armor class of a combatant with no armor and no dexterity modifier.
+pub const BASE_ARMOR_CLASS: i32 = 10;
+
+/// ## Attack
+/// A way a combatant can attack: a weapon and the bonuses they use it with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attack {
+    /// The name shown in the combat log, e.g. `Blaster Rifle`.
+    pub name: String,
+    pub weapon: Weapon,
+    /// Added to the d20 attack roll.
+    pub attack_bonus: i32,
+    /// Added to the weapon's damage.
+    pub damage_bonus: i32,
+}
+
+impl Attack {
+    pub fn new(name: &str, weapon: Weapon, attack_bonus: i32, damage_bonus: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            weapon,
+            attack_bonus,
+            damage_bonus,
+        }
+    }
+
+    /// A `1d3` kinetic melee attack, for a combatant with no usable weapon.
+    pub fn unarmed(attack_bonus: i32, damage_bonus: i32) -> Self {
+        Self::new(
+            "Unarmed Strike",
+            Weapon {
+                damage: "1d3".to_string(),
+                damage_type: "kinetic".to_string(),
+                range: 0,
+                properties: vec![],
+            },
+            attack_bonus,
+            damage_bonus,
+        )
+    }
+
+    pub fn is_melee(&self) -> bool {
+        self.weapon.range == 0
+    }
+
+    /// The damage expression with the damage bonus added, e.g. `1d8+2`.
+    pub fn damage_expression(&self) -> String {
+        match self.damage_bonus {
+            0 => self.weapon.damage.clone(),
+            bonus => format!("{}{:+}", self.weapon.damage, bonus),
+        }
+    }
+}
+
+/// ## Combatant
+/// A creature taking part in an encounter, with what it needs to fight and no more.  Damage is
+/// taken from stamina first and then from hit points; a combatant with no hit points left is
+/// defeated.
+///
+/// ### Example
+/// ```
+/// use crate::logic::combat::Combatant;
+///
+/// let mut pirate = Combatant::new("Pirate", "enemies", 10, 5, 13);
+/// assert_eq!(pirate.take_damage(8), (5, 3));
+/// assert_eq!(pirate.hit_points, 7);
+/// assert!(!pirate.is_defeated());
+///
+/// pirate.take_damage(20);
+/// assert_eq!(pirate.hit_points, 0);
+/// assert!(pirate.is_defeated());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Combatant {
+    pub name: String,
+    /// The side the combatant fights for, e.g. `players`.  Combat ends when one side is left.
+    pub side: String,
+    pub hit_points: i32,
+    pub max_hit_points: i32,
+    pub stamina: i32,
+    pub max_stamina: i32,
+    pub armor_class: i32,
+    pub initiative_bonus: i32,
+    pub attacks: Vec<Attack>,
+    /// The resistances, immunities and vulnerabilities applied to damage the combatant takes.
+    pub damage_modifiers: Vec<DamageModifier>,
+}
+
+impl Combatant {
+    /// A combatant at full hit points and stamina, with no initiative bonus or attacks.
+    pub fn new(name: &str, side: &str, hit_points: i32, stamina: i32, armor_class: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            side: side.to_string(),
+            hit_points,
+            max_hit_points: hit_points,
+            stamina,
+            max_stamina: stamina,
+            armor_class,
+            initiative_bonus: 0,
+            attacks: vec![],
+            damage_modifiers: vec![],
+        }
+    }
+
+    pub fn with_initiative_bonus(mut self, initiative_bonus: i32) -> Self {
+        self.initiative_bonus = initiative_bonus;
+        self
+    }
+
+    pub fn with_attack(mut self, attack: Attack) -> Self {
+        self.attacks.push(attack);
+        self
+    }
+
+    pub fn with_damage_modifier(mut self, damage_modifier: DamageModifier) -> Self {
+        self.damage_modifiers.push(damage_modifier);
+        self
+    }
+
+    /// ## from_enemy
+    /// A combatant for an enemy, with its weapons and armor looked up in the world's items.
+    ///
+    /// * Armor class is 10 plus the DEX modifier and the armor's `armor_bonus`.
+    /// * Initiative is the DEX modifier.
+    /// * Damage modifiers are the enemy's own and its armor's.
+    /// * Each weapon attacks with the enemy's level plus STR for melee or DEX for ranged
+    ///   weapons, and adds STR to melee damage.  Weapons the world does not define, or items that
+    ///   are not weapons, are skipped, and an enemy left with none fights unarmed.
+    ///
+    /// ### Arguments
+    /// * `enemy` - The enemy.
+    /// * `side` - The side it fights for.
+    /// * `game_data` - The content its items are looked up in.
+    /// * `world` - The world the enemy belongs to.
+    ///
+    /// ### Returns
+    /// The combatant, or [`CombatError::InvalidItem`] if its armor's bonus or damage modifiers
+    /// cannot be read.
+    pub fn from_enemy(
+        enemy: &Enemy,
+        side: &str,
+        game_data: &GameData,
+        world: &str,
+    ) -> Result<Self, CombatError> {
+        let strength = enemy.ability_modifier(Ability::Strength);
+        let dexterity = enemy.ability_modifier(Ability::Dexterity);
+        let armor = enemy
+            .armor
+            .as_ref()
+            .and_then(|armor| game_data.item(world, &armor.code));
+        let armor_bonus = armor_bonus(armor)?;
+
+        let mut combatant = Combatant::new(
+            &enemy.name,
+            side,
+            enemy.hit_points,
+            enemy.stamina,
+            BASE_ARMOR_CLASS
+                .saturating_add(dexterity)
+                .saturating_add(armor_bonus),
+        )
+        .with_initiative_bonus(dexterity);
+        combatant.damage_modifiers = enemy.damage_modifiers.clone();
+        combatant
+            .damage_modifiers
+            .extend(armor_damage_modifiers(armor)?);
+
+        for equipment in &enemy.weapons {
+            if let Some(item) = game_data.item(world, &equipment.code) {
+                if let Some(attack) = weapon_attack(item, enemy.level, strength, dexterity, 0, 0) {
+                    combatant = combatant.with_attack(attack);
+                }
+            }
+        }
+        if combatant.attacks.is_empty() {
+            combatant = combatant.with_attack(Attack::unarmed(
+                enemy.level.saturating_add(strength),
+                strength,
+            ));
+        }
+        Ok(combatant)
+    }
+
+    /// ## from_character
+    /// A combatant for a character at their current hit points and stamina.
+    ///
+    /// * Armor class is 10 plus the DEX modifier, the armor's `armor_bonus` and any armor class
+    ///   bonuses.
+    /// * Initiative is the DEX modifier plus any initiative bonuses.
+    /// * Damage modifiers are the armor's.
+    /// * Each weapon attacks as an enemy's does, plus any attack and damage roll bonuses.
+    ///
+    /// ### Arguments
+    /// * `character` - The character.
+    /// * `side` - The side they fight for.
+    /// * `weapons` - The weapons they carry.  Items that are not weapons are skipped.
+    /// * `armor` - The armor they wear, if any.
+    ///
+    /// ### Returns
+    /// The combatant, or [`CombatError::InvalidItem`] if the armor's bonus or damage modifiers
+    /// cannot be read.
+    pub fn from_character(
+        character: &Character,
+        side: &str,
+        weapons: &[Item],
+        armor: Option<&Item>,
+    ) -> Result<Self, CombatError> {
+        let strength = character.get_modifier(Ability::Strength);
+        let dexterity = character.get_modifier(Ability::Dexterity);
+        let attack_bonus = character.bonus_total(&BonusTarget::AttackRoll);
+        let damage_bonus = character.bonus_total(&BonusTarget::DamageRoll);
+
+        let mut combatant = Combatant::new(
+            character.name(),
+            side,
+            character.hit_points(),
+            character.stamina(),
+            BASE_ARMOR_CLASS
+                .saturating_add(dexterity)
+                .saturating_add(armor_bonus(armor)?)
+                .saturating_add(character.bonus_total(&BonusTarget::ArmorClass)),
+        )
+        .with_initiative_bonus(
+            dexterity.saturating_add(character.bonus_total(&BonusTarget::Initiative)),
+        );
+        combatant.damage_modifiers = armor_damage_modifiers(armor)?;
+
+        for item in weapons {
+            if let Some(attack) = weapon_attack(
+                item,
+                character.level(),
+                strength,
+                dexterity,
+                attack_bonus,
+                damage_bonus,
+            ) {
+                combatant = combatant.with_attack(attack);
+            }
+        }
+        if combatant.attacks.is_empty() {
+            combatant = combatant.with_attack(Attack::unarmed(
+                character
+                    .level()
+                    .saturating_add(strength)
+                    .saturating_add(attack_bonus),
+                strength.saturating_add(damage_bonus),
+            ));
+        }
+        Ok(combatant)
+    }
+
+    pub fn is_defeated(&self) -> bool {
+        self.hit_points <= 0
+    }
+
+    /// ## take_damage
+    /// Take damage from stamina first, and whatever stamina cannot absorb from hit points.
+    /// Neither goes below 0.
+    ///
+    /// ### Returns
+    /// The stamina and the hit points lost.
+    pub fn take_damage(&mut self, damage: i32) -> (i32, i32) {
+        let damage = damage.max(0);
+        let stamina_lost = damage.min(self.stamina.max(0));
+        self.stamina -= stamina_lost;
+        let hit_points_lost = (damage - stamina_lost).min(self.hit_points.max(0));
+        self.hit_points -= hit_points_lost;
+        (stamina_lost, hit_points_lost)
+    }
+}
+
+fn armor_bonus(armor: Option<&Item>) -> Result<i32, CombatError> {
+    match armor {
+        Some(armor) => armor.armor_bonus().map_err(|error| {
+            CombatError::InvalidItem(format!("{} armor_bonus: {}", armor.code, error))
+        }),
+        None => Ok(0),
+    }
+}
+
+fn armor_damage_modifiers(armor: Option<&Item>) -> Result<Vec<DamageModifier>, CombatError> {
+    match armor {
+        Some(armor) => armor.damage_modifiers().map_err(|error| {
+            CombatError::InvalidItem(format!("{} damage_modifiers: {}", armor.code, error))
+        }),
+        None => Ok(vec![]),
+    }
+}
+
+fn weapon_attack(
+    item: &Item,
+    level: i32,
+    strength: i32,
+    dexterity: i32,
+    attack_bonus: i32,
+    damage_bonus: i32,
+) -> Option<Attack> {
+    let weapon = item.weapon()?;
+    let attack = if weapon.range == 0 {
+        Attack::new(
+            &item.name,
+            weapon,
+            level.saturating_add(strength).saturating_add(attack_bonus),
+            strength.saturating_add(damage_bonus),
+        )
+    } else {
+        Attack::new(
+            &item.name,
+            weapon,
+            level.saturating_add(dexterity).saturating_add(attack_bonus),
+            damage_bonus,
+        )
+    };
+    Some(attack)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::bonuses::{Bonus, BonusType};
+    use crate::game_data::load_world_directory;
+    use crate::races::load_default_races;
+
+    #[test]
+    fn test_from_enemy_uses_the_worlds_weapons() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/worlds/devgalaxy");
+        let game_data = load_world_directory(path).unwrap();
+        let pirate = game_data.enemy("devgalaxy", "pirate").unwrap();
+
+        let combatant = Combatant::from_enemy(pirate, "enemies", &game_data, "devgalaxy").unwrap();
+
+        // DEX 14 and light armor
+        assert_eq!(combatant.armor_class, 13);
+        assert_eq!(combatant.initiative_bonus, 2);
+        assert_eq!(combatant.attacks[0].name, "Blaster Rifle");
+        assert_eq!(combatant.attacks[0].attack_bonus, 3);
+        assert_eq!(combatant.attacks[0].damage_expression(), "1d12");
+
+        let skeleton = game_data.enemy("devgalaxy", "skeleton").unwrap();
+        let combatant =
+            Combatant::from_enemy(skeleton, "enemies", &game_data, "devgalaxy").unwrap();
+        assert_eq!(combatant.attacks.len(), 1);
+        assert_eq!(combatant.attacks[0].name, "Sword");
+        assert_eq!(combatant.attacks[0].damage_expression(), "1d8");
+    }
+
+    #[test]
+    fn test_from_enemy_rounds_low_modifiers_down() {
+        let enemy: Enemy = serde_json::from_value(serde_json::json!({
+            "code": "drone", "name": "Drone", "class": "robot", "level": 1,
+            "hit_points": 6, "stamina": 0, "strength": 7, "dexterity": 9, "constitution": 10,
+            "intelligence": 10, "wisdom": 10, "charisma": 10
+        }))
+        .unwrap();
+
+        let combatant =
+            Combatant::from_enemy(&enemy, "enemies", &GameData::new(), "devgalaxy").unwrap();
+
+        assert_eq!(combatant.armor_class, 9);
+        assert_eq!(combatant.initiative_bonus, -1);
+        assert_eq!(combatant.attacks[0].attack_bonus, -1);
+        assert_eq!(combatant.attacks[0].damage_expression(), "1d3-2");
+    }
+
+    #[test]
+    fn test_from_character_adds_bonuses() {
+        let mut character = Character::new(
+            "Kira".to_string(),
+            2,
+            "soldier".to_string(),
+            load_default_races()["Human"].clone(),
+            16,
+            14,
+            12,
+            10,
+            10,
+            10,
+        );
+        character.restore(0, 20, 14);
+        character.add_bonus(Bonus::new(
+            "Weapon Focus",
+            BonusType::Untyped,
+            BonusTarget::AttackRoll,
+            1,
+        ));
+        let mut sword = Item::new("laser-sword", "weapon", "Laser Sword");
+        sword.item_properties = serde_json::json!({ "damage": "1d8", "damage_type": "energy" });
+        let mut armor = Item::new("light-armor", "armor", "Light Armor");
+        armor.item_properties = serde_json::json!({
+            "armor_bonus": 2,
+            "damage_modifiers": [{ "modifier": "resistance", "damage_type": "kinetic", "amount": 2 }]
+        });
+
+        let combatant =
+            Combatant::from_character(&character, "players", &[sword.clone()], Some(&armor))
+                .unwrap();
+
+        assert_eq!(combatant.hit_points, 20);
+        assert_eq!(combatant.stamina, 14);
+        assert_eq!(combatant.armor_class, 14);
+        assert_eq!(combatant.attacks[0].attack_bonus, 6);
+        assert_eq!(combatant.attacks[0].damage_expression(), "1d8+3");
+        assert_eq!(
+            combatant.damage_modifiers,
+            vec![DamageModifier::Resistance {
+                damage_type: "kinetic".to_string(),
+                amount: 2
+            }]
+        );
+
+        // a typo in one entry is an error, not armor without resistances
+        armor.item_properties["damage_modifiers"][0]["modifier"] = "resistence".into();
+        assert!(matches!(
+            Combatant::from_character(&character, "players", &[sword], Some(&armor)),
+            Err(CombatError::InvalidItem(reason)) if reason.starts_with("light-armor damage_modifiers")
+        ));
+
+        // so is a bonus too large to add to armor class
+        armor.item_properties = serde_json::json!({ "armor_bonus": 1_i64 << 40 });
+        assert!(matches!(
+            Combatant::from_character(&character, "players", &[], Some(&armor)),
+            Err(CombatError::InvalidItem(reason)) if reason.starts_with("light-armor armor_bonus")
+        ));
+        armor.item_properties = serde_json::json!({ "armor_bonus": i32::MAX });
+        let combatant =
+            Combatant::from_character(&character, "players", &[], Some(&armor)).unwrap();
+        assert_eq!(combatant.armor_class, i32::MAX);
+    }
+}
@@ -0,0 +1,649 @@
+use std::{error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::Combatant;
+use crate::damage::{apply_damage, Damage, DamageBreakdown};
+use crate::utilities::{d20_expression, parse_dice_expression, DiceRng, DiceRollError, RollResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombatError {
+    /// Initiative has not been rolled yet.
+    NotStarted,
+    /// Only one side is left standing.
+    Over,
+    /// There is no combatant at the index.
+    UnknownCombatant(usize),
+    /// The combatant cannot be attacked, e.g. because they are defeated.
+    InvalidTarget(String),
+    /// The attacker has no attack at the index.
+    UnknownAttack(usize),
+    /// The current combatant has already attacked this turn.
+    AlreadyActed,
+    /// A combatant's equipment cannot be read, e.g. malformed damage modifiers.
+    InvalidItem(String),
+    Roll(DiceRollError),
+}
+
+impl fmt::Display for CombatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CombatError::NotStarted => write!(f, "initiative has not been rolled"),
+            CombatError::Over => write!(f, "combat is over"),
+            CombatError::UnknownCombatant(index) => write!(f, "there is no combatant {}", index),
+            CombatError::InvalidTarget(reason) => write!(f, "invalid target: {}", reason),
+            CombatError::UnknownAttack(index) => write!(f, "there is no attack {}", index),
+            CombatError::AlreadyActed => write!(f, "the combatant has already attacked this turn"),
+            CombatError::InvalidItem(reason) => write!(f, "invalid item: {}", reason),
+            CombatError::Roll(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl error::Error for CombatError {}
+
+impl From<DiceRollError> for CombatError {
+    fn from(error: DiceRollError) -> Self {
+        CombatError::Roll(error)
+    }
+}
+
+/// ## AttackOutcome
+/// What happened when one combatant attacked another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttackOutcome {
+    pub attacker: String,
+    pub target: String,
+    /// The name of the attack, e.g. `Blaster Rifle`.
+    pub attack: String,
+    pub roll: RollResult,
+    pub armor_class: i32,
+    pub hit: bool,
+    /// A natural 20, which hits and does double damage.
+    pub critical: bool,
+    /// The damage roll, if the attack hit.
+    pub damage_roll: Option<RollResult>,
+    /// The damage the target took by type once its resistances, immunities and vulnerabilities
+    /// applied, if the attack hit.
+    pub damage_breakdown: Option<DamageBreakdown>,
+    pub damage: i32,
+    pub stamina_damage: i32,
+    pub hit_point_damage: i32,
+}
+
+impl fmt::Display for AttackOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} attacks {} with {}: {} = {} vs AC {}",
+            self.attacker,
+            self.target,
+            self.attack,
+            self.roll.breakdown,
+            self.roll.total,
+            self.armor_class
+        )?;
+        if !self.hit {
+            return write!(f, ", miss");
+        }
+        write!(
+            f,
+            ", {} for {} ({} stamina, {} hit points)",
+            if self.critical { "critical hit" } else { "hit" },
+            self.damage_breakdown.clone().unwrap_or_default(),
+            self.stamina_damage,
+            self.hit_point_damage
+        )
+    }
+}
+
+/// ## CombatEvent
+/// An entry in the combat log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CombatEvent {
+    Initiative {
+        combatant: String,
+        roll: RollResult,
+    },
+    RoundStarted {
+        round: u32,
+    },
+    Attack(Box<AttackOutcome>),
+    Defeated {
+        combatant: String,
+    },
+    /// Combat ended with one side standing.
+    Ended {
+        winner: String,
+    },
+}
+
+impl fmt::Display for CombatEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CombatEvent::Initiative { combatant, roll } => {
+                write!(f, "{} rolls initiative: {}", combatant, roll)
+            }
+            CombatEvent::RoundStarted { round } => write!(f, "Round {}", round),
+            CombatEvent::Attack(outcome) => write!(f, "{}", outcome),
+            CombatEvent::Defeated { combatant } => write!(f, "{} is defeated", combatant),
+            CombatEvent::Ended { winner } => write!(f, "{} win", winner),
+        }
+    }
+}
+
+/// ## Encounter
+/// A turn-based fight between combatants on two or more sides.  It only keeps state and rolls
+/// dice with the generator it is given, so a server, a test or a simulator can drive it.
+///
+/// Initiative decides the order combatants act in each round.  On their turn a combatant
+/// attacks once and ends their turn; defeated combatants are skipped.  Combat is over when only
+/// one side has combatants standing.
+///
+/// ### Example
+/// ```
+/// use crate::logic::combat::{Attack, Combatant, Encounter};
+/// use crate::logic::items::Weapon;
+/// use crate::logic::utilities::ScriptedRng;
+///
+/// let knife = Weapon {
+///     damage: "1d4".to_string(),
+///     damage_type: "kinetic".to_string(),
+///     range: 0,
+///     properties: vec![],
+/// };
+/// let mut encounter = Encounter::new(vec![
+///     Combatant::new("Kira", "players", 10, 10, 14)
+///         .with_attack(Attack::new("Survival Knife", knife.clone(), 4, 1)),
+///     Combatant::new("Pirate", "enemies", 4, 0, 12).with_attack(Attack::new("Knife", knife, 1, 0)),
+/// ]);
+///
+/// // initiative 15 and 8, then Kira hits with a 12 and rolls a 3 for damage
+/// let mut rng = ScriptedRng::new(vec![15, 8, 12, 3]);
+/// encounter.roll_initiative(&mut rng).unwrap();
+/// let outcome = encounter.take_turn(&mut rng).unwrap();
+///
+/// assert_eq!(
+///     outcome.to_string(),
+///     "Kira attacks Pirate with Survival Knife: 1d20 (12) + 4 = 16 vs AC 12, hit for 4 kinetic (0 stamina, 4 hit points)"
+/// );
+/// assert_eq!(encounter.winner(), Some("players"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Encounter {
+    combatants: Vec<Combatant>,
+    /// The index of each combatant in the order they act.
+    order: Vec<usize>,
+    /// The position in `order` of the combatant whose turn it is.
+    turn: usize,
+    /// Whether the combatant whose turn it is has attacked.
+    acted: bool,
+    round: u32,
+    log: Vec<CombatEvent>,
+}
+
+impl Encounter {
+    pub fn new(combatants: Vec<Combatant>) -> Self {
+        Self {
+            combatants,
+            order: vec![],
+            turn: 0,
+            acted: false,
+            round: 0,
+            log: vec![],
+        }
+    }
+
+    pub fn combatants(&self) -> &[Combatant] {
+        &self.combatants
+    }
+
+    pub fn combatant(&self, index: usize) -> Option<&Combatant> {
+        self.combatants.get(index)
+    }
+
+    /// The index of each combatant in initiative order, empty before initiative is rolled.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// The current round, starting at 1, or 0 before initiative is rolled.
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn log(&self) -> &[CombatEvent] {
+        &self.log
+    }
+
+    /// ## roll_initiative
+    /// Roll a d20 plus the initiative bonus for every combatant and start the first round.
+    /// Ties go to the higher bonus, and then to whoever was listed first.
+    pub fn roll_initiative<R: DiceRng + ?Sized>(&mut self, rng: &mut R) -> Result<(), CombatError> {
+        let mut rolls = vec![];
+        for (index, combatant) in self.combatants.iter().enumerate() {
+            let expression = parse_dice_expression(&d20_expression(combatant.initiative_bonus))?;
+            let roll = RollResult::roll(&expression, None, rng)?;
+            rolls.push((index, roll.total, combatant.initiative_bonus));
+            self.log.push(CombatEvent::Initiative {
+                combatant: combatant.name.clone(),
+                roll,
+            });
+        }
+        rolls.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)).then(a.0.cmp(&b.0)));
+
+        self.order = rolls.into_iter().map(|(index, _, _)| index).collect();
+        self.round = 1;
+        self.turn = 0;
+        self.acted = false;
+        self.log.push(CombatEvent::RoundStarted { round: 1 });
+        self.skip_defeated();
+        Ok(())
+    }
+
+    /// The index of the combatant whose turn it is, or `None` before initiative or once combat
+    /// is over.
+    pub fn current(&self) -> Option<usize> {
+        if self.order.is_empty() || self.is_over() {
+            return None;
+        }
+        self.order.get(self.turn).copied()
+    }
+
+    /// The side left standing once combat is over.
+    pub fn winner(&self) -> Option<&str> {
+        let mut sides = self.standing_sides();
+        match (sides.next(), sides.next()) {
+            (Some(side), None) => Some(side),
+            _ => None,
+        }
+    }
+
+    /// Whether at most one side has combatants standing.
+    pub fn is_over(&self) -> bool {
+        self.standing_sides().nth(1).is_none()
+    }
+
+    /// ## attack
+    /// The current combatant attacks a target.  The attack hits when the d20 roll plus the
+    /// attack bonus meets the target's armor class; a natural 20 always hits and doubles the
+    /// damage, and a natural 1 always misses.  A hit rolls at least 1 damage of the weapon's
+    /// damage type, which the target's damage modifiers then apply to.  A combatant attacks once
+    /// a turn; the attack does not end the turn, but another before [`Encounter::end_turn`] is
+    /// an error.
+    ///
+    /// ### Arguments
+    /// * `target` - The index of the combatant to attack.
+    /// * `attack` - The index of the attack to use, from the attacker's attacks.
+    /// * `rng` - The generator used for the attack and damage rolls.
+    pub fn attack<R: DiceRng + ?Sized>(
+        &mut self,
+        target: usize,
+        attack: usize,
+        rng: &mut R,
+    ) -> Result<AttackOutcome, CombatError> {
+        if self.order.is_empty() {
+            return Err(CombatError::NotStarted);
+        }
+        let attacker = self.current().ok_or(CombatError::Over)?;
+        if self.acted {
+            return Err(CombatError::AlreadyActed);
+        }
+        let defender = self
+            .combatants
+            .get(target)
+            .ok_or(CombatError::UnknownCombatant(target))?;
+        if target == attacker {
+            return Err(CombatError::InvalidTarget(
+                "a combatant cannot attack themselves".to_string(),
+            ));
+        }
+        if defender.is_defeated() {
+            return Err(CombatError::InvalidTarget(format!(
+                "{} is already defeated",
+                defender.name
+            )));
+        }
+        let armor_class = defender.armor_class;
+        let chosen = self.combatants[attacker]
+            .attacks
+            .get(attack)
+            .ok_or(CombatError::UnknownAttack(attack))?
+            .clone();
+
+        let expression = parse_dice_expression(&d20_expression(chosen.attack_bonus))?;
+        let roll = RollResult::roll(&expression, Some(armor_class), rng)?;
+        let critical = roll.natural_max;
+        let hit = critical || (!roll.natural_min && roll.total >= armor_class);
+
+        let mut outcome = AttackOutcome {
+            attacker: self.combatants[attacker].name.clone(),
+            target: self.combatants[target].name.clone(),
+            attack: chosen.name.clone(),
+            roll,
+            armor_class,
+            hit,
+            critical,
+            damage_roll: None,
+            damage_breakdown: None,
+            damage: 0,
+            stamina_damage: 0,
+            hit_point_damage: 0,
+        };
+
+        if hit {
+            let expression = parse_dice_expression(&chosen.damage_expression())?;
+            let damage_roll = RollResult::roll(&expression, None, rng)?;
+            let mut damage = damage_roll.total.max(1);
+            if critical {
+                damage = damage.saturating_mul(2);
+            }
+            let breakdown = apply_damage(
+                &[Damage::new(&chosen.weapon.damage_type, damage)],
+                &self.combatants[target].damage_modifiers,
+            );
+            outcome.damage = breakdown.total;
+            outcome.damage_roll = Some(damage_roll);
+            outcome.damage_breakdown = Some(breakdown);
+            (outcome.stamina_damage, outcome.hit_point_damage) =
+                self.combatants[target].take_damage(outcome.damage);
+        }
+
+        self.acted = true;
+        self.log
+            .push(CombatEvent::Attack(Box::new(outcome.clone())));
+        if hit && self.combatants[target].is_defeated() {
+            self.log.push(CombatEvent::Defeated {
+                combatant: self.combatants[target].name.clone(),
+            });
+        }
+        if let Some(winner) = self.winner() {
+            self.log.push(CombatEvent::Ended {
+                winner: winner.to_string(),
+            });
+        }
+
+        Ok(outcome)
+    }
+
+    /// ## end_turn
+    /// Pass the turn to the next combatant standing, starting a new round after the last.
+    pub fn end_turn(&mut self) -> Result<(), CombatError> {
+        if self.order.is_empty() {
+            return Err(CombatError::NotStarted);
+        }
+        if self.is_over() {
+            return Err(CombatError::Over);
+        }
+        self.advance();
+        self.skip_defeated();
+        Ok(())
+    }
+
+    /// ## take_turn
+    /// Let the current combatant act on their own: they attack the first opponent standing, in
+    /// the order combatants were listed, with their first attack, then end their turn.
+    pub fn take_turn<R: DiceRng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<AttackOutcome, CombatError> {
+        let attacker = match self.current() {
+            Some(attacker) => attacker,
+            None if self.order.is_empty() => return Err(CombatError::NotStarted),
+            None => return Err(CombatError::Over),
+        };
+        let side = &self.combatants[attacker].side;
+        let target = self
+            .combatants
+            .iter()
+            .position(|combatant| &combatant.side != side && !combatant.is_defeated())
+            .ok_or(CombatError::Over)?;
+
+        let outcome = self.attack(target, 0, rng)?;
+        if !self.is_over() {
+            self.end_turn()?;
+        }
+        Ok(outcome)
+    }
+
+    /// ## run
+    /// Roll initiative if needed and let every combatant act on their own until one side is
+    /// left or `max_rounds` have been fought.
+    ///
+    /// ### Returns
+    /// The winning side, or `None` if combat had not ended after `max_rounds`.
+    pub fn run<R: DiceRng + ?Sized>(
+        &mut self,
+        max_rounds: u32,
+        rng: &mut R,
+    ) -> Result<Option<String>, CombatError> {
+        if self.order.is_empty() {
+            self.roll_initiative(rng)?;
+        }
+        while !self.is_over() && self.round <= max_rounds {
+            self.take_turn(rng)?;
+        }
+        Ok(self.winner().map(str::to_string))
+    }
+
+    fn standing_sides(&self) -> impl Iterator<Item = &str> {
+        let mut sides: Vec<&str> = self
+            .combatants
+            .iter()
+            .filter(|combatant| !combatant.is_defeated())
+            .map(|combatant| combatant.side.as_str())
+            .collect();
+        sides.sort();
+        sides.dedup();
+        sides.into_iter()
+    }
+
+    fn advance(&mut self) {
+        self.acted = false;
+        self.turn += 1;
+        if self.turn >= self.order.len() {
+            self.turn = 0;
+            self.round += 1;
+            self.log
+                .push(CombatEvent::RoundStarted { round: self.round });
+        }
+    }
+
+    fn skip_defeated(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        while self.combatants[self.order[self.turn]].is_defeated() {
+            self.advance();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::Attack;
+    use crate::damage::DamageModifier;
+    use crate::items::Weapon;
+    use crate::utilities::{ScriptedRng, SeededRng};
+
+    fn rifle() -> Attack {
+        Attack::new(
+            "Blaster Rifle",
+            Weapon {
+                damage: "1d12".to_string(),
+                damage_type: "energy".to_string(),
+                range: 200,
+                properties: vec![],
+            },
+            3,
+            0,
+        )
+    }
+
+    fn encounter() -> Encounter {
+        Encounter::new(vec![
+            Combatant::new("Kira", "players", 12, 8, 15)
+                .with_initiative_bonus(2)
+                .with_attack(rifle()),
+            Combatant::new("Pirate", "enemies", 10, 4, 12).with_attack(rifle()),
+            Combatant::new("Captain", "enemies", 20, 10, 13)
+                .with_initiative_bonus(2)
+                .with_attack(rifle()),
+        ])
+    }
+
+    #[test]
+    fn test_initiative_order_breaks_ties_by_bonus() {
+        let mut encounter = encounter();
+        assert_eq!(
+            encounter.attack(1, 0, &mut ScriptedRng::new(vec![])),
+            Err(CombatError::NotStarted)
+        );
+
+        // Kira 10 + 2, Pirate 12 + 0, Captain 5 + 2
+        encounter
+            .roll_initiative(&mut ScriptedRng::new(vec![10, 12, 5]))
+            .unwrap();
+
+        assert_eq!(encounter.order(), &[0, 1, 2]);
+        assert_eq!(encounter.round(), 1);
+        assert_eq!(encounter.current(), Some(0));
+        assert_eq!(
+            encounter.log()[0].to_string(),
+            "Kira rolls initiative: 1d20 (10) + 2 = 12"
+        );
+    }
+
+    #[test]
+    fn test_damage_depletes_stamina_then_hit_points() {
+        let mut encounter = encounter();
+        encounter
+            .roll_initiative(&mut ScriptedRng::new(vec![20, 1, 1]))
+            .unwrap();
+        assert_eq!(
+            encounter.attack(0, 0, &mut ScriptedRng::new(vec![])),
+            Err(CombatError::InvalidTarget(
+                "a combatant cannot attack themselves".to_string()
+            ))
+        );
+
+        let outcome = encounter
+            .attack(1, 0, &mut ScriptedRng::new(vec![9, 7]))
+            .unwrap();
+        assert!(outcome.hit);
+        assert_eq!((outcome.stamina_damage, outcome.hit_point_damage), (4, 3));
+        assert_eq!(encounter.combatant(1).unwrap().hit_points, 7);
+
+        // a natural 1 misses whatever the bonus, on Kira's next turn
+        for _ in 0..3 {
+            encounter.end_turn().unwrap();
+        }
+        let outcome = encounter
+            .attack(1, 0, &mut ScriptedRng::new(vec![1]))
+            .unwrap();
+        assert!(!outcome.hit);
+        assert!(outcome.damage_roll.is_none());
+        assert_eq!(
+            outcome.to_string(),
+            "Kira attacks Pirate with Blaster Rifle: 1d20 (1) + 3 = 4 vs AC 12, miss"
+        );
+    }
+
+    #[test]
+    fn test_critical_hits_double_damage_and_defeat_ends_combat() {
+        let mut encounter = encounter();
+        encounter
+            .roll_initiative(&mut ScriptedRng::new(vec![20, 1, 1]))
+            .unwrap();
+
+        let outcome = encounter
+            .attack(1, 0, &mut ScriptedRng::new(vec![20, 7]))
+            .unwrap();
+        assert!(outcome.critical);
+        assert_eq!(outcome.damage, 14);
+        assert!(encounter.combatant(1).unwrap().is_defeated());
+        assert!(!encounter.is_over());
+
+        // the defeated pirate is skipped
+        encounter.end_turn().unwrap();
+        assert_eq!(encounter.current(), Some(2));
+        encounter.end_turn().unwrap();
+        assert_eq!(encounter.round(), 2);
+        assert_eq!(encounter.current(), Some(0));
+
+        // two critical hits for 24 each are more than the captain's 30 stamina and hit points,
+        // but Kira attacks once a turn
+        let mut rng = ScriptedRng::new(vec![20, 12, 20, 12]);
+        encounter.attack(2, 0, &mut rng).unwrap();
+        assert!(!encounter.is_over());
+        assert_eq!(
+            encounter.attack(2, 0, &mut rng),
+            Err(CombatError::AlreadyActed)
+        );
+        encounter.end_turn().unwrap();
+        encounter.end_turn().unwrap();
+        assert_eq!(encounter.current(), Some(0));
+        encounter.attack(2, 0, &mut rng).unwrap();
+        assert_eq!(encounter.winner(), Some("players"));
+        assert_eq!(encounter.current(), None);
+        assert_eq!(encounter.end_turn(), Err(CombatError::Over));
+        assert_eq!(
+            encounter.log().last().unwrap(),
+            &CombatEvent::Ended {
+                winner: "players".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_damage_modifiers_apply_to_hits() {
+        let mut encounter = Encounter::new(vec![
+            Combatant::new("Kira", "players", 12, 8, 15).with_attack(rifle()),
+            Combatant::new("Drone", "enemies", 10, 0, 12).with_damage_modifier(
+                DamageModifier::Resistance {
+                    damage_type: "energy".to_string(),
+                    amount: 5,
+                },
+            ),
+        ]);
+        encounter
+            .roll_initiative(&mut ScriptedRng::new(vec![20, 1]))
+            .unwrap();
+
+        let outcome = encounter
+            .attack(1, 0, &mut ScriptedRng::new(vec![12, 7]))
+            .unwrap();
+        assert_eq!(outcome.damage, 2);
+        assert_eq!(outcome.damage_breakdown.unwrap().taken("energy"), 2);
+        assert_eq!(encounter.combatant(1).unwrap().hit_points, 8);
+        assert_eq!(
+            encounter.log().last().unwrap().to_string(),
+            "Kira attacks Drone with Blaster Rifle: 1d20 (12) + 3 = 15 vs AC 12, hit for 7 energy - 5 resisted = 2 (0 stamina, 2 hit points)"
+        );
+
+        // a hit the target resists completely does no damage
+        encounter.end_turn().unwrap();
+        encounter.end_turn().unwrap();
+        let outcome = encounter
+            .attack(1, 0, &mut ScriptedRng::new(vec![12, 3]))
+            .unwrap();
+        assert!(outcome.hit);
+        assert_eq!(outcome.damage, 0);
+        assert_eq!(encounter.combatant(1).unwrap().hit_points, 8);
+    }
+
+    #[test]
+    fn test_run_is_reproducible_from_a_seed() {
+        let mut first = encounter();
+        let mut second = encounter();
+
+        let winner = first.run(100, &mut SeededRng::new(11)).unwrap();
+        second.run(100, &mut SeededRng::new(11)).unwrap();
+
+        assert!(winner.is_some());
+        assert_eq!(first, second);
+        assert!(first.is_over());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod combatant;
+pub mod encounter;
+
+pub use combatant::*;
+pub use encounter::*;
@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ConditionDefinition, ConditionDuration, StackingRule};
+use crate::bonuses::Bonus;
+use crate::utilities::RollResult;
+
+/// ## ActiveCondition
+/// A condition a character has, with its definition so that it can be saved and reloaded
+/// without the world's data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveCondition {
+    pub definition: ConditionDefinition,
+    pub remaining: ConditionDuration,
+    pub stacks: u32,
+}
+
+impl ActiveCondition {
+    pub fn code(&self) -> &str {
+        &self.definition.code
+    }
+
+    pub fn bonuses(&self) -> Vec<Bonus> {
+        self.definition.bonuses(self.stacks)
+    }
+}
+
+/// ## ConditionChange
+/// What applying a condition did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionChange {
+    Applied,
+    Refreshed,
+    Extended,
+    /// The condition now has this many stacks.
+    Stacked(u32),
+    Ignored,
+}
+
+/// ## ConditionSaveRoll
+/// A saving throw made at the end of a round to end a condition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionSaveRoll {
+    pub condition: String,
+    pub roll: RollResult,
+    pub success: bool,
+}
+
+/// ## RoundReport
+/// The saving throws made at the end of a round, and the conditions that ended.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundReport {
+    pub saves: Vec<ConditionSaveRoll>,
+    /// The codes of the conditions that were saved against or ran out.
+    pub ended: Vec<String>,
+}
+
+/// ## Conditions
+/// The conditions a character has.  Serializes with each condition's definition and the time
+/// it has left.
+///
+/// ### Example
+/// ```
+/// use crate::logic::bonuses::BonusTarget;
+/// use crate::logic::conditions::{ConditionChange, ConditionDefinition, ConditionDuration, Conditions};
+///
+/// let stunned = ConditionDefinition::new("stunned", "Stunned", ConditionDuration::Rounds(2))
+///     .with_effect(BonusTarget::ArmorClass, -2);
+///
+/// let mut conditions = Conditions::default();
+/// assert_eq!(conditions.apply(&stunned), ConditionChange::Applied);
+/// assert_eq!(conditions.tick_rounds(1), Vec::<String>::new());
+/// assert_eq!(conditions.tick_rounds(1), vec!["stunned".to_string()]);
+/// assert!(conditions.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conditions {
+    active: Vec<ActiveCondition>,
+}
+
+impl Conditions {
+    pub fn active(&self) -> &[ActiveCondition] {
+        &self.active
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    pub fn get(&self, code: &str) -> Option<&ActiveCondition> {
+        self.active.iter().find(|active| active.code() == code)
+    }
+
+    pub fn has(&self, code: &str) -> bool {
+        self.get(code).is_some()
+    }
+
+    /// ## apply
+    /// Give the character a condition, following its [`StackingRule`] if they already have it.
+    pub fn apply(&mut self, definition: &ConditionDefinition) -> ConditionChange {
+        let duration = definition.duration;
+        let existing = match self
+            .active
+            .iter_mut()
+            .find(|active| active.code() == definition.code)
+        {
+            Some(existing) => existing,
+            None => {
+                self.active.push(ActiveCondition {
+                    definition: definition.clone(),
+                    remaining: duration,
+                    stacks: 1,
+                });
+                return ConditionChange::Applied;
+            }
+        };
+
+        match definition.stacking {
+            StackingRule::Refresh => {
+                existing.remaining = existing.remaining.longest(duration);
+                ConditionChange::Refreshed
+            }
+            StackingRule::Extend => {
+                existing.remaining = existing.remaining.extended_by(duration);
+                ConditionChange::Extended
+            }
+            StackingRule::Stack { max_stacks } => {
+                existing.stacks = existing.stacks.saturating_add(1).min(max_stacks.max(1));
+                existing.remaining = existing.remaining.longest(duration);
+                ConditionChange::Stacked(existing.stacks)
+            }
+            StackingRule::Ignore => ConditionChange::Ignored,
+        }
+    }
+
+    /// Remove a condition, whatever its duration.  Returns whether the character had it.
+    pub fn remove(&mut self, code: &str) -> bool {
+        let count = self.active.len();
+        self.active.retain(|active| active.code() != code);
+        self.active.len() != count
+    }
+
+    /// The bonuses and penalties from every condition.
+    pub fn bonuses(&self) -> Vec<Bonus> {
+        self.active
+            .iter()
+            .flat_map(|active| active.bonuses())
+            .collect()
+    }
+
+    /// Count down `rounds` combat rounds, and return the codes of the conditions that ran out.
+    pub fn tick_rounds(&mut self, rounds: u32) -> Vec<String> {
+        self.expire(|remaining| remaining.after_rounds(rounds))
+    }
+
+    /// Count down `seconds` of real time, and return the codes of the conditions that ran out.
+    pub fn tick_seconds(&mut self, seconds: u64) -> Vec<String> {
+        self.expire(|remaining| remaining.after_seconds(seconds))
+    }
+
+    fn expire<F: Fn(&ConditionDuration) -> ConditionDuration>(&mut self, tick: F) -> Vec<String> {
+        let mut expired = vec![];
+        self.active.retain_mut(|active| {
+            active.remaining = tick(&active.remaining);
+            if active.remaining.is_expired() {
+                expired.push(active.code().to_string());
+                false
+            } else {
+                true
+            }
+        });
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonuses::BonusTarget;
+
+    fn poisoned(stacking: StackingRule) -> ConditionDefinition {
+        ConditionDefinition::new("poisoned", "Poisoned", ConditionDuration::Rounds(3))
+            .with_effect(BonusTarget::AttackRoll, -1)
+            .with_stacking(stacking)
+    }
+
+    #[test]
+    fn test_stacking_rules() {
+        let mut conditions = Conditions::default();
+        conditions.apply(&poisoned(StackingRule::Refresh));
+        conditions.tick_rounds(2);
+        assert_eq!(
+            conditions.apply(&poisoned(StackingRule::Refresh)),
+            ConditionChange::Refreshed
+        );
+        assert_eq!(
+            conditions.get("poisoned").unwrap().remaining,
+            ConditionDuration::Rounds(3)
+        );
+
+        assert_eq!(
+            conditions.apply(&poisoned(StackingRule::Extend)),
+            ConditionChange::Extended
+        );
+        assert_eq!(
+            conditions.get("poisoned").unwrap().remaining,
+            ConditionDuration::Rounds(6)
+        );
+
+        assert_eq!(
+            conditions.apply(&poisoned(StackingRule::Ignore)),
+            ConditionChange::Ignored
+        );
+    }
+
+    #[test]
+    fn test_stacks_multiply_effects() {
+        let stacking = StackingRule::Stack { max_stacks: 2 };
+        let mut conditions = Conditions::default();
+
+        conditions.apply(&poisoned(stacking));
+        assert_eq!(
+            conditions.apply(&poisoned(stacking)),
+            ConditionChange::Stacked(2)
+        );
+        assert_eq!(
+            conditions.apply(&poisoned(stacking)),
+            ConditionChange::Stacked(2)
+        );
+        assert_eq!(conditions.bonuses()[0].value, -2);
+        assert_eq!(conditions.bonuses()[0].source, "Poisoned");
+    }
+
+    #[test]
+    fn test_real_time_and_removal() {
+        let mut conditions = Conditions::default();
+        conditions.apply(&ConditionDefinition::new(
+            "fatigued",
+            "Fatigued",
+            ConditionDuration::Seconds(3600),
+        ));
+        conditions.apply(&poisoned(StackingRule::Refresh));
+
+        assert_eq!(conditions.tick_seconds(60), vec!["poisoned".to_string()]);
+        assert!(conditions.has("fatigued"));
+        assert!(conditions.remove("fatigued"));
+        assert!(!conditions.remove("fatigued"));
+    }
+
+    #[test]
+    fn test_conditions_survive_a_reload() {
+        let mut conditions = Conditions::default();
+        conditions.apply(&poisoned(StackingRule::Stack { max_stacks: 3 }));
+        conditions.apply(&poisoned(StackingRule::Stack { max_stacks: 3 }));
+        conditions.tick_rounds(1);
+
+        let json = serde_json::to_string(&conditions).unwrap();
+        let reloaded: Conditions = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(reloaded, conditions);
+        assert_eq!(reloaded.get("poisoned").unwrap().stacks, 2);
+    }
+
+    #[test]
+    fn test_reloaded_stacks_do_not_overflow() {
+        let stacking = StackingRule::Stack {
+            max_stacks: u32::MAX,
+        };
+        let mut conditions = Conditions::default();
+        conditions.apply(&poisoned(stacking));
+
+        let json = serde_json::to_string(&conditions)
+            .unwrap()
+            .replace("\"stacks\":1", &format!("\"stacks\":{}", u32::MAX));
+        let mut reloaded: Conditions = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            reloaded.apply(&poisoned(stacking)),
+            ConditionChange::Stacked(u32::MAX)
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+use crate::bonuses::{Bonus, BonusTarget, BonusType};
+
+/// The number of seconds in a combat round.
+pub const ROUND_SECONDS: u64 = 6;
+
+/// ## ConditionDuration
+/// How long a condition lasts, counted in combat rounds or in real time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "unit", content = "amount", rename_all = "snake_case")]
+pub enum ConditionDuration {
+    Rounds(u32),
+    Seconds(u64),
+    /// Until the character makes the condition's saving throw, or it is removed.
+    UntilSaved,
+    /// Until the condition is removed.
+    Permanent,
+}
+
+impl ConditionDuration {
+    pub fn is_expired(&self) -> bool {
+        matches!(
+            self,
+            ConditionDuration::Rounds(0) | ConditionDuration::Seconds(0)
+        )
+    }
+
+    /// The duration in seconds, or `None` if it only ends when saved or removed.
+    pub fn seconds(&self) -> Option<u64> {
+        match self {
+            ConditionDuration::Rounds(rounds) => Some(*rounds as u64 * ROUND_SECONDS),
+            ConditionDuration::Seconds(seconds) => Some(*seconds),
+            ConditionDuration::UntilSaved | ConditionDuration::Permanent => None,
+        }
+    }
+
+    /// The duration left after `rounds` rounds.
+    pub fn after_rounds(&self, rounds: u32) -> Self {
+        match self {
+            ConditionDuration::Rounds(left) => {
+                ConditionDuration::Rounds(left.saturating_sub(rounds))
+            }
+            ConditionDuration::Seconds(left) => {
+                ConditionDuration::Seconds(left.saturating_sub(rounds as u64 * ROUND_SECONDS))
+            }
+            other => *other,
+        }
+    }
+
+    /// The duration left after `seconds` of real time.  A partial round does not count.
+    pub fn after_seconds(&self, seconds: u64) -> Self {
+        match self {
+            ConditionDuration::Rounds(left) => {
+                let rounds = (seconds / ROUND_SECONDS).min(u32::MAX as u64) as u32;
+                ConditionDuration::Rounds(left.saturating_sub(rounds))
+            }
+            ConditionDuration::Seconds(left) => {
+                ConditionDuration::Seconds(left.saturating_sub(seconds))
+            }
+            other => *other,
+        }
+    }
+
+    /// The longer of two durations.  Durations that only end when saved or removed are longer
+    /// than any timed duration.
+    pub fn longest(self, other: Self) -> Self {
+        match (self.seconds(), other.seconds()) {
+            (Some(left), Some(right)) if left >= right => self,
+            (Some(_), Some(_)) => other,
+            (None, _) if self == ConditionDuration::Permanent => self,
+            (_, None) if other == ConditionDuration::Permanent => other,
+            (None, _) => self,
+            (_, None) => other,
+        }
+    }
+
+    /// Both durations added together, in rounds when both are in rounds.
+    pub fn extended_by(self, other: Self) -> Self {
+        match (self, other) {
+            (ConditionDuration::Rounds(left), ConditionDuration::Rounds(right)) => {
+                ConditionDuration::Rounds(left.saturating_add(right))
+            }
+            _ => match (self.seconds(), other.seconds()) {
+                (Some(left), Some(right)) => ConditionDuration::Seconds(left.saturating_add(right)),
+                _ => self.longest(other),
+            },
+        }
+    }
+}
+
+/// ## ConditionSave
+/// The saving throw a character can make at the end of each round to end a condition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionSave {
+    /// The saving throw, e.g. `fortitude`.  Bonuses to it apply to the roll.
+    pub save: String,
+    /// The ability whose modifier is added to the roll.
+    pub ability: Ability,
+    pub dc: i32,
+}
+
+/// ## StackingRule
+/// What happens when a condition is applied to a character who already has it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum StackingRule {
+    /// The condition keeps the longer of the two durations.
+    #[default]
+    Refresh,
+    /// The durations are added together.
+    Extend,
+    /// Each application adds a stack, up to `max_stacks`, and the effects are multiplied by the
+    /// number of stacks.  The duration is refreshed.
+    Stack { max_stacks: u32 },
+    /// The second application has no effect.
+    Ignore,
+}
+
+/// ## ConditionEffect
+/// A bonus or penalty a condition gives while it lasts, e.g. -2 to attack rolls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionEffect {
+    #[serde(flatten)]
+    pub target: BonusTarget,
+    pub value: i32,
+    #[serde(default = "untyped")]
+    pub bonus_type: BonusType,
+}
+
+fn untyped() -> BonusType {
+    BonusType::Untyped
+}
+
+impl ConditionEffect {
+    pub fn new(target: BonusTarget, value: i32) -> Self {
+        Self {
+            target,
+            value,
+            bonus_type: BonusType::Untyped,
+        }
+    }
+}
+
+/// ## ConditionDefinition
+/// A condition a world defines, such as stunned, poisoned or prone.
+///
+/// ### Example
+/// ```
+/// use crate::logic::conditions::{ConditionDefinition, ConditionDuration};
+///
+/// let poisoned: ConditionDefinition = serde_json::from_str(r#"{
+///     "code": "poisoned",
+///     "name": "Poisoned",
+///     "description": "Toxins sap your strength.",
+///     "effects": [{ "target": "ability", "name": "strength", "value": -2 }],
+///     "duration": { "unit": "rounds", "amount": 10 },
+///     "save": { "save": "fortitude", "ability": "constitution", "dc": 13 },
+///     "stacking": { "rule": "stack", "max_stacks": 3 }
+/// }"#).unwrap();
+///
+/// assert_eq!(poisoned.duration, ConditionDuration::Rounds(10));
+/// assert_eq!(poisoned.bonuses(2)[0].value, -4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionDefinition {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub effects: Vec<ConditionEffect>,
+    pub duration: ConditionDuration,
+    #[serde(default)]
+    pub save: Option<ConditionSave>,
+    #[serde(default)]
+    pub stacking: StackingRule,
+}
+
+impl ConditionDefinition {
+    pub fn new(code: &str, name: &str, duration: ConditionDuration) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            effects: vec![],
+            duration,
+            save: None,
+            stacking: StackingRule::default(),
+        }
+    }
+
+    pub fn with_effect(mut self, target: BonusTarget, value: i32) -> Self {
+        self.effects.push(ConditionEffect::new(target, value));
+        self
+    }
+
+    pub fn with_save(mut self, save: &str, ability: Ability, dc: i32) -> Self {
+        self.save = Some(ConditionSave {
+            save: save.to_string(),
+            ability,
+            dc,
+        });
+        self
+    }
+
+    pub fn with_stacking(mut self, stacking: StackingRule) -> Self {
+        self.stacking = stacking;
+        self
+    }
+
+    /// The bonuses the condition gives with `stacks` stacks, with the condition's name as their
+    /// source.
+    pub fn bonuses(&self, stacks: u32) -> Vec<Bonus> {
+        let stacks = stacks.min(i32::MAX as u32) as i32;
+        self.effects
+            .iter()
+            .map(|effect| {
+                Bonus::new(
+                    &self.name,
+                    effect.bonus_type,
+                    effect.target.clone(),
+                    effect.value.saturating_mul(stacks),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_durations_tick_down() {
+        assert_eq!(
+            ConditionDuration::Rounds(3).after_rounds(1),
+            ConditionDuration::Rounds(2)
+        );
+        assert_eq!(
+            ConditionDuration::Seconds(60).after_rounds(2),
+            ConditionDuration::Seconds(48)
+        );
+        assert_eq!(
+            ConditionDuration::Rounds(3).after_seconds(13),
+            ConditionDuration::Rounds(1)
+        );
+        assert!(ConditionDuration::Seconds(5).after_seconds(10).is_expired());
+        assert!(!ConditionDuration::UntilSaved.after_rounds(100).is_expired());
+    }
+
+    #[test]
+    fn test_longest_and_extended_durations() {
+        let rounds = ConditionDuration::Rounds(2);
+        let minute = ConditionDuration::Seconds(60);
+
+        assert_eq!(rounds.longest(minute), minute);
+        assert_eq!(
+            rounds.longest(ConditionDuration::UntilSaved),
+            ConditionDuration::UntilSaved
+        );
+        assert_eq!(
+            ConditionDuration::UntilSaved.longest(ConditionDuration::Permanent),
+            ConditionDuration::Permanent
+        );
+        assert_eq!(
+            rounds.extended_by(ConditionDuration::Rounds(3)),
+            ConditionDuration::Rounds(5)
+        );
+        assert_eq!(rounds.extended_by(minute), ConditionDuration::Seconds(72));
+    }
+
+    #[test]
+    fn test_definition_round_trips() {
+        let prone = ConditionDefinition::new("prone", "Prone", ConditionDuration::Permanent)
+            .with_effect(BonusTarget::AttackRoll, -4)
+            .with_stacking(StackingRule::Ignore);
+
+        let json = serde_json::to_string(&prone).unwrap();
+        assert!(json.contains(r#"{"target":"attack_roll","value":-4,"bonus_type":"untyped"}"#));
+        assert_eq!(
+            serde_json::from_str::<ConditionDefinition>(&json).unwrap(),
+            prone
+        );
+    }
+}
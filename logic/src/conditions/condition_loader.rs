@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use crate::abilities::Ability;
+use crate::bonuses::BonusTarget;
+use crate::conditions::condition::{ConditionDefinition, ConditionDuration, StackingRule};
+
+/// The conditions every world starts with, used when a world does not define a condition
+/// itself.  Worlds define their own with `condition` documents, see
+/// [`GameData::world_conditions`](crate::game_data::GameData::world_conditions).
+pub fn load_default_conditions() -> HashMap<String, ConditionDefinition> {
+    [
+        ConditionDefinition::new("stunned", "Stunned", ConditionDuration::Rounds(1))
+            .with_effect(BonusTarget::ArmorClass, -2)
+            .with_effect(BonusTarget::AttackRoll, -4)
+            .with_effect(BonusTarget::Skill("acrobatics".to_string()), -4)
+            .with_save("fortitude", Ability::Constitution, 15),
+        ConditionDefinition::new("poisoned", "Poisoned", ConditionDuration::Rounds(10))
+            .with_effect(BonusTarget::Ability(Ability::Strength), -2)
+            .with_effect(BonusTarget::Ability(Ability::Constitution), -2)
+            .with_save("fortitude", Ability::Constitution, 13)
+            .with_stacking(StackingRule::Stack { max_stacks: 3 }),
+        ConditionDefinition::new("off-target", "Off-Target", ConditionDuration::Rounds(1))
+            .with_effect(BonusTarget::AttackRoll, -2),
+        ConditionDefinition::new("prone", "Prone", ConditionDuration::Permanent)
+            .with_effect(BonusTarget::ArmorClass, -4)
+            .with_effect(BonusTarget::AttackRoll, -4)
+            .with_stacking(StackingRule::Ignore),
+    ]
+    .into_iter()
+    .map(|condition| (condition.code.clone(), condition))
+    .collect()
+}
@@ -0,0 +1,7 @@
+pub mod active_condition;
+pub mod condition;
+pub mod condition_loader;
+
+pub use active_condition::*;
+pub use condition::*;
+pub use condition_loader::*;
@@ -0,0 +1,238 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// ## DamageModifier
+/// How a creature or a piece of equipment changes the damage of one type it takes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "modifier", rename_all = "snake_case")]
+pub enum DamageModifier {
+    /// Reduce the damage by a flat amount, e.g. energy resistance 5.
+    Resistance { damage_type: String, amount: i32 },
+    /// Take no damage of the type.
+    Immunity { damage_type: String },
+    /// Take half as much damage again.
+    Vulnerability { damage_type: String },
+}
+
+impl DamageModifier {
+    /// The code of the damage type the modifier applies to.
+    pub fn damage_type(&self) -> &str {
+        match self {
+            DamageModifier::Resistance { damage_type, .. }
+            | DamageModifier::Immunity { damage_type }
+            | DamageModifier::Vulnerability { damage_type } => damage_type,
+        }
+    }
+}
+
+/// ## Damage
+/// An amount of damage of one type, before any modifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Damage {
+    pub damage_type: String,
+    pub amount: i32,
+}
+
+impl Damage {
+    pub fn new(damage_type: &str, amount: i32) -> Self {
+        Self {
+            damage_type: damage_type.to_string(),
+            amount,
+        }
+    }
+}
+
+/// ## DamagePart
+/// The damage of one type and what the target's modifiers did to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamagePart {
+    pub damage_type: String,
+    /// The damage dealt, before any modifiers.
+    pub amount: i32,
+    pub immune: bool,
+    /// The extra damage from a vulnerability.
+    pub vulnerable: i32,
+    /// The damage a resistance took away.
+    pub resisted: i32,
+    /// The damage the target takes.
+    pub taken: i32,
+}
+
+impl fmt::Display for DamagePart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.damage_type)?;
+        if self.immune {
+            return write!(f, " (immune) = 0");
+        }
+        if self.vulnerable > 0 {
+            write!(f, " + {} vulnerable", self.vulnerable)?;
+        }
+        if self.resisted > 0 {
+            write!(f, " - {} resisted", self.resisted)?;
+        }
+        if self.taken != self.amount {
+            write!(f, " = {}", self.taken)?;
+        }
+        Ok(())
+    }
+}
+
+/// ## DamageBreakdown
+/// The damage a target takes, by type, for the combat log.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DamageBreakdown {
+    pub parts: Vec<DamagePart>,
+    pub total: i32,
+}
+
+impl DamageBreakdown {
+    /// The damage of one type the target takes.
+    pub fn taken(&self, damage_type: &str) -> i32 {
+        self.parts
+            .iter()
+            .filter(|part| part.damage_type == damage_type)
+            .map(|part| part.taken)
+            .sum()
+    }
+}
+
+impl fmt::Display for DamageBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = self.parts.iter().map(|part| part.to_string()).collect();
+        write!(f, "{}", parts.join(", "))?;
+        if self.parts.len() > 1 {
+            write!(f, ", {} total", self.total)?;
+        }
+        Ok(())
+    }
+}
+
+/// ## apply_damage
+/// Work out how much damage a target takes once its damage modifiers apply.
+///
+/// Damage of the same type is added together first, so each modifier applies once per type.
+/// Immunity stops the damage.  Otherwise a vulnerability adds half the damage again, rounded
+/// down, and then the largest resistance to the type is taken away; resistances do not stack.
+/// No type does less than 0 damage.
+///
+/// ### Arguments
+/// * `damage` - The damage dealt, e.g. a weapon's damage roll.
+/// * `modifiers` - The target's resistances, immunities and vulnerabilities.
+///
+/// ### Returns
+/// The damage taken of each type, in the order the types were first dealt, and the total.
+///
+/// ### Example
+/// ```
+/// use crate::logic::damage::{apply_damage, Damage, DamageModifier};
+///
+/// let modifiers = vec![
+///     DamageModifier::Resistance { damage_type: "energy".to_string(), amount: 5 },
+///     DamageModifier::Vulnerability { damage_type: "kinetic".to_string() },
+/// ];
+/// let breakdown = apply_damage(&[Damage::new("energy", 7), Damage::new("kinetic", 5)], &modifiers);
+///
+/// assert_eq!(breakdown.total, 9);
+/// assert_eq!(
+///     breakdown.to_string(),
+///     "7 energy - 5 resisted = 2, 5 kinetic + 2 vulnerable = 7, 9 total"
+/// );
+/// ```
+pub fn apply_damage(damage: &[Damage], modifiers: &[DamageModifier]) -> DamageBreakdown {
+    let mut parts: Vec<DamagePart> = vec![];
+    for dealt in damage {
+        let amount = dealt.amount.max(0);
+        match parts
+            .iter_mut()
+            .find(|part| part.damage_type == dealt.damage_type)
+        {
+            Some(part) => part.amount = part.amount.saturating_add(amount),
+            None => parts.push(DamagePart {
+                damage_type: dealt.damage_type.clone(),
+                amount,
+                immune: false,
+                vulnerable: 0,
+                resisted: 0,
+                taken: 0,
+            }),
+        }
+    }
+
+    for part in &mut parts {
+        let applies = |modifier: &&DamageModifier| modifier.damage_type() == part.damage_type;
+        let mut resistance = 0;
+        for modifier in modifiers.iter().filter(applies) {
+            match modifier {
+                DamageModifier::Immunity { .. } => part.immune = true,
+                DamageModifier::Vulnerability { .. } => part.vulnerable = part.amount / 2,
+                DamageModifier::Resistance { amount, .. } => resistance = resistance.max(*amount),
+            }
+        }
+
+        if part.immune {
+            part.vulnerable = 0;
+            continue;
+        }
+        let damage = part.amount.saturating_add(part.vulnerable);
+        part.resisted = resistance.clamp(0, damage);
+        part.taken = damage - part.resisted;
+    }
+
+    DamageBreakdown {
+        total: parts.iter().map(|part| part.taken).sum(),
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_immunity_stops_damage_and_resistances_do_not_stack() {
+        let modifiers = vec![
+            DamageModifier::Immunity {
+                damage_type: "plasma".to_string(),
+            },
+            DamageModifier::Vulnerability {
+                damage_type: "plasma".to_string(),
+            },
+            DamageModifier::Resistance {
+                damage_type: "energy".to_string(),
+                amount: 2,
+            },
+            DamageModifier::Resistance {
+                damage_type: "energy".to_string(),
+                amount: 10,
+            },
+        ];
+
+        let breakdown = apply_damage(
+            &[
+                Damage::new("plasma", 9),
+                Damage::new("energy", 4),
+                Damage::new("energy", 3),
+            ],
+            &modifiers,
+        );
+
+        assert_eq!(breakdown.parts.len(), 2);
+        assert_eq!(breakdown.taken("plasma"), 0);
+        assert_eq!(breakdown.parts[1].amount, 7);
+        assert_eq!(breakdown.parts[1].resisted, 7);
+        assert_eq!(breakdown.total, 0);
+        assert_eq!(
+            breakdown.to_string(),
+            "9 plasma (immune) = 0, 7 energy - 7 resisted = 0, 0 total"
+        );
+    }
+
+    #[test]
+    fn test_unmodified_damage_is_taken_in_full() {
+        let breakdown = apply_damage(&[Damage::new("kinetic", 4)], &[]);
+
+        assert_eq!(breakdown.total, 4);
+        assert_eq!(breakdown.to_string(), "4 kinetic");
+    }
+}
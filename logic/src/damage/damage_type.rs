@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// ## DamageType
+/// A kind of damage a world defines, such as `energy` or `kinetic`.  Weapons deal it, and
+/// creatures and equipment can resist it, be immune to it or be vulnerable to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageType {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl DamageType {
+    pub fn new(code: &str, name: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+        }
+    }
+}
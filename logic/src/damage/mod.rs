@@ -0,0 +1,5 @@
+pub mod damage_modifier;
+pub mod damage_type;
+
+pub use damage_modifier::*;
+pub use damage_type::*;
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::{ability_modifier, Ability};
+use crate::damage::DamageModifier;
+
+/// ## Equipment
+/// An item an enemy carries, by code, and how many.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Equipment {
+    pub code: String,
+    #[serde(default = "one")]
+    pub quantity: i32,
+}
+
+fn one() -> i32 {
+    1
+}
+
+/// ## Enemy
+/// A creature a world defines for characters to fight, with the items it carries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enemy {
+    pub code: String,
+    pub name: String,
+    pub class: String,
+    pub level: i32,
+    #[serde(default)]
+    pub description: String,
+    pub hit_points: i32,
+    pub stamina: i32,
+    pub strength: i32,
+    pub dexterity: i32,
+    pub constitution: i32,
+    pub intelligence: i32,
+    pub wisdom: i32,
+    pub charisma: i32,
+    #[serde(default)]
+    pub weapons: Vec<Equipment>,
+    #[serde(default)]
+    pub armor: Option<Equipment>,
+    /// The enemy's own resistances, immunities and vulnerabilities.
+    #[serde(default)]
+    pub damage_modifiers: Vec<DamageModifier>,
+}
+
+impl Enemy {
+    pub fn ability_score(&self, ability: Ability) -> i32 {
+        match ability {
+            Ability::Strength => self.strength,
+            Ability::Dexterity => self.dexterity,
+            Ability::Constitution => self.constitution,
+            Ability::Intelligence => self.intelligence,
+            Ability::Wisdom => self.wisdom,
+            Ability::Charisma => self.charisma,
+        }
+    }
+
+    /// The modifier for an ability, worked out the way a character's is.
+    pub fn ability_modifier(&self, ability: Ability) -> i32 {
+        ability_modifier(self.ability_score(ability))
+    }
+
+    /// The codes of every item the enemy carries.
+    pub fn equipment_codes(&self) -> impl Iterator<Item = &str> {
+        self.weapons
+            .iter()
+            .chain(self.armor.iter())
+            .map(|equipment| equipment.code.as_str())
+    }
+}
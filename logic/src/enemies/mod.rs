@@ -0,0 +1,3 @@
+pub mod enemy;
+
+pub use enemy::*;
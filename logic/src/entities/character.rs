@@ -1,13 +1,36 @@
-use std::collections::HashMap;
+use crate::abilities::{ability_modifier, Ability, AbilityScores, DetailedAbilityScore};
+use crate::bonuses::{AppliedBonus, Bonus, BonusSet, BonusTarget};
+use crate::classes::{CharacterClass, ClassFeature};
+use crate::conditions::{
+    ConditionChange, ConditionDefinition, ConditionSave, ConditionSaveRoll, Conditions, RoundReport,
+};
+use crate::progression::{ExperienceTable, HitPointMethod, LevelGain, LevelUp, LevelUpError};
+use crate::races::{visibility, LightLevel, Race, Sense, Visibility};
+use crate::skills::CharacterSkill;
+use crate::themes::{Theme, ThemeFeature};
+use crate::utilities::{
+    d20_expression, roll_dice_check_with, CompiledExpression, DiceRng, DiceRollError,
+    ExpressionContext, ExpressionError,
+};
 
-use crate::abilities::{Ability, AbilityScores, DetailedAbilityScore};
-use crate::races::Race;
- // Add this line to import the Race type
+/// ## CharacterIdentity
+/// Where a character is stored.  The rules never read it, but it is kept so that a character
+/// can be converted to the wire format and back without losing it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharacterIdentity {
+    pub id: Option<i64>,
+    pub code: Option<String>,
+    pub world_id: Option<i64>,
+    pub user_id: Option<i64>,
+}
 
 pub struct Character {
+    identity: CharacterIdentity,
     name: String,
     level: i32,
     class: String,
+    theme: String,
+    theme_definition: Option<Theme>,
     race: Race,
     strength: i32,
     dexterity: i32,
@@ -15,21 +38,25 @@ pub struct Character {
     intelligence: i32,
     wisdom: i32,
     charisma: i32,
+    bonuses: BonusSet,
+    experience: i64,
+    hit_points: i32,
+    stamina: i32,
+    skill_ranks: i32,
+    features: Vec<ClassFeature>,
+    conditions: Conditions,
+    feats: Vec<String>,
+    skills: Vec<CharacterSkill>,
 }
 
 impl AbilityScores for Character {
     fn get_ability_score(&self, ability: Ability) -> i32 {
-        let score = self.get_raw_ability_score(ability);
-        let ability_score_modifier = self.race.ability_modifiers.get(&ability);
-        match ability_score_modifier {
-            Some(modifier) => score + modifier,
-            None => score,
-        }
+        self.get_raw_ability_score(ability)
+            + self.all_bonuses().total(&BonusTarget::Ability(ability))
     }
 
     fn get_modifier(&self, ability: Ability) -> i32 {
-        let score = self.get_ability_score(ability);
-        (score - 10) / 2
+        ability_modifier(self.get_ability_score(ability))
     }
 
     fn get_raw_ability_score(&self, ability: Ability) -> i32 {
@@ -44,29 +71,16 @@ impl AbilityScores for Character {
     }
 
     fn get_detail_ability_score(&self, ability: Ability) -> DetailedAbilityScore {
-        let score = self.get_raw_ability_score(ability);
-        let ability_score_modifier = self.race.ability_modifiers.get(&ability);
-
-        match ability_score_modifier {
-            Some(modifier) => {
-                let mut detailed = DetailedAbilityScore {
-                    ability,
-                    score: score + modifier,
-                    modifiers: HashMap::new(),
-                };
-                detailed.modifiers.insert("racial".to_string(), *modifier);
-                detailed
-            }
-            None => DetailedAbilityScore {
-                ability,
-                score,
-                modifiers: HashMap::new(),
-            },
-        }
+        DetailedAbilityScore::new(
+            ability,
+            self.get_raw_ability_score(ability),
+            self.all_bonuses().applied(&BonusTarget::Ability(ability)),
+        )
     }
 }
 
 impl Character {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: String,
         level: i32,
@@ -80,9 +94,12 @@ impl Character {
         charisma: i32,
     ) -> Self {
         Character {
+            identity: CharacterIdentity::default(),
             name,
             level,
             class,
+            theme: String::new(),
+            theme_definition: None,
             race,
             strength,
             dexterity,
@@ -90,15 +107,438 @@ impl Character {
             intelligence,
             wisdom,
             charisma,
+            bonuses: BonusSet::new(),
+            experience: 0,
+            hit_points: 0,
+            stamina: 0,
+            skill_ranks: 0,
+            features: vec![],
+            conditions: Conditions::default(),
+            feats: vec![],
+            skills: vec![],
+        }
+    }
+
+    pub fn identity(&self) -> &CharacterIdentity {
+        &self.identity
+    }
+
+    pub fn set_identity(&mut self, identity: CharacterIdentity) {
+        self.identity = identity;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    /// The code of the character's theme, if they have one.
+    pub fn theme(&self) -> &str {
+        &self.theme
+    }
+
+    /// Set the character's theme by code.  The rules of a different theme the character had
+    /// no longer apply.
+    pub fn set_theme(&mut self, theme: &str) {
+        self.theme = theme.to_string();
+        if self
+            .theme_definition
+            .as_ref()
+            .is_some_and(|definition| definition.code != theme)
+        {
+            self.theme_definition = None;
+        }
+    }
+
+    /// ## apply_theme
+    /// Give the character a theme.  Its ability bonuses apply to their ability scores and they
+    /// have its features up to their level.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        self.theme = theme.code.clone();
+        self.theme_definition = Some(theme.clone());
+    }
+
+    /// The features the character has from their theme at their level.
+    pub fn theme_features(&self) -> Vec<&ThemeFeature> {
+        self.theme_definition
+            .iter()
+            .flat_map(|theme| theme.features_up_to(self.level))
+            .collect()
+    }
+
+    pub fn race(&self) -> &Race {
+        &self.race
+    }
+
+    pub fn experience(&self) -> i64 {
+        self.experience
+    }
+
+    pub fn add_experience(&mut self, experience: i64) {
+        self.experience = self.experience.saturating_add(experience).max(0);
+    }
+
+    /// ## grant_class_features
+    /// Give the character every feature `class` grants up to their level, replacing the
+    /// features they had, e.g. when loading a character that was stored without them.
+    pub fn grant_class_features(&mut self, class: &CharacterClass) {
+        self.features = class
+            .features
+            .iter()
+            .filter(|feature| feature.level <= self.level)
+            .cloned()
+            .collect();
+    }
+
+    /// ## restore
+    /// Restore the experience, hit points and stamina a character was stored with.
+    pub fn restore(&mut self, experience: i64, hit_points: i32, stamina: i32) {
+        self.experience = experience.max(0);
+        self.hit_points = hit_points;
+        self.stamina = stamina;
+    }
+
+    /// The hit points gained from levelling.
+    pub fn hit_points(&self) -> i32 {
+        self.hit_points
+    }
+
+    /// The stamina gained from levelling.
+    pub fn stamina(&self) -> i32 {
+        self.stamina
+    }
+
+    /// The skill ranks gained from levelling.
+    pub fn skill_ranks(&self) -> i32 {
+        self.skill_ranks
+    }
+
+    /// The class features the character has been granted by levelling.
+    pub fn features(&self) -> &[ClassFeature] {
+        &self.features
+    }
+
+    /// The codes of the feats the character has taken.
+    pub fn feats(&self) -> &[String] {
+        &self.feats
+    }
+
+    pub fn has_feat(&self, code: &str) -> bool {
+        self.feats
+            .iter()
+            .any(|feat| feat.eq_ignore_ascii_case(code))
+    }
+
+    /// Record a feat the character has taken.  Check its
+    /// [`crate::feats::Prerequisites`] first.
+    pub fn add_feat(&mut self, code: &str) {
+        if !self.has_feat(code) {
+            self.feats.push(code.to_string());
+        }
+    }
+
+    /// The ranks the character has put into each skill.
+    pub fn skills(&self) -> &[CharacterSkill] {
+        &self.skills
+    }
+
+    /// The character's ranks in a skill, if they have any.
+    pub fn skill(&self, code: &str) -> Option<&CharacterSkill> {
+        self.skills
+            .iter()
+            .find(|skill| skill.skill_code.eq_ignore_ascii_case(code))
+    }
+
+    /// Set the character's ranks in a skill, replacing any they had.
+    pub fn set_skill(&mut self, skill: CharacterSkill) {
+        self.skills
+            .retain(|existing| !existing.skill_code.eq_ignore_ascii_case(&skill.skill_code));
+        self.skills.push(skill);
+    }
+
+    /// Whether the character has the experience for the next level of `table`.
+    pub fn can_level_up(&self, table: &ExperienceTable) -> bool {
+        table
+            .experience_for(self.level + 1)
+            .is_some_and(|needed| self.experience >= needed)
+    }
+
+    /// ## level_up
+    /// Move the character to the next level of their class.
+    ///
+    /// ### Arguments
+    /// * `class` - The character's class.
+    /// * `table` - The world's experience table.
+    /// * `method` - How the hit points for the new level are found.
+    /// * `rng` - The generator used when hit points are rolled or the class expressions roll
+    ///   dice.
+    ///
+    /// ### Returns
+    /// A record of what changed, or why the character could not level up, in which case the
+    /// character is unchanged.  Stamina and skill ranks are evaluated at the new level.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::classes::CharacterClass;
+    /// use crate::logic::entities::Character;
+    /// use crate::logic::progression::{ExperienceTable, HitPointMethod};
+    /// use crate::logic::races::load_default_races;
+    ///
+    /// let races = load_default_races();
+    /// let class = CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT").unwrap();
+    /// let mut character = Character::new(
+    ///     "Kira".to_string(), 1, "mechanic".to_string(), races["Human"].clone(),
+    ///     10, 10, 13, 15, 10, 10,
+    /// );
+    /// character.add_experience(1_300);
+    ///
+    /// let change = character
+    ///     .level_up(&class, &ExperienceTable::default(), HitPointMethod::Fixed, &mut rand::thread_rng())
+    ///     .unwrap();
+    /// assert_eq!(change.to_level, 2);
+    /// assert_eq!(change.hit_points, 6);
+    /// assert_eq!(change.stamina, 12);
+    /// assert_eq!(change.skill_ranks, 7);
+    /// assert_eq!(character.level(), 2);
+    /// ```
+    pub fn level_up<R: DiceRng + ?Sized>(
+        &mut self,
+        class: &CharacterClass,
+        table: &ExperienceTable,
+        method: HitPointMethod,
+        rng: &mut R,
+    ) -> Result<LevelUp, LevelUpError> {
+        if !class.code.eq_ignore_ascii_case(&self.class) {
+            return Err(LevelUpError::WrongClass {
+                expected: self.class.clone(),
+                actual: class.code.clone(),
+            });
+        }
+
+        let to_level = self.level + 1;
+        let needed = table
+            .experience_for(to_level)
+            .ok_or(LevelUpError::MaximumLevel(self.level))?;
+        if self.experience < needed {
+            return Err(LevelUpError::NotEnoughExperience {
+                level: to_level,
+                needed,
+                experience: self.experience,
+            });
+        }
+
+        let (gain, hit_point_roll) = LevelGain::roll(self, class, to_level, method, rng)?;
+        let features: Vec<ClassFeature> = class.features_at(to_level).cloned().collect();
+
+        let change = LevelUp {
+            class: class.code.clone(),
+            from_level: self.level,
+            to_level,
+            hit_points: gain.hit_points,
+            hit_point_roll,
+            stamina: gain.stamina,
+            skill_ranks: gain.skill_ranks,
+            features,
+        };
+
+        self.level = to_level;
+        self.hit_points = self.hit_points.saturating_add(change.hit_points);
+        self.stamina = self.stamina.saturating_add(change.stamina);
+        self.skill_ranks = self.skill_ranks.saturating_add(change.skill_ranks);
+        self.features.extend(change.features.iter().cloned());
+
+        Ok(change)
+    }
+
+    /// The bonuses from equipment and effects.  Racial bonuses come from the race and are not
+    /// included.
+    pub fn bonuses(&self) -> &BonusSet {
+        &self.bonuses
+    }
+
+    /// ## add_bonus
+    /// Add a bonus from equipment or an effect, e.g. an enhancement bonus to strength from a
+    /// belt.
+    pub fn add_bonus(&mut self, bonus: Bonus) {
+        self.bonuses.add(bonus);
+    }
+
+    /// ## remove_bonuses_from
+    /// Remove every bonus from a source, e.g. when an item is unequipped or an effect ends.
+    pub fn remove_bonuses_from(&mut self, source: &str) -> usize {
+        self.bonuses.remove_source(source)
+    }
+
+    /// The conditions the character has.
+    pub fn conditions(&self) -> &Conditions {
+        &self.conditions
+    }
+
+    /// Replace the character's conditions, e.g. with those saved when they were last stored.
+    pub fn set_conditions(&mut self, conditions: Conditions) {
+        self.conditions = conditions;
+    }
+
+    /// ## apply_condition
+    /// Give the character a condition.  Its effects apply to their ability scores, skills and
+    /// attacks until it ends.
+    pub fn apply_condition(&mut self, condition: &ConditionDefinition) -> ConditionChange {
+        self.conditions.apply(condition)
+    }
+
+    pub fn remove_condition(&mut self, code: &str) -> bool {
+        self.conditions.remove(code)
+    }
+
+    /// ## saving_throw_bonus
+    /// The bonus to a saving throw: the ability modifier plus every bonus to the save.
+    ///
+    /// ### Arguments
+    /// * `save` - The saving throw, e.g. `fortitude`.
+    /// * `ability` - The ability the save uses, e.g. constitution.
+    pub fn saving_throw_bonus(&self, save: &str, ability: Ability) -> i32 {
+        self.get_modifier(ability).saturating_add(
+            self.all_bonuses()
+                .total(&BonusTarget::Save(save.to_string())),
+        )
+    }
+
+    /// ## saving_throw_bonus_against
+    /// The bonus to a saving throw against a damage type or condition: the saving throw bonus
+    /// plus any racial bonus against it, e.g. +2 fortitude against `poisoned`.
+    pub fn saving_throw_bonus_against(&self, save: &str, ability: Ability, against: &str) -> i32 {
+        let mut bonuses = self.all_bonuses();
+        bonuses.extend(self.race.save_bonuses(save, against));
+        self.get_modifier(ability)
+            .saturating_add(bonuses.total(&BonusTarget::Save(save.to_string())))
+    }
+
+    /// The senses the character has from their race, with their ranges in feet.
+    pub fn senses(&self) -> Vec<(Sense, i32)> {
+        self.race.senses()
+    }
+
+    /// ## visibility
+    /// How well the character sees something `distance` feet away in the given light, using
+    /// their senses.
+    pub fn visibility(&self, light: LightLevel, distance: i32) -> Visibility {
+        visibility(&self.senses(), light, distance)
+    }
+
+    /// The proficiencies the character has from their race.  Class proficiencies come from
+    /// the class.
+    pub fn proficiencies(&self) -> Vec<&str> {
+        self.race.proficiencies()
+    }
+
+    pub fn is_proficient(&self, proficiency: &str) -> bool {
+        self.proficiencies()
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(proficiency))
+    }
+
+    /// ## end_round
+    /// End a combat round for the character.  They make a saving throw against each condition
+    /// that allows one, then every condition counts down a round.
+    ///
+    /// ### Returns
+    /// The saving throws and the conditions that ended, or an error if a save could not be
+    /// rolled.
+    pub fn end_round<R: DiceRng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<RoundReport, DiceRollError> {
+        let mut report = RoundReport::default();
+
+        let saves: Vec<(String, ConditionSave)> = self
+            .conditions
+            .active()
+            .iter()
+            .filter_map(|active| {
+                active
+                    .definition
+                    .save
+                    .clone()
+                    .map(|save| (active.code().to_string(), save))
+            })
+            .collect();
+        for (code, save) in saves {
+            let bonus = self.saving_throw_bonus_against(&save.save, save.ability, &code);
+            let roll = roll_dice_check_with(d20_expression(bonus), save.dc, rng)?;
+            if roll.is_success() {
+                self.conditions.remove(&code);
+                report.ended.push(code.clone());
+            }
+            report.saves.push(ConditionSaveRoll {
+                condition: code,
+                success: roll.is_success(),
+                roll: roll.result().clone(),
+            });
+        }
+
+        report.ended.extend(self.conditions.tick_rounds(1));
+        Ok(report)
+    }
+
+    /// Let `seconds` of real time pass, and return the codes of the conditions that ran out.
+    pub fn pass_time(&mut self, seconds: u64) -> Vec<String> {
+        self.conditions.tick_seconds(seconds)
+    }
+
+    /// ## applied_bonuses
+    /// Every racial, equipment and effect bonus to `target`, and whether each one applies.
+    pub fn applied_bonuses(&self, target: &BonusTarget) -> Vec<AppliedBonus> {
+        self.all_bonuses().applied(target)
+    }
+
+    /// The total of every bonus to `target` that applies, e.g. to armor class or attack rolls.
+    pub fn bonus_total(&self, target: &BonusTarget) -> i32 {
+        self.all_bonuses().total(target)
+    }
+
+    /// The racial bonuses followed by every other bonus.
+    fn all_bonuses(&self) -> BonusSet {
+        let mut bonuses: BonusSet = self.race.bonuses().into_iter().collect();
+        if let Some(theme) = &self.theme_definition {
+            bonuses.extend(theme.bonuses());
         }
+        bonuses.extend(self.bonuses.bonuses().iter().cloned());
+        bonuses.extend(self.conditions.bonuses());
+        bonuses
+    }
+
+    /// ## expression_context
+    /// The context class expressions are evaluated against: ability modifiers and scores,
+    /// `LEVEL`, `CLASS`, `RACE` and `SPEED`.
+    pub fn expression_context(&self) -> ExpressionContext {
+        ExpressionContext::from_ability_scores(self)
+            .with_level(self.level)
+            .with_class(&self.class)
+            .with_race(&self.race)
+    }
+
+    /// ## evaluate
+    /// Evaluate an expression such as a class `stamina_expression` for this character.
+    ///
+    /// ### Arguments
+    /// * `expression` - The compiled expression, e.g. `10 + CON`.
+    pub fn evaluate(&self, expression: &CompiledExpression) -> Result<i64, ExpressionError> {
+        expression.evaluate(&self.expression_context())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    
-
     use super::*;
+    use crate::bonuses::BonusType;
+    use crate::utilities::ScriptedRng;
     use crate::{abilities::ability::Ability, load_default_races};
 
     #[test]
@@ -126,4 +566,305 @@ mod tests {
         assert_eq!(character.get_ability_score(Ability::Wisdom), 11);
         assert_eq!(character.get_ability_score(Ability::Charisma), 11);
     }
+
+    #[test]
+    fn test_modifiers_round_down_below_10() {
+        let character = Character::new(
+            "Test".to_string(),
+            1,
+            "Fighter".to_string(),
+            load_default_races()["Human"].clone(),
+            8,
+            6,
+            9,
+            10,
+            2,
+            10,
+        );
+
+        // the human bonus makes these 9, 7, 10, 11 and 3
+        assert_eq!(character.get_modifier(Ability::Strength), -1);
+        assert_eq!(character.get_modifier(Ability::Dexterity), -2);
+        assert_eq!(character.get_modifier(Ability::Constitution), 0);
+        assert_eq!(character.get_modifier(Ability::Intelligence), 0);
+        assert_eq!(character.get_modifier(Ability::Wisdom), -4);
+    }
+
+    #[test]
+    fn test_evaluate_class_expressions() {
+        let races = load_default_races();
+        let character = Character::new(
+            "Test".to_string(),
+            3,
+            "mechanic".to_string(),
+            races["Dwarf"].clone(),
+            12,
+            10,
+            14,
+            15,
+            8,
+            10,
+        );
+        let constitution = character.get_modifier(Ability::Constitution);
+        let intelligence = character.get_modifier(Ability::Intelligence);
+
+        let stamina = CompiledExpression::compile("10 + CON").unwrap();
+        let skill_points = CompiledExpression::compile("4 + INT").unwrap();
+
+        assert_eq!(
+            character.evaluate(&stamina).unwrap(),
+            10 + constitution as i64
+        );
+        assert_eq!(
+            character.evaluate(&skill_points).unwrap(),
+            4 + intelligence as i64
+        );
+
+        let context = character.expression_context();
+        assert_eq!(
+            context.get("CON_SCORE").unwrap(),
+            &character
+                .get_ability_score(Ability::Constitution)
+                .to_string()
+        );
+        assert_eq!(context.get("LEVEL").unwrap(), "3");
+        assert_eq!(context.get("CLASS").unwrap(), "mechanic");
+        assert_eq!(context.get("RACE").unwrap(), "Dwarf");
+    }
+
+    #[test]
+    fn test_bonuses_change_scores_and_breakdown() {
+        let races = load_default_races();
+        let mut character = Character::new(
+            "Test".to_string(),
+            1,
+            "Fighter".to_string(),
+            races["Dwarf"].clone(),
+            14,
+            10,
+            13,
+            10,
+            10,
+            10,
+        );
+        let strength = BonusTarget::Ability(Ability::Strength);
+        character.add_bonus(Bonus::new(
+            "Belt of Giant Strength",
+            BonusType::Enhancement,
+            strength.clone(),
+            4,
+        ));
+        character.add_bonus(Bonus::new(
+            "Bull's Strength",
+            BonusType::Enhancement,
+            strength.clone(),
+            2,
+        ));
+        character.add_bonus(Bonus::new("Rage", BonusType::Morale, strength, 2));
+
+        assert_eq!(character.get_ability_score(Ability::Strength), 20);
+        assert_eq!(character.get_modifier(Ability::Strength), 5);
+
+        let detail = character.get_detail_ability_score(Ability::Strength);
+        assert_eq!(detail.base, 14);
+        assert_eq!(detail.score, 20);
+        assert_eq!(detail.modifiers["enhancement"], 4);
+        assert_eq!(detail.modifiers["morale"], 2);
+        assert_eq!(detail.bonuses.len(), 3);
+        assert!(!detail.bonuses[1].applied);
+
+        let constitution = character.get_detail_ability_score(Ability::Constitution);
+        assert_eq!(constitution.score, 15);
+        assert_eq!(constitution.modifiers["racial"], 2);
+        assert_eq!(constitution.bonuses[0].bonus.source, "Dwarf");
+
+        assert_eq!(character.remove_bonuses_from("Belt of Giant Strength"), 1);
+        assert_eq!(character.get_ability_score(Ability::Strength), 18);
+    }
+
+    #[test]
+    fn test_theme_bonuses_and_features_apply() {
+        let mut character = level_one_mechanic();
+        let dexterity = character.get_ability_score(Ability::Dexterity);
+        let feature = |level: i32, code: &str| ThemeFeature {
+            level,
+            code: code.to_string(),
+            name: code.to_string(),
+            description: code.to_string(),
+        };
+        let ace_pilot = Theme::new("ace-pilot", "Ace Pilot")
+            .with_ability_bonus(Ability::Dexterity, 1)
+            .with_feature(feature(1, "theme-knowledge"))
+            .with_feature(feature(6, "lone-wolf"));
+
+        character.apply_theme(&ace_pilot);
+        assert_eq!(character.theme(), "ace-pilot");
+        assert_eq!(
+            character.get_ability_score(Ability::Dexterity),
+            dexterity + 1
+        );
+        assert_eq!(character.theme_features().len(), 1);
+
+        character.set_theme("bounty-hunter");
+        assert_eq!(character.get_ability_score(Ability::Dexterity), dexterity);
+        assert!(character.theme_features().is_empty());
+    }
+
+    fn mechanic() -> CharacterClass {
+        CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT")
+            .unwrap()
+            .with_feature(ClassFeature {
+                level: 2,
+                code: "mechanic-overload".to_string(),
+                name: "Overload".to_string(),
+                description: "Overload a device.".to_string(),
+            })
+    }
+
+    fn level_one_mechanic() -> Character {
+        let races = load_default_races();
+        Character::new(
+            "Kira".to_string(),
+            1,
+            "mechanic".to_string(),
+            races["Human"].clone(),
+            10,
+            10,
+            13,
+            7,
+            10,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_level_up_records_changes() {
+        let mut character = level_one_mechanic();
+        let table = ExperienceTable::new(vec![0, 100, 300]).unwrap();
+        character.add_experience(150);
+        assert!(character.can_level_up(&table));
+
+        let mut rng = ScriptedRng::new(vec![4]);
+        let change = character
+            .level_up(&mechanic(), &table, HitPointMethod::Roll, &mut rng)
+            .unwrap();
+
+        assert_eq!(change.from_level, 1);
+        assert_eq!(change.to_level, 2);
+        assert_eq!(change.hit_points, 4);
+        assert_eq!(change.hit_point_roll.unwrap().to_string(), "1d6 (4) = 4");
+        assert_eq!(change.stamina, 12);
+        // 4 + INT is 3 with an intelligence of 8, but a level always grants a rank
+        assert_eq!(change.skill_ranks, 3);
+        assert_eq!(change.features.len(), 1);
+        assert_eq!(change.features[0].code, "mechanic-overload");
+
+        assert_eq!(character.level(), 2);
+        assert_eq!(character.hit_points(), 4);
+        assert_eq!(character.stamina(), 12);
+        assert_eq!(character.skill_ranks(), 3);
+        assert_eq!(character.features().len(), 1);
+        assert!(!character.can_level_up(&table));
+    }
+
+    #[test]
+    fn test_level_up_errors_leave_the_character_unchanged() {
+        let mut character = level_one_mechanic();
+        let table = ExperienceTable::new(vec![0, 100]).unwrap();
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(
+            character
+                .level_up(&mechanic(), &table, HitPointMethod::Fixed, &mut rng)
+                .unwrap_err()
+                .to_string(),
+            "level 2 needs 100 experience, but the character has 0"
+        );
+
+        character.add_experience(500);
+        let soldier = CharacterClass::new("soldier", "Soldier", 7, "7 + CON", "4 + INT").unwrap();
+        assert!(matches!(
+            character.level_up(&soldier, &table, HitPointMethod::Fixed, &mut rng),
+            Err(LevelUpError::WrongClass { .. })
+        ));
+        assert_eq!(character.level(), 1);
+
+        let change = character
+            .level_up(&mechanic(), &table, HitPointMethod::Average, &mut rng)
+            .unwrap();
+        assert_eq!(change.hit_points, 4);
+        assert_eq!(
+            character.level_up(&mechanic(), &table, HitPointMethod::Fixed, &mut rng),
+            Err(LevelUpError::MaximumLevel(2))
+        );
+        assert_eq!(character.hit_points(), 4);
+    }
+
+    #[test]
+    fn test_conditions_affect_scores_and_end_on_a_save() {
+        let mut character = level_one_mechanic();
+        let poisoned = crate::conditions::load_default_conditions()["poisoned"].clone();
+        let strength = character.get_ability_score(Ability::Strength);
+
+        character.apply_condition(&poisoned);
+        character.apply_condition(&poisoned);
+        assert_eq!(character.get_ability_score(Ability::Strength), strength - 4);
+
+        // constitution 14 - 4 from two stacks is a +0 modifier against DC 13
+        let report = character
+            .end_round(&mut ScriptedRng::new(vec![12]))
+            .unwrap();
+        assert!(!report.saves[0].success);
+        assert!(report.ended.is_empty());
+
+        let report = character
+            .end_round(&mut ScriptedRng::new(vec![13]))
+            .unwrap();
+        assert!(report.saves[0].success);
+        assert_eq!(report.ended, vec!["poisoned".to_string()]);
+        assert_eq!(character.get_ability_score(Ability::Strength), strength);
+    }
+
+    #[test]
+    fn test_special_abilities_apply_to_saves_and_senses() {
+        let races = load_default_races();
+        let mut dwarf = Character::new(
+            "Brom".to_string(),
+            1,
+            "mechanic".to_string(),
+            races["Dwarf"].clone(),
+            10,
+            10,
+            12,
+            10,
+            10,
+            10,
+        );
+        let poisoned = crate::conditions::load_default_conditions()["poisoned"].clone();
+
+        // constitution 12 + 2 is a +2 modifier, and dwarven resilience adds 2 against poison
+        assert_eq!(
+            dwarf.saving_throw_bonus("fortitude", Ability::Constitution),
+            2
+        );
+        assert_eq!(
+            dwarf.saving_throw_bonus_against("fortitude", Ability::Constitution, "poisoned"),
+            4
+        );
+
+        // poison lowers constitution to 12, so 10 + 1 + 2 only makes DC 13 with the racial bonus
+        dwarf.apply_condition(&poisoned);
+        let report = dwarf.end_round(&mut ScriptedRng::new(vec![10])).unwrap();
+        assert!(report.saves[0].success);
+
+        assert_eq!(
+            dwarf.visibility(LightLevel::Darkness, 60),
+            Visibility::Clear
+        );
+        assert_eq!(
+            dwarf.visibility(LightLevel::Darkness, 65),
+            Visibility::Hidden
+        );
+        assert!(dwarf.is_proficient("Battleaxe"));
+    }
 }
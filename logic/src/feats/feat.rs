@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use super::Prerequisites;
+
+/// ## Feat
+/// A feat a world defines, with the prerequisites a character must meet to take it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feat {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub prerequisites: Prerequisites,
+    #[serde(default)]
+    pub benefits: String,
+}
+
+impl Feat {
+    pub fn new(code: &str, name: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            prerequisites: Prerequisites::default(),
+            benefits: String::new(),
+        }
+    }
+
+    pub fn with_prerequisites(mut self, prerequisites: Prerequisites) -> Self {
+        self.prerequisites = prerequisites;
+        self
+    }
+}
@@ -0,0 +1,5 @@
+pub mod feat;
+pub mod prerequisite;
+
+pub use feat::*;
+pub use prerequisite::*;
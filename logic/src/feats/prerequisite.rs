@@ -0,0 +1,459 @@
+use std::str::FromStr;
+use std::{error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::{Ability, AbilityScores};
+use crate::entities::Character;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrerequisiteError {
+    clause: String,
+    message: String,
+}
+
+impl PrerequisiteError {
+    fn new(clause: &str, message: &str) -> Self {
+        Self {
+            clause: clause.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// The clause that could not be read, e.g. `DEX fifteen`.
+    pub fn clause(&self) -> &str {
+        &self.clause
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for PrerequisiteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid prerequisite '{}': {}",
+            self.clause, self.message
+        )
+    }
+}
+
+impl error::Error for PrerequisiteError {}
+
+/// ## Prerequisite
+/// A single requirement a character must meet to take a feat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prerequisite {
+    /// A minimum ability score, including bonuses, e.g. `DEX 15`.
+    Ability { ability: Ability, minimum: i32 },
+    /// A minimum character level, e.g. `level 5`.
+    Level(i32),
+    /// A class and a minimum level in it, e.g. `class soldier 3`.
+    ClassLevel { class: String, level: i32 },
+    /// Another feat, e.g. `feat weapon-focus`.
+    Feat(String),
+    /// A minimum number of ranks in a skill, e.g. `ranks computers 5`.
+    SkillRanks { skill: String, ranks: i32 },
+    /// A race, by code or name, e.g. `race dwarf` or `race Dwarf`.
+    Race(String),
+    /// Any one of several requirements, e.g. `STR 13 or DEX 13`.
+    AnyOf(Vec<Prerequisite>),
+}
+
+impl Prerequisite {
+    /// Whether the character meets the requirement.
+    pub fn is_met(&self, character: &Character) -> bool {
+        self.unmet_reason(character).is_none()
+    }
+
+    /// Why the character does not meet the requirement, or `None` if they do.
+    pub fn unmet_reason(&self, character: &Character) -> Option<String> {
+        let met = match self {
+            Prerequisite::Ability { ability, minimum } => {
+                let score = character.get_ability_score(*ability);
+                if score >= *minimum {
+                    return None;
+                }
+                return Some(format!("requires {}, but has {}", self, score));
+            }
+            Prerequisite::Level(level) => {
+                if character.level() >= *level {
+                    return None;
+                }
+                return Some(format!(
+                    "requires {}, but is level {}",
+                    self,
+                    character.level()
+                ));
+            }
+            Prerequisite::ClassLevel { class, level } => {
+                character.class().eq_ignore_ascii_case(class) && character.level() >= *level
+            }
+            Prerequisite::Feat(feat) => character.has_feat(feat),
+            Prerequisite::SkillRanks { skill, ranks } => {
+                let has = character.skill(skill).map(|skill| skill.ranks).unwrap_or(0);
+                if has >= *ranks {
+                    return None;
+                }
+                return Some(format!("requires {}, but has {}", self, has));
+            }
+            Prerequisite::Race(race) => {
+                character.race().code.eq_ignore_ascii_case(race)
+                    || character.race().name.eq_ignore_ascii_case(race)
+            }
+            Prerequisite::AnyOf(prerequisites) => prerequisites
+                .iter()
+                .any(|prerequisite| prerequisite.is_met(character)),
+        };
+
+        if met {
+            None
+        } else {
+            Some(format!("requires {}", self))
+        }
+    }
+
+    fn parse(clause: &str) -> Result<Prerequisite, PrerequisiteError> {
+        let alternatives: Vec<&str> = clause.split(" or ").map(str::trim).collect();
+        if alternatives.len() > 1 {
+            return alternatives
+                .into_iter()
+                .map(Prerequisite::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Prerequisite::AnyOf);
+        }
+
+        let words: Vec<&str> = clause.split_whitespace().collect();
+        let number = |word: Option<&&str>| -> Result<i32, PrerequisiteError> {
+            match word {
+                Some(word) => word.parse::<i32>().map_err(|_| {
+                    PrerequisiteError::new(clause, &format!("'{}' is not a number", word))
+                }),
+                None => Err(PrerequisiteError::new(clause, "expected a number")),
+            }
+        };
+        let name = |word: Option<&&str>, what: &str| -> Result<String, PrerequisiteError> {
+            match word {
+                Some(word) => Ok(word.to_string()),
+                None => Err(PrerequisiteError::new(
+                    clause,
+                    &format!("expected a {}", what),
+                )),
+            }
+        };
+
+        let (prerequisite, length) = match words.first().map(|word| word.to_ascii_lowercase()) {
+            None => return Err(PrerequisiteError::new(clause, "empty prerequisite")),
+            Some(word) if word == "level" => (Prerequisite::Level(number(words.get(1))?), 2),
+            Some(word) if word == "feat" => (Prerequisite::Feat(name(words.get(1), "feat")?), 2),
+            Some(word) if word == "race" => {
+                // race names may contain spaces, e.g. `race Half Orc`
+                if words.len() < 2 {
+                    return Err(PrerequisiteError::new(clause, "expected a race"));
+                }
+                (Prerequisite::Race(words[1..].join(" ")), words.len())
+            }
+            Some(word) if word == "ranks" => (
+                Prerequisite::SkillRanks {
+                    skill: name(words.get(1), "skill")?,
+                    ranks: number(words.get(2))?,
+                },
+                3,
+            ),
+            Some(word) if word == "class" => {
+                let class = name(words.get(1), "class")?;
+                match words.get(2) {
+                    Some(_) => (
+                        Prerequisite::ClassLevel {
+                            class,
+                            level: number(words.get(2))?,
+                        },
+                        3,
+                    ),
+                    None => (Prerequisite::ClassLevel { class, level: 1 }, 2),
+                }
+            }
+            Some(word) => match Ability::from_name(&word) {
+                Some(ability) => (
+                    Prerequisite::Ability {
+                        ability,
+                        minimum: number(words.get(1))?,
+                    },
+                    2,
+                ),
+                None => {
+                    return Err(PrerequisiteError::new(
+                        clause,
+                        &format!("unknown prerequisite '{}'", words[0]),
+                    ))
+                }
+            },
+        };
+
+        if words.len() > length {
+            return Err(PrerequisiteError::new(
+                clause,
+                &format!("unexpected '{}'", words[length..].join(" ")),
+            ));
+        }
+
+        Ok(prerequisite)
+    }
+}
+
+impl fmt::Display for Prerequisite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Prerequisite::Ability { ability, minimum } => {
+                write!(f, "{} {}", ability.abbreviation(), minimum)
+            }
+            Prerequisite::Level(level) => write!(f, "level {}", level),
+            Prerequisite::ClassLevel { class, level } => write!(f, "class {} {}", class, level),
+            Prerequisite::Feat(feat) => write!(f, "feat {}", feat),
+            Prerequisite::SkillRanks { skill, ranks } => write!(f, "ranks {} {}", skill, ranks),
+            Prerequisite::Race(race) => write!(f, "race {}", race),
+            Prerequisite::AnyOf(prerequisites) => {
+                let alternatives: Vec<String> =
+                    prerequisites.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", alternatives.join(" or "))
+            }
+        }
+    }
+}
+
+/// ## Prerequisites
+/// Everything a character must meet to take a feat, written as comma separated clauses:
+///
+/// * `STR 13` or `strength 13` - a minimum ability score, including bonuses
+/// * `level 5` - a minimum character level
+/// * `class soldier 3` - a class, and optionally a minimum level in it
+/// * `feat weapon-focus` - another feat, by code
+/// * `ranks computers 5` - a minimum number of ranks in a skill, by code
+/// * `race Dwarf` - a race, by code or name
+///
+/// A clause can list alternatives with `or`, e.g. `STR 13 or DEX 13`.  An empty string has no
+/// prerequisites.  Prerequisites serialize as this text.
+///
+/// ### Example
+/// ```
+/// use crate::logic::entities::Character;
+/// use crate::logic::feats::Prerequisites;
+/// use crate::logic::races::load_default_races;
+///
+/// let races = load_default_races();
+/// let character = Character::new(
+///     "Kira".to_string(), 1, "soldier".to_string(), races["Human"].clone(),
+///     14, 11, 10, 10, 10, 10,
+/// );
+///
+/// let prerequisites: Prerequisites = "STR 13, DEX 13 or level 3".parse().unwrap();
+/// assert_eq!(
+///     prerequisites.check(&character),
+///     Err(vec!["requires DEX 13 or level 3".to_string()])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Prerequisites {
+    prerequisites: Vec<Prerequisite>,
+}
+
+impl Prerequisites {
+    pub fn new(prerequisites: Vec<Prerequisite>) -> Self {
+        Self { prerequisites }
+    }
+
+    pub fn prerequisites(&self) -> &[Prerequisite] {
+        &self.prerequisites
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prerequisites.is_empty()
+    }
+
+    /// The prerequisites the character does not meet.
+    pub fn unmet<'a>(&'a self, character: &Character) -> Vec<&'a Prerequisite> {
+        self.prerequisites
+            .iter()
+            .filter(|prerequisite| !prerequisite.is_met(character))
+            .collect()
+    }
+
+    /// ## check
+    /// Whether the character qualifies.
+    ///
+    /// ### Returns
+    /// `Ok` if every prerequisite is met, otherwise why each unmet prerequisite failed.
+    pub fn check(&self, character: &Character) -> Result<(), Vec<String>> {
+        let reasons: Vec<String> = self
+            .prerequisites
+            .iter()
+            .filter_map(|prerequisite| prerequisite.unmet_reason(character))
+            .collect();
+
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons)
+        }
+    }
+}
+
+impl FromStr for Prerequisites {
+    type Err = PrerequisiteError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let prerequisites = text
+            .split(',')
+            .map(str::trim)
+            .filter(|clause| !clause.is_empty())
+            .map(Prerequisite::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Prerequisites::new(prerequisites))
+    }
+}
+
+impl fmt::Display for Prerequisites {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clauses: Vec<String> = self.prerequisites.iter().map(|p| p.to_string()).collect();
+        write!(f, "{}", clauses.join(", "))
+    }
+}
+
+impl TryFrom<String> for Prerequisites {
+    type Error = PrerequisiteError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<Prerequisites> for String {
+    fn from(prerequisites: Prerequisites) -> Self {
+        prerequisites.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::races::load_default_races;
+    use crate::skills::CharacterSkill;
+
+    fn character() -> Character {
+        let mut character = Character::new(
+            "Test".to_string(),
+            3,
+            "mechanic".to_string(),
+            load_default_races()["Dwarf"].clone(),
+            10,
+            14,
+            10,
+            16,
+            10,
+            10,
+        );
+        character.add_feat("skill-focus");
+        character.set_skill(CharacterSkill::new("engineering", 3, true));
+        character
+    }
+
+    #[test]
+    fn test_parse_every_clause() {
+        let prerequisites: Prerequisites =
+            "dexterity 13, level 2, class Mechanic 3, feat skill-focus, ranks engineering 3, race dwarf"
+                .parse()
+                .unwrap();
+
+        assert_eq!(prerequisites.prerequisites().len(), 6);
+        assert_eq!(
+            prerequisites.prerequisites()[0],
+            Prerequisite::Ability {
+                ability: Ability::Dexterity,
+                minimum: 13
+            }
+        );
+        assert_eq!(
+            prerequisites.to_string(),
+            "DEX 13, level 2, class Mechanic 3, feat skill-focus, ranks engineering 3, race dwarf"
+        );
+        assert_eq!(prerequisites.check(&character()), Ok(()));
+    }
+
+    #[test]
+    fn test_unmet_clauses_are_reported() {
+        let prerequisites: Prerequisites =
+            "INT 18, level 5, ranks computers 1, class soldier, feat weapon-focus or race Human"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            prerequisites.check(&character()),
+            Err(vec![
+                "requires INT 18, but has 16".to_string(),
+                "requires level 5, but is level 3".to_string(),
+                "requires ranks computers 1, but has 0".to_string(),
+                "requires class soldier 1".to_string(),
+                "requires feat weapon-focus or race Human".to_string(),
+            ])
+        );
+        assert_eq!(prerequisites.unmet(&character()).len(), 5);
+    }
+
+    #[test]
+    fn test_race_is_matched_by_code_or_name() {
+        let mut race = load_default_races()["Dwarf"].clone();
+        race.name = "Mountain Dwarf".to_string();
+        let character = Character::new(
+            "Test".to_string(),
+            1,
+            "mechanic".to_string(),
+            race,
+            10,
+            10,
+            10,
+            10,
+            10,
+            10,
+        );
+
+        for met in ["race dwarf", "race Mountain Dwarf"] {
+            let prerequisites: Prerequisites = met.parse().unwrap();
+            assert_eq!(prerequisites.check(&character), Ok(()), "{}", met);
+        }
+        let prerequisites: Prerequisites = "race elf".parse().unwrap();
+        assert_eq!(
+            prerequisites.check(&character),
+            Err(vec!["requires race elf".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_invalid_clauses_are_rejected() {
+        let error = "STR 13, DEX fifteen".parse::<Prerequisites>().unwrap_err();
+        assert_eq!(error.clause(), "DEX fifteen");
+        assert_eq!(error.message(), "'fifteen' is not a number");
+
+        let error = "luck 3".parse::<Prerequisites>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid prerequisite 'luck 3': unknown prerequisite 'luck'"
+        );
+
+        let error = "level 3 please".parse::<Prerequisites>().unwrap_err();
+        assert_eq!(error.message(), "unexpected 'please'");
+    }
+
+    #[test]
+    fn test_prerequisites_serialize_as_text() {
+        let prerequisites: Prerequisites = serde_json::from_str("\"STR 13 or DEX 13\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&prerequisites).unwrap(),
+            "\"STR 13 or DEX 13\""
+        );
+        assert!(Prerequisites::default().check(&character()).is_ok());
+    }
+}
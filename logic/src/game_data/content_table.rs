@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use crate::classes::CharacterClass;
+use crate::conditions::ConditionDefinition;
+use crate::damage::DamageType;
+use crate::enemies::Enemy;
+use crate::feats::Feat;
+use crate::items::Item;
+use crate::races::Race;
+use crate::skills::Skill;
+use crate::themes::Theme;
+
+/// ## Content
+/// Something a world defines and refers to by code.
+pub trait Content {
+    fn code(&self) -> &str;
+}
+
+macro_rules! impl_content {
+    ($($content:ty),*) => {
+        $(
+            impl Content for $content {
+                fn code(&self) -> &str {
+                    &self.code
+                }
+            }
+        )*
+    };
+}
+
+impl_content!(
+    Item,
+    Enemy,
+    CharacterClass,
+    Race,
+    Theme,
+    Skill,
+    Feat,
+    DamageType,
+    ConditionDefinition
+);
+
+/// ## ContentTable
+/// Content of one kind, such as every race, keyed by the world that defines it and its code.
+///
+/// ### Example
+/// ```
+/// use crate::logic::game_data::ContentTable;
+/// use crate::logic::items::Item;
+///
+/// let mut items = ContentTable::new();
+/// items.insert("devgalaxy", Item::new("blaster-rifle", "weapon", "Blaster Rifle"));
+/// items.insert("devgalaxy", Item::new("light-armor", "armor", "Light Armor"));
+///
+/// assert_eq!(items.get("devgalaxy", "blaster-rifle").unwrap().name, "Blaster Rifle");
+/// assert!(items.get("otherworld", "blaster-rifle").is_none());
+/// assert_eq!(items.filter("devgalaxy", |item| item.is_weapon()).count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContentTable<T> {
+    entries: BTreeMap<(String, String), T>,
+    /// The world and code of every insert that replaced content, in the order they happened.
+    duplicates: Vec<(String, String)>,
+}
+
+impl<T> Default for ContentTable<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            duplicates: vec![],
+        }
+    }
+}
+
+impl<T: Content> ContentTable<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add content to a world, returning what it replaced with the same code.  A replaced code
+    /// is remembered as a duplicate.
+    pub fn insert(&mut self, world: &str, content: T) -> Option<T> {
+        let key = (world.to_string(), content.code().to_string());
+        let replaced = self.entries.insert(key.clone(), content);
+        if replaced.is_some() {
+            self.duplicates.push(key);
+        }
+        replaced
+    }
+
+    pub fn get(&self, world: &str, code: &str) -> Option<&T> {
+        self.entries.get(&(world.to_string(), code.to_string()))
+    }
+
+    pub fn get_mut(&mut self, world: &str, code: &str) -> Option<&mut T> {
+        self.entries.get_mut(&(world.to_string(), code.to_string()))
+    }
+
+    pub fn contains(&self, world: &str, code: &str) -> bool {
+        self.get(world, code).is_some()
+    }
+
+    pub fn remove(&mut self, world: &str, code: &str) -> Option<T> {
+        self.entries.remove(&(world.to_string(), code.to_string()))
+    }
+
+    /// Add every entry of `other`, replacing content with the same world and code.  Replacing
+    /// content this way is an update, so it is not a duplicate, but `other`'s duplicates are kept.
+    pub fn merge(&mut self, other: Self) {
+        self.entries.extend(other.entries);
+        self.duplicates.extend(other.duplicates);
+    }
+
+    /// The world and code of content that was inserted more than once.
+    pub fn duplicates(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.duplicates
+            .iter()
+            .map(|(world, code)| (world.as_str(), code.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every entry with the world it belongs to, ordered by world and then code.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.entries
+            .iter()
+            .map(|((world, _), content)| (world.as_str(), content))
+    }
+
+    /// The content one world defines, ordered by code.
+    pub fn in_world<'a>(&'a self, world: &'a str) -> impl Iterator<Item = &'a T> {
+        self.entries
+            .iter()
+            .filter(move |((entry_world, _), _)| entry_world == world)
+            .map(|(_, content)| content)
+    }
+
+    /// The content one world defines that matches `predicate`, ordered by code.
+    pub fn filter<'a, P>(&'a self, world: &'a str, predicate: P) -> impl Iterator<Item = &'a T>
+    where
+        P: Fn(&T) -> bool + 'a,
+    {
+        self.in_world(world)
+            .filter(move |content| predicate(content))
+    }
+
+    /// The worlds that define content of this kind.
+    pub fn worlds(&self) -> impl Iterator<Item = &str> {
+        let mut worlds: Vec<&str> = self
+            .entries
+            .keys()
+            .map(|(world, _)| world.as_str())
+            .collect();
+        worlds.dedup();
+        worlds.into_iter()
+    }
+}
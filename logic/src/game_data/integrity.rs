@@ -0,0 +1,778 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Content, ContentTable, GameData};
+use crate::bonuses::BonusTarget;
+use crate::feats::Prerequisite;
+use crate::races::SpecialAbilityEffect;
+
+/// ## ContentKind
+/// A kind of world content that can be referred to by code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Item,
+    ItemCategory,
+    Enemy,
+    CharacterClass,
+    ClassFeature,
+    Race,
+    Theme,
+    ThemeFeature,
+    Skill,
+    Feat,
+    DamageType,
+    Condition,
+}
+
+impl fmt::Display for ContentKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ContentKind::Item => "item",
+            ContentKind::ItemCategory => "item category",
+            ContentKind::Enemy => "enemy",
+            ContentKind::CharacterClass => "class",
+            ContentKind::ClassFeature => "class feature",
+            ContentKind::Race => "race",
+            ContentKind::Theme => "theme",
+            ContentKind::ThemeFeature => "theme feature",
+            ContentKind::Skill => "skill",
+            ContentKind::Feat => "feat",
+            ContentKind::DamageType => "damage type",
+            ContentKind::Condition => "condition",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// ## IntegrityIssue
+/// Something wrong with how a world's content fits together, found by [`IntegrityCheck`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    /// Content refers to a code the world does not define, e.g. an enemy's armor.
+    DanglingReference {
+        world: String,
+        kind: ContentKind,
+        code: String,
+        /// Where the reference is, e.g. `weapons[1]` or `prerequisites`.
+        field: String,
+        target: ContentKind,
+        reference: String,
+    },
+    /// A code is defined more than once where it must be unique.
+    DuplicateCode {
+        world: String,
+        kind: ContentKind,
+        code: String,
+    },
+    /// Content belongs to a world that is not defined.
+    Orphan {
+        world: String,
+        kind: ContentKind,
+        code: String,
+    },
+    /// A property cannot be read, e.g. an item's malformed `damage_modifiers`.
+    InvalidProperty {
+        world: String,
+        kind: ContentKind,
+        code: String,
+        field: String,
+        error: String,
+    },
+}
+
+impl IntegrityIssue {
+    pub fn world(&self) -> &str {
+        match self {
+            IntegrityIssue::DanglingReference { world, .. }
+            | IntegrityIssue::DuplicateCode { world, .. }
+            | IntegrityIssue::Orphan { world, .. }
+            | IntegrityIssue::InvalidProperty { world, .. } => world,
+        }
+    }
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntegrityIssue::DanglingReference {
+                world,
+                kind,
+                code,
+                field,
+                target,
+                reference,
+            } => write!(
+                f,
+                "{}: {} '{}' {} refers to {} '{}', which does not exist",
+                world, kind, code, field, target, reference
+            ),
+            IntegrityIssue::DuplicateCode { world, kind, code } => {
+                write!(
+                    f,
+                    "{}: {} '{}' is defined more than once",
+                    world, kind, code
+                )
+            }
+            IntegrityIssue::Orphan { world, kind, code } => write!(
+                f,
+                "{}: {} '{}' belongs to a world that is not defined",
+                world, kind, code
+            ),
+            IntegrityIssue::InvalidProperty {
+                world,
+                kind,
+                code,
+                field,
+                error,
+            } => write!(
+                f,
+                "{}: {} '{}' {} cannot be read: {}",
+                world, kind, code, field, error
+            ),
+        }
+    }
+}
+
+/// ## IntegrityCheck
+/// Resolves every code reference in a set of worlds, and finds duplicate codes and content
+/// whose world is not defined.
+///
+/// ### Example
+/// ```
+/// use crate::logic::enemies::Enemy;
+/// use crate::logic::game_data::{GameData, IntegrityCheck, IntegrityIssue};
+/// use crate::logic::items::Item;
+///
+/// let mut game_data = GameData::new();
+/// game_data.define_world("devgalaxy");
+/// game_data
+///     .items_mut()
+///     .insert("devgalaxy", Item::new("blaster-rifle", "weapon", "Blaster Rifle").with_category(1));
+/// game_data.enemies_mut().insert("devgalaxy", serde_json::from_str::<Enemy>(r#"{
+///     "code": "pirate", "name": "Pirate", "class": "space pirate", "level": 1,
+///     "hit_points": 10, "stamina": 10, "strength": 10, "dexterity": 14, "constitution": 12,
+///     "intelligence": 10, "wisdom": 10, "charisma": 10,
+///     "weapons": [{ "code": "blaster-rifle" }],
+///     "armor": { "code": "light-armor" }
+/// }"#).unwrap());
+///
+/// let issues = IntegrityCheck::new(&game_data).with_item_categories([1]).run();
+/// assert_eq!(issues.len(), 1);
+/// assert_eq!(
+///     issues[0].to_string(),
+///     "devgalaxy: enemy 'pirate' armor refers to item 'light-armor', which does not exist"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntegrityCheck<'a> {
+    game_data: &'a GameData,
+    item_categories: Option<BTreeSet<i64>>,
+    external: BTreeSet<ContentKind>,
+}
+
+impl<'a> IntegrityCheck<'a> {
+    pub fn new(game_data: &'a GameData) -> Self {
+        Self {
+            game_data,
+            item_categories: None,
+            external: BTreeSet::new(),
+        }
+    }
+
+    /// The item categories that exist.  Item categories are only resolved when they are known.
+    pub fn with_item_categories(mut self, categories: impl IntoIterator<Item = i64>) -> Self {
+        self.item_categories = Some(categories.into_iter().collect());
+        self
+    }
+
+    /// Do not resolve references to a kind of content that is kept somewhere else, e.g. skills
+    /// when the game data only holds what is being imported.
+    pub fn with_external(mut self, kind: ContentKind) -> Self {
+        self.external.insert(kind);
+        self
+    }
+
+    /// ## run
+    /// Check every world in the game data.
+    ///
+    /// ### Returns
+    /// Every issue found: content in undefined worlds first, then duplicate codes, then
+    /// dangling references, each ordered by world and code.
+    pub fn run(&self) -> Vec<IntegrityIssue> {
+        let mut issues = vec![];
+        self.check_orphans(&mut issues);
+        self.check_duplicates(&mut issues);
+        self.check_enemies(&mut issues);
+        self.check_items(&mut issues);
+        self.check_races(&mut issues);
+        self.check_feats(&mut issues);
+        self.check_damage_types(&mut issues);
+        self.check_conditions(&mut issues);
+        issues
+    }
+
+    fn check_orphans(&self, issues: &mut Vec<IntegrityIssue>) {
+        let game_data = self.game_data;
+        let mut orphans = |kind: ContentKind, content: Vec<(&str, &str)>| {
+            for (world, code) in content {
+                if !game_data.is_world_defined(world) {
+                    issues.push(IntegrityIssue::Orphan {
+                        world: world.to_string(),
+                        kind,
+                        code: code.to_string(),
+                    });
+                }
+            }
+        };
+
+        orphans(ContentKind::Item, codes(game_data.items()));
+        orphans(ContentKind::Enemy, codes(game_data.enemies()));
+        orphans(ContentKind::CharacterClass, codes(game_data.classes()));
+        orphans(ContentKind::Race, codes(game_data.races()));
+        orphans(ContentKind::Theme, codes(game_data.themes()));
+        orphans(ContentKind::Skill, codes(game_data.skills()));
+        orphans(ContentKind::Feat, codes(game_data.feats()));
+        orphans(ContentKind::DamageType, codes(game_data.damage_types()));
+        orphans(ContentKind::Condition, codes(game_data.conditions()));
+    }
+
+    fn check_duplicates(&self, issues: &mut Vec<IntegrityIssue>) {
+        let game_data = self.game_data;
+        let mut duplicates = |kind: ContentKind, content: Vec<(&str, &str)>| {
+            for (world, code) in content {
+                issues.push(IntegrityIssue::DuplicateCode {
+                    world: world.to_string(),
+                    kind,
+                    code: code.to_string(),
+                });
+            }
+        };
+
+        duplicates(ContentKind::Item, game_data.items().duplicates().collect());
+        duplicates(
+            ContentKind::Enemy,
+            game_data.enemies().duplicates().collect(),
+        );
+        duplicates(
+            ContentKind::CharacterClass,
+            game_data.classes().duplicates().collect(),
+        );
+        duplicates(ContentKind::Race, game_data.races().duplicates().collect());
+        duplicates(
+            ContentKind::Theme,
+            game_data.themes().duplicates().collect(),
+        );
+        duplicates(
+            ContentKind::Skill,
+            game_data.skills().duplicates().collect(),
+        );
+        duplicates(ContentKind::Feat, game_data.feats().duplicates().collect());
+        duplicates(
+            ContentKind::DamageType,
+            game_data.damage_types().duplicates().collect(),
+        );
+        duplicates(
+            ContentKind::Condition,
+            game_data.conditions().duplicates().collect(),
+        );
+
+        // class feature codes are unique across a world's classes, theme feature codes within
+        // their theme
+        for world in game_data.classes().worlds() {
+            let mut seen = HashSet::new();
+            for class in game_data.classes().in_world(world) {
+                for feature in &class.features {
+                    if !seen.insert(feature.code.as_str()) {
+                        duplicates(ContentKind::ClassFeature, vec![(world, &feature.code)]);
+                    }
+                }
+            }
+        }
+        for (world, theme) in game_data.themes().iter() {
+            let mut seen = HashSet::new();
+            for feature in &theme.features {
+                if !seen.insert(feature.code.as_str()) {
+                    duplicates(ContentKind::ThemeFeature, vec![(world, &feature.code)]);
+                }
+            }
+        }
+    }
+
+    fn check_enemies(&self, issues: &mut Vec<IntegrityIssue>) {
+        for (world, enemy) in self.game_data.enemies().iter() {
+            let mut equipment: Vec<(String, &str)> = enemy
+                .weapons
+                .iter()
+                .enumerate()
+                .map(|(index, weapon)| (format!("weapons[{}]", index), weapon.code.as_str()))
+                .collect();
+            if let Some(armor) = &enemy.armor {
+                equipment.push(("armor".to_string(), armor.code.as_str()));
+            }
+
+            for (field, reference) in equipment {
+                if !self.resolves(ContentKind::Item, world, reference) {
+                    issues.push(dangling(
+                        world,
+                        ContentKind::Enemy,
+                        &enemy.code,
+                        &field,
+                        ContentKind::Item,
+                        reference,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_items(&self, issues: &mut Vec<IntegrityIssue>) {
+        let categories = match &self.item_categories {
+            Some(categories) => categories,
+            None => return,
+        };
+
+        for (world, item) in self.game_data.items().iter() {
+            if let Some(category_id) = item.category_id {
+                if !categories.contains(&category_id) {
+                    issues.push(dangling(
+                        world,
+                        ContentKind::Item,
+                        &item.code,
+                        "category_id",
+                        ContentKind::ItemCategory,
+                        &category_id.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_races(&self, issues: &mut Vec<IntegrityIssue>) {
+        for (world, race) in self.game_data.races().iter() {
+            for special_ability in &race.special_abilities {
+                for effect in &special_ability.effects {
+                    if let SpecialAbilityEffect::SkillBonus { skill, .. } = effect {
+                        if !self.resolves(ContentKind::Skill, world, skill) {
+                            issues.push(dangling(
+                                world,
+                                ContentKind::Race,
+                                &race.code,
+                                &format!("special_abilities.{}", special_ability.code),
+                                ContentKind::Skill,
+                                skill,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_feats(&self, issues: &mut Vec<IntegrityIssue>) {
+        for (world, feat) in self.game_data.feats().iter() {
+            let mut pending: Vec<&Prerequisite> =
+                feat.prerequisites.prerequisites().iter().collect();
+            while let Some(prerequisite) = pending.pop() {
+                let (target, reference) = match prerequisite {
+                    Prerequisite::Feat(code) => (ContentKind::Feat, code),
+                    Prerequisite::SkillRanks { skill, .. } => (ContentKind::Skill, skill),
+                    Prerequisite::ClassLevel { class, .. } => (ContentKind::CharacterClass, class),
+                    Prerequisite::Race(race) => (ContentKind::Race, race),
+                    Prerequisite::AnyOf(alternatives) => {
+                        pending.extend(alternatives);
+                        continue;
+                    }
+                    Prerequisite::Ability { .. } | Prerequisite::Level(_) => continue,
+                };
+
+                if !self.resolves(target, world, reference) {
+                    issues.push(dangling(
+                        world,
+                        ContentKind::Feat,
+                        &feat.code,
+                        "prerequisites",
+                        target,
+                        reference,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Weapon damage types, and the damage types of item and enemy resistances, immunities and
+    /// vulnerabilities.  Item damage modifiers that cannot be read are reported as well.
+    fn check_damage_types(&self, issues: &mut Vec<IntegrityIssue>) {
+        let mut references: Vec<(&str, ContentKind, &str, String, String)> = vec![];
+        for (world, item) in self.game_data.items().iter() {
+            if let Some(weapon) = item.weapon() {
+                references.push((
+                    world,
+                    ContentKind::Item,
+                    &item.code,
+                    "damage_type".to_string(),
+                    weapon.damage_type,
+                ));
+            }
+            let modifiers = match item.damage_modifiers() {
+                Ok(modifiers) => modifiers,
+                Err(error) => {
+                    issues.push(IntegrityIssue::InvalidProperty {
+                        world: world.to_string(),
+                        kind: ContentKind::Item,
+                        code: item.code.clone(),
+                        field: "damage_modifiers".to_string(),
+                        error: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+            for (index, modifier) in modifiers.iter().enumerate() {
+                references.push((
+                    world,
+                    ContentKind::Item,
+                    &item.code,
+                    format!("damage_modifiers[{}]", index),
+                    modifier.damage_type().to_string(),
+                ));
+            }
+        }
+        for (world, enemy) in self.game_data.enemies().iter() {
+            for (index, modifier) in enemy.damage_modifiers.iter().enumerate() {
+                references.push((
+                    world,
+                    ContentKind::Enemy,
+                    &enemy.code,
+                    format!("damage_modifiers[{}]", index),
+                    modifier.damage_type().to_string(),
+                ));
+            }
+        }
+
+        for (world, kind, code, field, reference) in references {
+            if !self.resolves(ContentKind::DamageType, world, &reference) {
+                issues.push(dangling(
+                    world,
+                    kind,
+                    code,
+                    &field,
+                    ContentKind::DamageType,
+                    &reference,
+                ));
+            }
+        }
+    }
+
+    /// The skills condition effects give bonuses or penalties to.
+    fn check_conditions(&self, issues: &mut Vec<IntegrityIssue>) {
+        for (world, condition) in self.game_data.conditions().iter() {
+            for (index, effect) in condition.effects.iter().enumerate() {
+                if let BonusTarget::Skill(skill) = &effect.target {
+                    if !self.resolves(ContentKind::Skill, world, skill) {
+                        issues.push(dangling(
+                            world,
+                            ContentKind::Condition,
+                            &condition.code,
+                            &format!("effects[{}]", index),
+                            ContentKind::Skill,
+                            skill,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether a reference names content the world defines.  Classes and races are matched
+    /// without regard to case, and races by name as well, the way feat prerequisites are met.
+    fn resolves(&self, kind: ContentKind, world: &str, reference: &str) -> bool {
+        if self.external.contains(&kind) {
+            return true;
+        }
+
+        let game_data = self.game_data;
+        match kind {
+            ContentKind::Item => game_data.items().contains(world, reference),
+            ContentKind::Enemy => game_data.enemies().contains(world, reference),
+            ContentKind::Theme => game_data.themes().contains(world, reference),
+            ContentKind::Skill => game_data.skills().contains(world, reference),
+            ContentKind::Feat => game_data.feats().contains(world, reference),
+            ContentKind::DamageType => game_data.damage_types().contains(world, reference),
+            ContentKind::Condition => game_data.world_conditions(world).contains_key(reference),
+            ContentKind::CharacterClass => game_data
+                .classes()
+                .in_world(world)
+                .any(|class| class.code.eq_ignore_ascii_case(reference)),
+            ContentKind::Race => game_data.races().in_world(world).any(|race| {
+                race.code.eq_ignore_ascii_case(reference)
+                    || race.name.eq_ignore_ascii_case(reference)
+            }),
+            ContentKind::ItemCategory => self.item_categories.as_ref().is_none_or(|categories| {
+                reference
+                    .parse()
+                    .is_ok_and(|category_id| categories.contains(&category_id))
+            }),
+            ContentKind::ClassFeature => game_data.classes().in_world(world).any(|class| {
+                class
+                    .features
+                    .iter()
+                    .any(|feature| feature.code == reference)
+            }),
+            ContentKind::ThemeFeature => game_data.themes().in_world(world).any(|theme| {
+                theme
+                    .features
+                    .iter()
+                    .any(|feature| feature.code == reference)
+            }),
+        }
+    }
+}
+
+/// ## check_integrity
+/// Check every world in the game data, with nothing kept elsewhere and item categories not
+/// resolved.  See [`IntegrityCheck`] to change either.
+pub fn check_integrity(game_data: &GameData) -> Vec<IntegrityIssue> {
+    IntegrityCheck::new(game_data).run()
+}
+
+fn codes<T: Content>(table: &ContentTable<T>) -> Vec<(&str, &str)> {
+    table
+        .iter()
+        .map(|(world, content)| (world, content.code()))
+        .collect()
+}
+
+fn dangling(
+    world: &str,
+    kind: ContentKind,
+    code: &str,
+    field: &str,
+    target: ContentKind,
+    reference: &str,
+) -> IntegrityIssue {
+    IntegrityIssue::DanglingReference {
+        world: world.to_string(),
+        kind,
+        code: code.to_string(),
+        field: field.to_string(),
+        target,
+        reference: reference.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::abilities::Ability;
+    use crate::classes::{CharacterClass, ClassFeature};
+    use crate::conditions::{ConditionDefinition, ConditionDuration};
+    use crate::damage::DamageType;
+    use crate::feats::{Feat, Prerequisites};
+    use crate::game_data::load_world_directory;
+    use crate::items::Item;
+    use crate::races::{Race, SpecialAbility};
+    use crate::skills::Skill;
+
+    fn feature(code: &str) -> ClassFeature {
+        ClassFeature {
+            level: 1,
+            code: code.to_string(),
+            name: code.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_duplicates_and_orphans() {
+        let mut game_data = GameData::new();
+        game_data.define_world("devgalaxy");
+        let classes = game_data.classes_mut();
+        classes.insert(
+            "devgalaxy",
+            CharacterClass::new("soldier", "Soldier", 7, "7 + CON", "4 + INT")
+                .unwrap()
+                .with_feature(feature("fighting-style")),
+        );
+        classes.insert(
+            "devgalaxy",
+            CharacterClass::new("soldier", "Soldier", 7, "7 + CON", "4 + INT").unwrap(),
+        );
+        classes.insert(
+            "devgalaxy",
+            CharacterClass::new("envoy", "Envoy", 6, "6 + CON", "8 + INT")
+                .unwrap()
+                .with_feature(feature("skill-expertise"))
+                .with_feature(feature("skill-expertise")),
+        );
+        game_data.skills_mut().insert(
+            "fantasy",
+            Skill::new("arcana", "Arcana", Ability::Intelligence),
+        );
+
+        assert_eq!(
+            check_integrity(&game_data),
+            vec![
+                IntegrityIssue::Orphan {
+                    world: "fantasy".to_string(),
+                    kind: ContentKind::Skill,
+                    code: "arcana".to_string(),
+                },
+                IntegrityIssue::DuplicateCode {
+                    world: "devgalaxy".to_string(),
+                    kind: ContentKind::CharacterClass,
+                    code: "soldier".to_string(),
+                },
+                IntegrityIssue::DuplicateCode {
+                    world: "devgalaxy".to_string(),
+                    kind: ContentKind::ClassFeature,
+                    code: "skill-expertise".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_feat_and_race_references() {
+        let mut game_data = GameData::new();
+        game_data.define_world("devgalaxy");
+        game_data.races_mut().insert(
+            "devgalaxy",
+            Race::new(
+                "Android",
+                Default::default(),
+                vec![SpecialAbility::new("Flat Affect").with_effect(
+                    SpecialAbilityEffect::SkillBonus {
+                        skill: "sense-motive".to_string(),
+                        value: -2,
+                    },
+                )],
+                30,
+            )
+            .with_code("android"),
+        );
+        game_data.skills_mut().insert(
+            "devgalaxy",
+            Skill::new("piloting", "Piloting", Ability::Dexterity),
+        );
+        game_data.feats_mut().insert(
+            "devgalaxy",
+            Feat::new("ace", "Ace").with_prerequisites(
+                "ranks piloting 5, race android or feat barrel-roll"
+                    .parse::<Prerequisites>()
+                    .unwrap(),
+            ),
+        );
+
+        let issues = check_integrity(&game_data);
+        assert_eq!(
+            issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>(),
+            vec![
+                "devgalaxy: race 'android' special_abilities.flat-affect refers to skill 'sense-motive', which does not exist",
+                "devgalaxy: feat 'ace' prerequisites refers to feat 'barrel-roll', which does not exist",
+            ]
+        );
+
+        let issues = IntegrityCheck::new(&game_data)
+            .with_external(ContentKind::Skill)
+            .with_external(ContentKind::Feat)
+            .run();
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_damage_type_references() {
+        let mut game_data = GameData::new();
+        game_data.define_world("devgalaxy");
+        game_data
+            .damage_types_mut()
+            .insert("devgalaxy", DamageType::new("energy", "Energy"));
+        let items = game_data.items_mut();
+        let mut rifle = Item::new("blaster-rifle", "weapon", "Blaster Rifle");
+        rifle.item_properties = serde_json::json!({ "damage": "1d12", "damage_type": "energy" });
+        items.insert("devgalaxy", rifle);
+        let mut sword = Item::new("plasma-sword", "weapon", "Plasma Sword");
+        sword.item_properties = serde_json::json!({ "damage": "1d10", "damage_type": "plasma" });
+        items.insert("devgalaxy", sword);
+        let mut armor = Item::new("shield-armor", "armor", "Shield Armor");
+        armor.item_properties = serde_json::json!({
+            "armor_bonus": 1,
+            "damage_modifiers": [
+                { "modifier": "resistance", "damage_type": "energy", "amount": 5 },
+                { "modifier": "immunity", "damage_type": "sonic" }
+            ]
+        });
+        items.insert("devgalaxy", armor);
+        let mut shield = Item::new("energy-shield", "armor", "Energy Shield");
+        shield.item_properties = serde_json::json!({
+            "damage_modifiers": [{ "modifier": "resistence", "damage_type": "energy", "amount": 5 }]
+        });
+        items.insert("devgalaxy", shield);
+
+        let issues = check_integrity(&game_data);
+        assert_eq!(
+            issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>(),
+            vec![
+                "devgalaxy: item 'energy-shield' damage_modifiers cannot be read: unknown variant `resistence`, expected one of `resistance`, `immunity`, `vulnerability`",
+                "devgalaxy: item 'plasma-sword' damage_type refers to damage type 'plasma', which does not exist",
+                "devgalaxy: item 'shield-armor' damage_modifiers[1] refers to damage type 'sonic', which does not exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_condition_references() {
+        let mut game_data = GameData::new();
+        game_data.define_world("devgalaxy");
+        game_data.skills_mut().insert(
+            "devgalaxy",
+            Skill::new("acrobatics", "Acrobatics", Ability::Dexterity),
+        );
+        let conditions = game_data.conditions_mut();
+        conditions.insert(
+            "devgalaxy",
+            ConditionDefinition::new("stunned", "Stunned", ConditionDuration::Rounds(1))
+                .with_effect(BonusTarget::Skill("acrobatics".to_string()), -4)
+                .with_effect(BonusTarget::Skill("athletics".to_string()), -4),
+        );
+        conditions.insert(
+            "devgalaxy",
+            ConditionDefinition::new("prone", "Prone", ConditionDuration::Permanent),
+        );
+        conditions.insert(
+            "devgalaxy",
+            ConditionDefinition::new("prone", "Prone", ConditionDuration::Permanent),
+        );
+        conditions.insert(
+            "fantasy",
+            ConditionDefinition::new("charmed", "Charmed", ConditionDuration::UntilSaved),
+        );
+
+        let issues = check_integrity(&game_data);
+        assert_eq!(
+            issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>(),
+            vec![
+                "fantasy: condition 'charmed' belongs to a world that is not defined",
+                "devgalaxy: condition 'prone' is defined more than once",
+                "devgalaxy: condition 'stunned' effects[1] refers to skill 'athletics', which does not exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_devgalaxy_is_consistent() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/worlds/devgalaxy");
+        let game_data = load_world_directory(path).unwrap();
+
+        let issues = IntegrityCheck::new(&game_data)
+            .with_item_categories([1, 2, 3])
+            .with_external(ContentKind::Skill)
+            .run();
+
+        assert_eq!(issues, vec![]);
+    }
+}
@@ -0,0 +1,11 @@
+pub mod content_table;
+pub mod integrity;
+pub mod registry;
+pub mod world_loader;
+
+pub use content_table::{Content, ContentTable};
+pub use integrity::{check_integrity, ContentKind, IntegrityCheck, IntegrityIssue};
+pub use registry::GameData;
+pub use world_loader::{
+    load_world_directory, load_world_into, read_world_documents, WorldDocument, WorldLoadError,
+};
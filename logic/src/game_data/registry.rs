@@ -0,0 +1,218 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::ContentTable;
+use crate::classes::CharacterClass;
+use crate::conditions::{load_default_conditions, ConditionDefinition};
+use crate::damage::DamageType;
+use crate::enemies::Enemy;
+use crate::feats::Feat;
+use crate::items::Item;
+use crate::races::Race;
+use crate::skills::Skill;
+use crate::themes::Theme;
+
+/// ## GameData
+/// The content of every loaded world, by kind.  It is the in-memory source of truth the game
+/// loop and the tools read content from.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::game_data::GameData;
+/// use crate::logic::skills::Skill;
+///
+/// let mut game_data = GameData::new();
+/// game_data
+///     .skills_mut()
+///     .insert("devgalaxy", Skill::new("piloting", "Piloting", Ability::Dexterity));
+///
+/// assert!(game_data.skill("devgalaxy", "piloting").is_some());
+/// assert_eq!(game_data.worlds(), vec!["devgalaxy"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GameData {
+    /// The worlds whose world definition was loaded.
+    defined_worlds: BTreeSet<String>,
+    items: ContentTable<Item>,
+    enemies: ContentTable<Enemy>,
+    classes: ContentTable<CharacterClass>,
+    races: ContentTable<Race>,
+    themes: ContentTable<Theme>,
+    skills: ContentTable<Skill>,
+    feats: ContentTable<Feat>,
+    damage_types: ContentTable<DamageType>,
+    conditions: ContentTable<ConditionDefinition>,
+}
+
+macro_rules! content_accessors {
+    ($($table:ident, $table_mut:ident, $single:ident: $content:ty;)*) => {
+        $(
+            pub fn $table(&self) -> &ContentTable<$content> {
+                &self.$table
+            }
+
+            pub fn $table_mut(&mut self) -> &mut ContentTable<$content> {
+                &mut self.$table
+            }
+
+            pub fn $single(&self, world: &str, code: &str) -> Option<&$content> {
+                self.$table.get(world, code)
+            }
+        )*
+    };
+}
+
+impl GameData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    content_accessors! {
+        items, items_mut, item: Item;
+        enemies, enemies_mut, enemy: Enemy;
+        classes, classes_mut, class: CharacterClass;
+        races, races_mut, race: Race;
+        themes, themes_mut, theme: Theme;
+        skills, skills_mut, skill: Skill;
+        feats, feats_mut, feat: Feat;
+        damage_types, damage_types_mut, damage_type: DamageType;
+        conditions, conditions_mut, condition: ConditionDefinition;
+    }
+
+    /// ## world_conditions
+    /// The conditions a world's characters can be given: the world's own conditions, and the
+    /// default conditions it does not replace.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::conditions::{ConditionDefinition, ConditionDuration};
+    /// use crate::logic::game_data::GameData;
+    ///
+    /// let mut game_data = GameData::new();
+    /// game_data.conditions_mut().insert(
+    ///     "devgalaxy",
+    ///     ConditionDefinition::new("stunned", "Dazed", ConditionDuration::Rounds(2)),
+    /// );
+    ///
+    /// let conditions = game_data.world_conditions("devgalaxy");
+    /// assert_eq!(conditions["stunned"].name, "Dazed");
+    /// assert!(conditions.contains_key("prone"));
+    /// ```
+    pub fn world_conditions(&self, world: &str) -> HashMap<String, ConditionDefinition> {
+        let mut conditions = load_default_conditions();
+        for condition in self.conditions.in_world(world) {
+            conditions.insert(condition.code.clone(), condition.clone());
+        }
+        conditions
+    }
+
+    /// Record that a world's definition was loaded, so its content is not orphaned.
+    pub fn define_world(&mut self, world: &str) {
+        self.defined_worlds.insert(world.to_string());
+    }
+
+    pub fn is_world_defined(&self, world: &str) -> bool {
+        self.defined_worlds.contains(world)
+    }
+
+    /// Add all of `other`'s content, replacing content with the same world and code.
+    pub fn merge(&mut self, other: GameData) {
+        self.defined_worlds.extend(other.defined_worlds);
+        self.items.merge(other.items);
+        self.enemies.merge(other.enemies);
+        self.classes.merge(other.classes);
+        self.races.merge(other.races);
+        self.themes.merge(other.themes);
+        self.skills.merge(other.skills);
+        self.feats.merge(other.feats);
+        self.damage_types.merge(other.damage_types);
+        self.conditions.merge(other.conditions);
+    }
+
+    /// The worlds that define any content, in order.
+    pub fn worlds(&self) -> Vec<&str> {
+        let worlds: BTreeSet<&str> = self
+            .items
+            .worlds()
+            .chain(self.enemies.worlds())
+            .chain(self.classes.worlds())
+            .chain(self.races.worlds())
+            .chain(self.themes.worlds())
+            .chain(self.skills.worlds())
+            .chain(self.feats.worlds())
+            .chain(self.damage_types.worlds())
+            .chain(self.conditions.worlds())
+            .collect();
+        worlds.into_iter().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.worlds().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Ability;
+
+    #[test]
+    fn test_content_is_kept_per_world() {
+        let mut game_data = GameData::new();
+        game_data.items_mut().insert(
+            "devgalaxy",
+            Item::new("blaster-rifle", "weapon", "Blaster Rifle"),
+        );
+        game_data
+            .items_mut()
+            .insert("fantasy", Item::new("longsword", "weapon", "Longsword"));
+        let replaced = game_data.items_mut().insert(
+            "devgalaxy",
+            Item::new("blaster-rifle", "weapon", "Heavy Blaster Rifle"),
+        );
+
+        assert_eq!(replaced.unwrap().name, "Blaster Rifle");
+        assert_eq!(game_data.items().len(), 2);
+        assert_eq!(
+            game_data.item("devgalaxy", "blaster-rifle").unwrap().name,
+            "Heavy Blaster Rifle"
+        );
+        assert!(game_data.item("fantasy", "blaster-rifle").is_none());
+        assert_eq!(game_data.worlds(), vec!["devgalaxy", "fantasy"]);
+    }
+
+    #[test]
+    fn test_iteration_and_filtering() {
+        let mut game_data = GameData::new();
+        let skills = game_data.skills_mut();
+        skills.insert(
+            "devgalaxy",
+            Skill::new("piloting", "Piloting", Ability::Dexterity),
+        );
+        skills.insert(
+            "devgalaxy",
+            Skill::new("computers", "Computers", Ability::Intelligence).trained_only(),
+        );
+        skills.insert(
+            "fantasy",
+            Skill::new("arcana", "Arcana", Ability::Intelligence).trained_only(),
+        );
+
+        let codes: Vec<&str> = game_data
+            .skills()
+            .in_world("devgalaxy")
+            .map(|skill| skill.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["computers", "piloting"]);
+        assert_eq!(
+            game_data
+                .skills()
+                .filter("devgalaxy", |skill| skill.trained_only)
+                .count(),
+            1
+        );
+        assert_eq!(game_data.skills().iter().count(), 3);
+        assert!(game_data.skills_mut().remove("fantasy", "arcana").is_some());
+        assert_eq!(game_data.worlds(), vec!["devgalaxy"]);
+    }
+}
@@ -0,0 +1,598 @@
+use std::path::{Path, PathBuf};
+use std::{error, fmt, fs};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::GameData;
+use crate::abilities::Ability;
+use crate::classes::{CharacterClass, ClassFeature};
+use crate::skills::Skill;
+use crate::utilities::validate_character_expression;
+
+/// ## WorldLoadError
+/// Why a world file could not be loaded: the file, the index of the document when the file
+/// holds an array, and the JSON path of the value that is wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldLoadError {
+    pub file: PathBuf,
+    pub index: Option<usize>,
+    /// The path within the document, e.g. `spec.features[1].level`, or empty when the file
+    /// itself could not be read.
+    pub path: String,
+    pub message: String,
+}
+
+impl WorldLoadError {
+    fn new(file: &Path, index: Option<usize>, path: &str, message: impl fmt::Display) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            index,
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for WorldLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(index) = self.index {
+            write!(f, "[{}]", index)?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl error::Error for WorldLoadError {}
+
+#[derive(Deserialize)]
+struct WorldSpec {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct CharacterClassSpec {
+    code: String,
+    name: String,
+    hit_points: i32,
+    stamina_expression: String,
+    skillpoint_expression: String,
+    #[serde(default)]
+    features: Option<Vec<ClassFeatureSpec>>,
+}
+
+#[derive(Deserialize)]
+struct ClassFeatureSpec {
+    level: i32,
+    code: String,
+    name: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct SkillSpec {
+    code: String,
+    name: String,
+    ability: String,
+    #[serde(default)]
+    trained_only: bool,
+    #[serde(default)]
+    armor_penalty: bool,
+}
+
+/// ## WorldDocument
+/// A document read from a world file, e.g.
+/// `{ "kind": "race", "apiVersion": "v1", "spec": { ... } }`, before its spec is converted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldDocument {
+    pub file: PathBuf,
+    /// The index of the document when the file holds an array.
+    pub index: Option<usize>,
+    pub value: Value,
+}
+
+impl WorldDocument {
+    fn error(&self, path: &str, message: impl fmt::Display) -> WorldLoadError {
+        WorldLoadError::new(&self.file, self.index, path, message)
+    }
+
+    pub fn kind(&self) -> Result<&str, WorldLoadError> {
+        match self.value.get("kind") {
+            Some(Value::String(kind)) => Ok(kind),
+            Some(_) => Err(self.error("kind", "expected a string")),
+            None => Err(self.error("kind", "missing field `kind`")),
+        }
+    }
+
+    /// The document's spec as it was written, e.g. to send to the server.
+    pub fn spec_value(&self) -> Option<&Value> {
+        self.value.get("spec")
+    }
+
+    /// The code of the content the document defines, if its spec has one.
+    pub fn code(&self) -> Option<&str> {
+        self.spec_value()?.get("code")?.as_str()
+    }
+
+    /// Deserialize the document's spec, reporting the path of a value that does not fit.
+    pub fn spec<T: DeserializeOwned>(&self) -> Result<T, WorldLoadError> {
+        let spec = match self.value.get("spec") {
+            Some(spec) => spec,
+            None => return Err(self.error("spec", "missing field `spec`")),
+        };
+        serde_path_to_error::deserialize(spec).map_err(|e| {
+            let path = e.path().to_string();
+            let path = if path == "." {
+                "spec".to_string()
+            } else {
+                format!("spec.{}", path)
+            };
+            self.error(&path, e.inner())
+        })
+    }
+}
+
+/// ## load_world_directory
+/// Build a world in memory from a directory such as `data/worlds/devgalaxy`, with no server or
+/// database.
+///
+/// ### Arguments
+/// * `path` - The world's directory.  Every `.json` file below it is read.  A file holds one
+///   document like `{ "kind": "race", "apiVersion": "v1", "spec": { ... } }`, or an array of
+///   them.
+///
+/// ### Returns
+/// The world's content, keyed by the code of its `world` document, or by the directory name
+/// when it has none.  Otherwise every document that could not be loaded.
+///
+/// ### Example
+/// ```no_run
+/// use crate::logic::game_data::load_world_directory;
+///
+/// let game_data = load_world_directory("data/worlds/devgalaxy").unwrap();
+/// assert!(game_data.class("devgalaxy", "mechanic").is_some());
+/// ```
+pub fn load_world_directory(path: impl AsRef<Path>) -> Result<GameData, Vec<WorldLoadError>> {
+    let mut game_data = GameData::new();
+    load_world_into(&mut game_data, path)?;
+    Ok(game_data)
+}
+
+/// ## load_world_into
+/// Load a world directory into existing game data, e.g. to hold several worlds at once.
+///
+/// ### Returns
+/// The code of the world that was loaded, or every document that could not be loaded.  Content
+/// is only added when the whole world loads.
+pub fn load_world_into(
+    game_data: &mut GameData,
+    path: impl AsRef<Path>,
+) -> Result<String, Vec<WorldLoadError>> {
+    let path = path.as_ref();
+    let (documents, mut errors) = read_directory(path);
+
+    let mut world = None;
+    for document in &documents {
+        if document.kind() == Ok("world") {
+            match document.spec::<WorldSpec>() {
+                Ok(spec) => world = Some(spec.code),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+    let world = world.unwrap_or_else(|| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+
+    let mut loaded = GameData::new();
+    if documents
+        .iter()
+        .any(|document| document.kind() == Ok("world"))
+    {
+        loaded.define_world(&world);
+    }
+    for document in &documents {
+        if let Err(e) = load_document(&mut loaded, &world, document) {
+            errors.push(e);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    game_data.merge(loaded);
+    Ok(world)
+}
+
+/// ## read_world_documents
+/// Read every document in a world directory without converting their specs, e.g. to upload
+/// them as they were written once [`load_world_directory`] has checked them.
+///
+/// ### Returns
+/// The documents, ordered by file and index, or every file that could not be read.
+pub fn read_world_documents(
+    path: impl AsRef<Path>,
+) -> Result<Vec<WorldDocument>, Vec<WorldLoadError>> {
+    match read_directory(path.as_ref()) {
+        (documents, errors) if errors.is_empty() => Ok(documents),
+        (_, errors) => Err(errors),
+    }
+}
+
+fn read_directory(path: &Path) -> (Vec<WorldDocument>, Vec<WorldLoadError>) {
+    let mut files = vec![];
+    if let Err(e) = collect_json_files(path, &mut files) {
+        return (vec![], vec![WorldLoadError::new(path, None, "", e)]);
+    }
+    files.sort();
+
+    let mut documents = vec![];
+    let mut errors = vec![];
+    for file in files {
+        match read_documents(&file) {
+            Ok(mut read) => documents.append(&mut read),
+            Err(e) => errors.push(e),
+        }
+    }
+    (documents, errors)
+}
+
+fn collect_json_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        if entry_path.is_dir() {
+            collect_json_files(&entry_path, files)?;
+        } else if entry_path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            files.push(entry_path);
+        }
+    }
+    Ok(())
+}
+
+fn read_documents(file: &Path) -> Result<Vec<WorldDocument>, WorldLoadError> {
+    let contents = fs::read_to_string(file).map_err(|e| WorldLoadError::new(file, None, "", e))?;
+    let value: Value =
+        serde_json::from_str(&contents).map_err(|e| WorldLoadError::new(file, None, "", e))?;
+
+    Ok(match value {
+        Value::Array(values) => values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| WorldDocument {
+                file: file.to_path_buf(),
+                index: Some(index),
+                value,
+            })
+            .collect(),
+        value => vec![WorldDocument {
+            file: file.to_path_buf(),
+            index: None,
+            value,
+        }],
+    })
+}
+
+fn load_document(
+    game_data: &mut GameData,
+    world: &str,
+    document: &WorldDocument,
+) -> Result<(), WorldLoadError> {
+    match document.kind()? {
+        "world" => {}
+        "item" => {
+            game_data.items_mut().insert(world, document.spec()?);
+        }
+        "enemy" => {
+            game_data.enemies_mut().insert(world, document.spec()?);
+        }
+        "race" => {
+            game_data.races_mut().insert(world, document.spec()?);
+        }
+        "theme" => {
+            game_data.themes_mut().insert(world, document.spec()?);
+        }
+        "feat" => {
+            game_data.feats_mut().insert(world, document.spec()?);
+        }
+        "damagetype" => {
+            game_data.damage_types_mut().insert(world, document.spec()?);
+        }
+        "condition" => {
+            game_data.conditions_mut().insert(world, document.spec()?);
+        }
+        "skill" => {
+            let spec: SkillSpec = document.spec()?;
+            let ability = Ability::from_name(&spec.ability).ok_or_else(|| {
+                document.error(
+                    "spec.ability",
+                    format!("unknown ability '{}'", spec.ability),
+                )
+            })?;
+            let mut skill = Skill::new(&spec.code, &spec.name, ability);
+            skill.trained_only = spec.trained_only;
+            skill.armor_penalty = spec.armor_penalty;
+            game_data.skills_mut().insert(world, skill);
+        }
+        "characterclass" => {
+            let spec: CharacterClassSpec = document.spec()?;
+            for (field, expression) in [
+                ("stamina_expression", &spec.stamina_expression),
+                ("skillpoint_expression", &spec.skillpoint_expression),
+            ] {
+                validate_character_expression(expression).map_err(|errors| {
+                    document.error(&format!("spec.{}", field), errors.join(", "))
+                })?;
+            }
+            let mut class = CharacterClass::new(
+                &spec.code,
+                &spec.name,
+                spec.hit_points,
+                &spec.stamina_expression,
+                &spec.skillpoint_expression,
+            )
+            .map_err(|e| document.error("spec", e))?;
+            for feature in spec.features.unwrap_or_default() {
+                class = class.with_feature(ClassFeature {
+                    level: feature.level,
+                    code: feature.code,
+                    name: feature.name,
+                    description: feature.description,
+                });
+            }
+            game_data.classes_mut().insert(world, class);
+        }
+        kind => return Err(document.error("kind", format!("unknown kind '{}'", kind))),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("logic-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        for (file, contents) in files {
+            let file = path.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, contents).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_load_devgalaxy() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/worlds/devgalaxy");
+        let game_data = load_world_directory(path).unwrap();
+
+        assert_eq!(game_data.worlds(), vec!["devgalaxy"]);
+        assert!(game_data.is_world_defined("devgalaxy"));
+        assert!(game_data.class("devgalaxy", "mechanic").is_some());
+        assert!(game_data.race("devgalaxy", "vesk").is_some());
+        assert!(game_data.theme("devgalaxy", "ace-pilot").is_some());
+        assert_eq!(
+            game_data
+                .item("devgalaxy", "blaster-rifle")
+                .and_then(|item| item.weapon())
+                .unwrap()
+                .damage,
+            "1d12"
+        );
+        assert!(game_data.enemy("devgalaxy", "pirate").is_some());
+        assert!(game_data.damage_type("devgalaxy", "energy").is_some());
+        let hacking = game_data.skill("devgalaxy", "hacking").unwrap();
+        assert_eq!(hacking.ability, Ability::Intelligence);
+        assert!(hacking.trained_only);
+        assert!(
+            game_data
+                .skill("devgalaxy", "piloting")
+                .unwrap()
+                .armor_penalty
+        );
+        assert!(game_data.skill("devgalaxy", "engineering").is_some());
+        assert_eq!(
+            game_data.condition("devgalaxy", "off-target").unwrap().name,
+            "Off-Target"
+        );
+    }
+
+    #[test]
+    fn test_mechanic_character_class() {
+        let document = WorldDocument {
+            file: PathBuf::from("classes.json"),
+            index: None,
+            value: serde_json::json!({
+                "kind": "characterclass",
+                "apiVersion": "v1",
+                "spec": {
+                    "id": 1,
+                    "world_id": 1,
+                    "code": "mechanic",
+                    "name": "Mechanic",
+                    "description": "A master of machines and technology.",
+                    "hit_points": 6,
+                    "stamina_expression": "10 + CON",
+                    "skillpoint_expression": "4 + INT",
+                    "proficiencies": ["Light Armor", "Basic Melee Weapons", "Small Arms"],
+                    "features": [
+                        {
+                            "level": 1,
+                            "code": "ai",
+                            "name": "Artificial Intelligence",
+                            "description": "You have an AI that assists you."
+                        },
+                        {
+                            "level": 1,
+                            "code": "rig",
+                            "name": "Custom Rig",
+                            "description": "You have a custom rig for your tools."
+                        }
+                    ]
+                }
+            }),
+        };
+
+        let mut game_data = GameData::new();
+        load_document(&mut game_data, "devgalaxy", &document).unwrap();
+
+        let mechanic = game_data.class("devgalaxy", "mechanic").unwrap();
+        assert_eq!(mechanic.name, "Mechanic");
+        assert_eq!(mechanic.hit_points, 6);
+        assert_eq!(
+            mechanic
+                .features_at(1)
+                .map(|feature| feature.code.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["ai", "rig"]
+        );
+    }
+
+    #[test]
+    fn test_mechanic_character_class_json() {
+        let mechanic_json = r#"
+        {
+            "kind": "characterclass",
+            "apiVersion": "v1",
+            "spec": {
+                "id": 1,
+                "world_id": 1,
+                "code": "mechanic",
+                "name": "Mechanic",
+                "description": "A master of machines and technology.",
+                "hit_points": 6,
+                "stamina_expression": "10 + CON",
+                "skillpoint_expression": "4 + INT",
+                "proficiencies": ["Light Armor", "Basic Melee Weapons", "Small Arms"],
+                "features": [
+                    {
+                        "level": 1,
+                        "code": "ai",
+                        "name": "Artificial Intelligence",
+                        "description": "You have an AI that assists you."
+                    },
+                    {
+                        "level": 1,
+                        "code": "rig",
+                        "name": "Custom Rig",
+                        "description": "You have a custom rig for your tools."
+                    }
+                ]
+            }
+        }
+        "#;
+        let path = world_directory(
+            "mechanic",
+            &[
+                (
+                    "world.json",
+                    r#"{ "kind": "world", "apiVersion": "v1", "spec": { "code": "devgalaxy" } }"#,
+                ),
+                ("classes/mechanic.json", mechanic_json),
+            ],
+        );
+
+        let game_data = load_world_directory(&path);
+        fs::remove_dir_all(&path).unwrap();
+
+        let game_data = game_data.unwrap();
+        let mechanic = game_data.class("devgalaxy", "mechanic").unwrap();
+        assert_eq!(mechanic.stamina_expression.source(), "10 + CON");
+        assert_eq!(mechanic.features_at(1).count(), 2);
+    }
+
+    #[test]
+    fn test_errors_report_the_file_index_and_path() {
+        let path = world_directory(
+            "broken",
+            &[
+                (
+                    "world.json",
+                    r#"{ "kind": "world", "apiVersion": "v1", "spec": { "code": "broken" } }"#,
+                ),
+                (
+                    "classes/classes.json",
+                    r#"[
+                        { "kind": "feat", "apiVersion": "v1", "spec": { "code": "toughness", "name": "Toughness" } },
+                        { "kind": "theme", "apiVersion": "v1", "spec": {
+                            "code": "ace-pilot",
+                            "name": "Ace Pilot",
+                            "features": [{ "level": "one", "code": "a", "name": "A", "description": "A" }]
+                        } },
+                        { "kind": "spaceship", "apiVersion": "v1", "spec": {} }
+                    ]"#,
+                ),
+                ("notes.json", "{ not json"),
+            ],
+        );
+
+        let errors = load_world_directory(&path).unwrap_err();
+        fs::remove_dir_all(&path).unwrap();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].file.ends_with("notes.json"));
+        assert_eq!(errors[0].index, None);
+        assert_eq!(errors[1].index, Some(1));
+        assert_eq!(errors[1].path, "spec.features[0].level");
+        assert!(errors[1].to_string().starts_with(&format!(
+            "{}[1] at spec.features[0].level: ",
+            errors[1].file.display()
+        )));
+        assert_eq!(errors[2].index, Some(2));
+        assert_eq!(errors[2].message, "unknown kind 'spaceship'");
+    }
+
+    #[test]
+    fn test_specs_are_checked_against_the_rules() {
+        let path = world_directory(
+            "rules",
+            &[(
+                "content.json",
+                r#"[
+                    { "kind": "characterclass", "apiVersion": "v1", "spec": {
+                        "code": "mechanic",
+                        "name": "Mechanic",
+                        "hit_points": 6,
+                        "stamina_expression": "10 + CON",
+                        "skillpoint_expression": "4 + ITN"
+                    } },
+                    { "kind": "race", "apiVersion": "v1", "spec": {
+                        "code": "vesk",
+                        "name": "Vesk",
+                        "ability_modifiers": { "strength": 2, "luck": 1 }
+                    } },
+                    { "kind": "theme", "apiVersion": "v1", "spec": {
+                        "code": "ace-pilot",
+                        "name": "Ace Pilot",
+                        "ability_bonuses": { "luck": 1 }
+                    } }
+                ]"#,
+            )],
+        );
+
+        let errors = load_world_directory(&path).unwrap_err();
+        let documents = read_world_documents(&path).unwrap();
+        fs::remove_dir_all(&path).unwrap();
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].path, "spec.skillpoint_expression");
+        assert!(errors[0].message.contains("unknown variable 'ITN'"));
+        assert!(errors[1].path.starts_with("spec.ability_modifiers"));
+        assert!(errors[1].message.contains("unknown variant `luck`"));
+        assert!(errors[2].path.starts_with("spec.ability_bonuses"));
+        assert!(errors[2].message.contains("unknown variant `luck`"));
+
+        // the documents can still be read as they were written
+        assert_eq!(documents.len(), 3);
+        assert_eq!(documents[1].kind(), Ok("race"));
+        assert_eq!(documents[1].code(), Some("vesk"));
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::damage::DamageModifier;
+
+/// ## Weapon
+/// The combat properties of a weapon item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Weapon {
+    /// The damage dice, e.g. `1d12`.
+    pub damage: String,
+    /// The damage type, e.g. `energy`.
+    pub damage_type: String,
+    /// The range in feet, or 0 for a melee weapon.
+    #[serde(default)]
+    pub range: i32,
+    #[serde(default)]
+    pub properties: Vec<String>,
+}
+
+/// ## Item
+/// Something a world defines that can be carried, bought and sold, such as a weapon or armor.
+/// What an item does depends on its type and is described by its properties.
+///
+/// ### Example
+/// ```
+/// use crate::logic::items::Item;
+///
+/// let rifle: Item = serde_json::from_str(r#"{
+///     "code": "blaster-rifle",
+///     "item_type": "weapon",
+///     "name": "Blaster Rifle",
+///     "description": "A longarm energy weapon",
+///     "item_properties": { "damage": "1d12", "damage_type": "energy", "range": 200 },
+///     "base_price": 375
+/// }"#).unwrap();
+///
+/// assert_eq!(rifle.weapon().unwrap().damage, "1d12");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub code: String,
+    pub item_type: String,
+    /// The item category the server files the item under, by id.
+    #[serde(default)]
+    pub category_id: Option<i64>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub item_properties: serde_json::Value,
+    #[serde(default)]
+    pub base_price: i64,
+}
+
+impl Item {
+    pub fn new(code: &str, item_type: &str, name: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            item_type: item_type.to_string(),
+            category_id: None,
+            name: name.to_string(),
+            description: String::new(),
+            item_properties: serde_json::Value::Null,
+            base_price: 0,
+        }
+    }
+
+    pub fn with_category(mut self, category_id: i64) -> Self {
+        self.category_id = Some(category_id);
+        self
+    }
+
+    pub fn is_weapon(&self) -> bool {
+        self.item_type == "weapon"
+    }
+
+    pub fn is_armor(&self) -> bool {
+        self.item_type == "armor"
+    }
+
+    /// The bonus armor gives to armor class, from its `armor_bonus` property, or 0 for anything
+    /// else.
+    ///
+    /// ### Returns
+    /// The bonus, or the error reading it if it is not a whole number that fits in an `i32`.
+    pub fn armor_bonus(&self) -> Result<i32, serde_json::Error> {
+        match self.item_properties.get("armor_bonus") {
+            Some(armor_bonus) if self.is_armor() => serde_json::from_value(armor_bonus.clone()),
+            _ => Ok(0),
+        }
+    }
+
+    /// The resistances, immunities and vulnerabilities the item gives whoever wears it, from its
+    /// `damage_modifiers` property, e.g.
+    /// `[{ "modifier": "resistance", "damage_type": "energy", "amount": 5 }]`.
+    ///
+    /// ### Returns
+    /// The modifiers, none if the item has no `damage_modifiers`, or the error reading them if
+    /// any entry is malformed.
+    pub fn damage_modifiers(&self) -> Result<Vec<DamageModifier>, serde_json::Error> {
+        match self.item_properties.get("damage_modifiers") {
+            Some(modifiers) => serde_json::from_value(modifiers.clone()),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// The item's weapon properties, if it is a weapon and they can be read.
+    pub fn weapon(&self) -> Option<Weapon> {
+        if !self.is_weapon() {
+            return None;
+        }
+        serde_json::from_value(self.item_properties.clone()).ok()
+    }
+}
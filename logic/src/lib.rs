@@ -1,9 +1,33 @@
 pub mod abilities;
+pub mod bonuses;
+pub mod classes;
+pub mod combat;
+pub mod conditions;
+pub mod damage;
+pub mod enemies;
 pub mod entities;
+pub mod feats;
+pub mod game_data;
+pub mod items;
+pub mod progression;
 pub mod races;
+pub mod skills;
+pub mod themes;
 pub mod utilities;
 
 pub use abilities::*;
+pub use bonuses::*;
+pub use classes::*;
+pub use combat::*;
+pub use conditions::*;
+pub use damage::*;
+pub use enemies::*;
 pub use entities::*;
+pub use feats::*;
+pub use game_data::*;
+pub use items::*;
+pub use progression::*;
 pub use races::*;
+pub use skills::*;
+pub use themes::*;
 pub use utilities::*;
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// The experience needed for each level of the default table, starting with level 1.
+pub const DEFAULT_EXPERIENCE_TABLE: [i64; 20] = [
+    0, 1_300, 3_300, 6_000, 10_000, 15_000, 23_000, 34_000, 50_000, 71_000, 105_000, 145_000,
+    210_000, 295_000, 425_000, 600_000, 850_000, 1_200_000, 1_700_000, 2_400_000,
+];
+
+/// ## ExperienceTable
+/// The total experience a character needs to reach each level.  Each world may have its own;
+/// the highest level is the length of the table.
+///
+/// ### Example
+/// ```
+/// use crate::logic::progression::ExperienceTable;
+///
+/// let table = ExperienceTable::default();
+/// assert_eq!(table.level_for(0), 1);
+/// assert_eq!(table.level_for(3_299), 2);
+/// assert_eq!(table.level_for(3_300), 3);
+/// assert_eq!(table.experience_for(2), Some(1_300));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<i64>", into = "Vec<i64>")]
+pub struct ExperienceTable {
+    thresholds: Vec<i64>,
+}
+
+impl Default for ExperienceTable {
+    fn default() -> Self {
+        Self {
+            thresholds: DEFAULT_EXPERIENCE_TABLE.to_vec(),
+        }
+    }
+}
+
+impl ExperienceTable {
+    /// ## new
+    /// Create a table from the experience needed for each level, starting with level 1.
+    ///
+    /// ### Returns
+    /// The table, or every problem with it: it must start at 0 and always increase.
+    pub fn new(thresholds: Vec<i64>) -> Result<Self, Vec<String>> {
+        let mut errors = vec![];
+        match thresholds.first() {
+            None => errors.push("experience table is empty".to_string()),
+            Some(0) => {}
+            Some(first) => errors.push(format!("level 1 must need 0 experience, not {}", first)),
+        }
+        for (index, pair) in thresholds.windows(2).enumerate() {
+            if pair[1] <= pair[0] {
+                errors.push(format!(
+                    "level {} needs {} experience, which is not more than level {} ({})",
+                    index + 2,
+                    pair[1],
+                    index + 1,
+                    pair[0]
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { thresholds })
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn max_level(&self) -> i32 {
+        self.thresholds.len() as i32
+    }
+
+    /// The total experience needed to reach `level`, or `None` past the highest level.
+    pub fn experience_for(&self, level: i32) -> Option<i64> {
+        if level < 1 {
+            return None;
+        }
+        self.thresholds.get(level as usize - 1).copied()
+    }
+
+    /// The highest level that `experience` reaches.
+    pub fn level_for(&self, experience: i64) -> i32 {
+        self.thresholds
+            .iter()
+            .take_while(|threshold| **threshold <= experience)
+            .count()
+            .max(1) as i32
+    }
+
+    pub fn thresholds(&self) -> &[i64] {
+        &self.thresholds
+    }
+}
+
+impl TryFrom<Vec<i64>> for ExperienceTable {
+    type Error = String;
+
+    fn try_from(thresholds: Vec<i64>) -> Result<Self, Self::Error> {
+        ExperienceTable::new(thresholds).map_err(|errors| errors.join(", "))
+    }
+}
+
+impl From<ExperienceTable> for Vec<i64> {
+    fn from(table: ExperienceTable) -> Self {
+        table.thresholds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_and_limits() {
+        let table = ExperienceTable::new(vec![0, 100, 300]).unwrap();
+
+        assert_eq!(table.max_level(), 3);
+        assert_eq!(table.level_for(-5), 1);
+        assert_eq!(table.level_for(99), 1);
+        assert_eq!(table.level_for(100), 2);
+        assert_eq!(table.level_for(1_000_000), 3);
+        assert_eq!(table.experience_for(3), Some(300));
+        assert_eq!(table.experience_for(4), None);
+        assert_eq!(table.experience_for(0), None);
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        assert_eq!(
+            ExperienceTable::new(vec![]),
+            Err(vec!["experience table is empty".to_string()])
+        );
+        assert_eq!(
+            ExperienceTable::new(vec![10, 300, 200]).unwrap_err(),
+            vec![
+                "level 1 must need 0 experience, not 10",
+                "level 3 needs 200 experience, which is not more than level 2 (300)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serializes_as_a_list() {
+        let table: ExperienceTable = serde_json::from_str("[0, 500, 1500]").unwrap();
+        assert_eq!(table.max_level(), 3);
+        assert_eq!(serde_json::to_string(&table).unwrap(), "[0,500,1500]");
+        assert!(serde_json::from_str::<ExperienceTable>("[0, 500, 100]").is_err());
+    }
+}
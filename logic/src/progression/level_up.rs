@@ -0,0 +1,212 @@
+use std::{error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::classes::{CharacterClass, ClassFeature};
+use crate::entities::Character;
+use crate::utilities::{
+    parse_dice_expression, DiceRng, DiceRollError, ExpressionError, RollResult,
+};
+
+/// ## HitPointMethod
+/// How the hit points gained on a new level are found from the class's `hit_points`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HitPointMethod {
+    /// Gain the class's full `hit_points`.
+    #[default]
+    Fixed,
+    /// Gain the average roll of a die with `hit_points` sides, rounded up.
+    Average,
+    /// Roll a die with `hit_points` sides.
+    Roll,
+}
+
+/// ## LevelUp
+/// Exactly what changed when a character gained a level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelUp {
+    pub class: String,
+    pub from_level: i32,
+    pub to_level: i32,
+    /// The hit points gained.
+    pub hit_points: i32,
+    /// The roll behind the hit points, when they were rolled.
+    pub hit_point_roll: Option<RollResult>,
+    /// The stamina gained, from the class `stamina_expression`.
+    pub stamina: i32,
+    /// The skill ranks gained, from the class `skillpoint_expression`, and never less than 1.
+    pub skill_ranks: i32,
+    /// The class features granted at the new level.
+    pub features: Vec<ClassFeature>,
+}
+
+impl LevelUp {
+    /// What the character gained, to be kept with them.
+    pub fn gain(&self) -> LevelGain {
+        LevelGain {
+            level: self.to_level,
+            hit_points: self.hit_points,
+            stamina: self.stamina,
+            skill_ranks: self.skill_ranks,
+        }
+    }
+}
+
+/// ## LevelGain
+/// The hit points, stamina and skill ranks a character gained at one level.  These may have
+/// been rolled, so they are kept and summed by [`super::Vitals::from_gains`] rather than
+/// worked out again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelGain {
+    pub level: i32,
+    pub hit_points: i32,
+    pub stamina: i32,
+    pub skill_ranks: i32,
+}
+
+impl LevelGain {
+    /// ## roll
+    /// Work out what a character gains at `level` of their class.  The first level always
+    /// gives the class's full `hit_points`; later levels follow `method`.
+    ///
+    /// ### Returns
+    /// The gain, and the roll behind the hit points when they were rolled.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::classes::CharacterClass;
+    /// use crate::logic::entities::Character;
+    /// use crate::logic::progression::{HitPointMethod, LevelGain};
+    /// use crate::logic::races::load_default_races;
+    ///
+    /// let races = load_default_races();
+    /// let class = CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT").unwrap();
+    /// let character = Character::new(
+    ///     "Kira".to_string(), 1, "mechanic".to_string(), races["Human"].clone(),
+    ///     10, 10, 13, 15, 10, 10,
+    /// );
+    ///
+    /// let (gain, _) =
+    ///     LevelGain::roll(&character, &class, 3, HitPointMethod::Average, &mut rand::thread_rng())
+    ///         .unwrap();
+    /// assert_eq!((gain.hit_points, gain.stamina, gain.skill_ranks), (4, 12, 7));
+    /// ```
+    pub fn roll<R: DiceRng + ?Sized>(
+        character: &Character,
+        class: &CharacterClass,
+        level: i32,
+        method: HitPointMethod,
+        rng: &mut R,
+    ) -> Result<(LevelGain, Option<RollResult>), LevelUpError> {
+        let (hit_points, hit_point_roll) = match method {
+            _ if level <= 1 => (class.hit_points, None),
+            HitPointMethod::Fixed => (class.hit_points, None),
+            HitPointMethod::Average => (class.hit_points / 2 + 1, None),
+            HitPointMethod::Roll => {
+                let dice = parse_dice_expression(&format!("1d{}", class.hit_points))?;
+                let roll = RollResult::roll(&dice, None, rng)?;
+                (roll.total, Some(roll))
+            }
+        };
+
+        let context = character.expression_context().with_level(level);
+        let stamina = class
+            .stamina_expression
+            .evaluate_with(&context, rng)?
+            .clamp(0, i32::MAX as i64) as i32;
+        let skill_ranks = class
+            .skillpoint_expression
+            .evaluate_with(&context, rng)?
+            .clamp(1, i32::MAX as i64) as i32;
+
+        let gain = LevelGain {
+            level,
+            hit_points: hit_points.max(1),
+            stamina,
+            skill_ranks,
+        };
+        Ok((gain, hit_point_roll))
+    }
+}
+
+impl fmt::Display for LevelUp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} level {} -> {}: +{} hit points, +{} stamina, +{} skill ranks",
+            self.class,
+            self.from_level,
+            self.to_level,
+            self.hit_points,
+            self.stamina,
+            self.skill_ranks
+        )?;
+        if !self.features.is_empty() {
+            let names: Vec<&str> = self
+                .features
+                .iter()
+                .map(|feature| feature.name.as_str())
+                .collect();
+            write!(f, ", gained {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// ## LevelUpError
+/// Why a character could not gain a level.  The character is left unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelUpError {
+    NotEnoughExperience {
+        level: i32,
+        needed: i64,
+        experience: i64,
+    },
+    MaximumLevel(i32),
+    /// The class passed in is not the character's class.
+    WrongClass {
+        expected: String,
+        actual: String,
+    },
+    Expression(ExpressionError),
+    Roll(DiceRollError),
+}
+
+impl fmt::Display for LevelUpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelUpError::NotEnoughExperience {
+                level,
+                needed,
+                experience,
+            } => write!(
+                f,
+                "level {} needs {} experience, but the character has {}",
+                level, needed, experience
+            ),
+            LevelUpError::MaximumLevel(level) => {
+                write!(f, "level {} is the highest level", level)
+            }
+            LevelUpError::WrongClass { expected, actual } => {
+                write!(f, "the character is a {}, not a {}", expected, actual)
+            }
+            LevelUpError::Expression(error) => write!(f, "{}", error),
+            LevelUpError::Roll(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl error::Error for LevelUpError {}
+
+impl From<ExpressionError> for LevelUpError {
+    fn from(error: ExpressionError) -> Self {
+        LevelUpError::Expression(error)
+    }
+}
+
+impl From<DiceRollError> for LevelUpError {
+    fn from(error: DiceRollError) -> Self {
+        LevelUpError::Roll(error)
+    }
+}
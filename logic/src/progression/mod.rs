@@ -0,0 +1,7 @@
+pub mod experience_table;
+pub mod level_up;
+pub mod vitals;
+
+pub use experience_table::*;
+pub use level_up::*;
+pub use vitals::*;
@@ -0,0 +1,270 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::classes::CharacterClass;
+use crate::entities::Character;
+use crate::progression::LevelGain;
+use crate::utilities::ExpressionError;
+
+/// ## DerivedValue
+/// A value computed from class and race data, with each part that went into it.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DerivedValue {
+    pub total: i32,
+    /// Each part of the total, e.g. `("Mechanic level 1: 10 + CON", 12)`.
+    pub breakdown: Vec<(String, i32)>,
+}
+
+impl DerivedValue {
+    pub(crate) fn add(&mut self, source: String, value: i32) {
+        self.total = self.total.saturating_add(value);
+        self.breakdown.push((source, value));
+    }
+}
+
+impl fmt::Display for DerivedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = self
+            .breakdown
+            .iter()
+            .map(|(source, value)| format!("{} ({})", value, source))
+            .collect();
+        write!(f, "{} = {}", parts.join(" + "), self.total)
+    }
+}
+
+/// ## Vitals
+/// A character's maximum hit points and stamina, and the skill points they gain each level,
+/// derived from their class, race and ability scores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Vitals {
+    pub max_hit_points: DerivedValue,
+    pub max_stamina: DerivedValue,
+    pub skill_points_per_level: DerivedValue,
+}
+
+impl Vitals {
+    /// ## calculate
+    /// Derive a character's vitals at their current level.
+    ///
+    /// * Hit points are the race's hit points plus the class `hit_points` for every level.
+    /// * Stamina is the class `stamina_expression` for every level, evaluated at that level and
+    ///   never less than 0.
+    /// * Skill points are the class `skillpoint_expression`, and never less than 1.
+    ///
+    /// This is what a character who took the class's full hit points at every level has.  An
+    /// expression that rolls dice is an error, as it has no single maximum: keep what the
+    /// character gained at each level and use [`Vitals::from_gains`] instead.
+    ///
+    /// ### Arguments
+    /// * `character` - The character, including their race.
+    /// * `class` - The character's class.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::classes::CharacterClass;
+    /// use crate::logic::entities::Character;
+    /// use crate::logic::progression::Vitals;
+    /// use crate::logic::races::load_default_races;
+    ///
+    /// let races = load_default_races();
+    /// let class = CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT").unwrap();
+    /// let character = Character::new(
+    ///     "Kira".to_string(), 2, "mechanic".to_string(), races["Human"].clone(),
+    ///     10, 10, 13, 15, 10, 10,
+    /// );
+    ///
+    /// let vitals = Vitals::calculate(&character, &class).unwrap();
+    /// assert_eq!(vitals.max_hit_points.total, 12);
+    /// assert_eq!(vitals.max_stamina.total, 24);
+    /// assert_eq!(vitals.skill_points_per_level.total, 7);
+    /// ```
+    pub fn calculate(
+        character: &Character,
+        class: &CharacterClass,
+    ) -> Result<Vitals, ExpressionError> {
+        for expression in [&class.stamina_expression, &class.skillpoint_expression] {
+            if expression.rolls_dice() {
+                return Err(ExpressionError::new(&format!(
+                    "'{}' rolls dice, so it has no single maximum",
+                    expression.source()
+                )));
+            }
+        }
+
+        let race = character.race();
+        let levels = 1..=character.level().max(1);
+
+        let mut max_hit_points = DerivedValue::default();
+        if race.hit_points != 0 {
+            max_hit_points.add(race.name.clone(), race.hit_points);
+        }
+        max_hit_points.add(
+            format!("{} x {} levels", class.name, levels.clone().count()),
+            class.hit_points.saturating_mul(*levels.end()),
+        );
+
+        let mut max_stamina = DerivedValue::default();
+        for level in levels {
+            let context = character.expression_context().with_level(level);
+            let stamina = class.stamina_expression.evaluate(&context)?;
+            max_stamina.add(
+                format!(
+                    "{} level {}: {}",
+                    class.name, level, class.stamina_expression
+                ),
+                stamina.clamp(0, i32::MAX as i64) as i32,
+            );
+        }
+
+        let mut skill_points_per_level = DerivedValue::default();
+        let skill_points = character.evaluate(&class.skillpoint_expression)?;
+        skill_points_per_level.add(
+            format!("{}: {}", class.name, class.skillpoint_expression),
+            skill_points.clamp(1, i32::MAX as i64) as i32,
+        );
+
+        Ok(Vitals {
+            max_hit_points,
+            max_stamina,
+            skill_points_per_level,
+        })
+    }
+
+    /// ## from_gains
+    /// A character's vitals from what they gained at each level, see
+    /// [`LevelGain::roll`].  Hit points are the race's hit points plus every gain, stamina is
+    /// every gain, and skill points are those of the latest level.
+    ///
+    /// ### Arguments
+    /// * `character` - The character, including their race.
+    /// * `class` - The character's class.
+    /// * `gains` - What the character gained at each level.
+    pub fn from_gains(
+        character: &Character,
+        class: &CharacterClass,
+        gains: &[LevelGain],
+    ) -> Vitals {
+        let race = character.race();
+
+        let mut max_hit_points = DerivedValue::default();
+        if race.hit_points != 0 {
+            max_hit_points.add(race.name.clone(), race.hit_points);
+        }
+        let mut max_stamina = DerivedValue::default();
+        for gain in gains {
+            let source = format!("{} level {}", class.name, gain.level);
+            max_hit_points.add(source.clone(), gain.hit_points);
+            max_stamina.add(source, gain.stamina);
+        }
+
+        let mut skill_points_per_level = DerivedValue::default();
+        if let Some(gain) = gains.iter().max_by_key(|gain| gain.level) {
+            skill_points_per_level.add(
+                format!("{} level {}", class.name, gain.level),
+                gain.skill_ranks,
+            );
+        }
+
+        Vitals {
+            max_hit_points,
+            max_stamina,
+            skill_points_per_level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progression::HitPointMethod;
+    use crate::races::{load_default_races as races, Race};
+    use crate::utilities::ScriptedRng;
+
+    fn character(level: i32, race: Race, constitution: i32, intelligence: i32) -> Character {
+        Character::new(
+            "Test".to_string(),
+            level,
+            "soldier".to_string(),
+            race,
+            10,
+            10,
+            constitution,
+            intelligence,
+            10,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_vitals_scale_with_level() {
+        let class = CharacterClass::new("soldier", "Soldier", 7, "7 + CON", "4 + INT").unwrap();
+        let dwarf = races()["Dwarf"].clone().with_hit_points(6);
+        let dwarf = character(3, dwarf, 12, 10);
+
+        let vitals = Vitals::calculate(&dwarf, &class).unwrap();
+
+        assert_eq!(vitals.max_hit_points.total, 6 + 7 * 3);
+        assert_eq!(
+            vitals.max_hit_points.to_string(),
+            "6 (Dwarf) + 21 (Soldier x 3 levels) = 27"
+        );
+        // constitution 12 + 2 racial is a +2 modifier
+        assert_eq!(vitals.max_stamina.total, 9 * 3);
+        assert_eq!(vitals.max_stamina.breakdown.len(), 3);
+        assert_eq!(
+            vitals.max_stamina.breakdown[0].0,
+            "Soldier level 1: 7 + CON"
+        );
+        assert_eq!(vitals.skill_points_per_level.total, 4);
+    }
+
+    #[test]
+    fn test_vitals_have_floors() {
+        let class = CharacterClass::new("weakling", "Weakling", 1, "CON", "INT").unwrap();
+        let vitals =
+            Vitals::calculate(&character(1, races()["Elf"].clone(), 3, 3), &class).unwrap();
+
+        assert_eq!(vitals.max_stamina.total, 0);
+        assert_eq!(vitals.skill_points_per_level.total, 1);
+    }
+
+    #[test]
+    fn test_vitals_from_rolled_gains() {
+        let class = CharacterClass::new("scout", "Scout", 8, "roll(1d8) + CON", "4 + INT").unwrap();
+        let character = character(3, races()["Human"].clone(), 12, 10);
+
+        let error = Vitals::calculate(&character, &class).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "'roll(1d8) + CON' rolls dice, so it has no single maximum"
+        );
+
+        let mut rng = ScriptedRng::new(vec![5, 7, 2, 6, 3]);
+        let gains: Vec<LevelGain> = (1..=3)
+            .map(|level| {
+                LevelGain::roll(&character, &class, level, HitPointMethod::Roll, &mut rng)
+                    .unwrap()
+                    .0
+            })
+            .collect();
+        let vitals = Vitals::from_gains(&character, &class, &gains);
+
+        // the first level takes the full 8 hit points, then 7 and 6 are rolled
+        assert_eq!(vitals.max_hit_points.total, 8 + 7 + 6);
+        // constitution 12 is a +1 modifier
+        assert_eq!(vitals.max_stamina.total, (5 + 1) + (2 + 1) + (3 + 1));
+        assert_eq!(vitals, Vitals::from_gains(&character, &class, &gains));
+        assert_eq!(vitals.skill_points_per_level.total, 4);
+    }
+
+    #[test]
+    fn test_expression_errors_are_returned() {
+        let class = CharacterClass::new("broken", "Broken", 6, "10 + LUCK", "4").unwrap();
+        let error =
+            Vitals::calculate(&character(1, races()["Human"].clone(), 10, 10), &class).unwrap_err();
+
+        assert_eq!(error.message(), "unknown variable 'LUCK'");
+    }
+}
@@ -1,5 +1,9 @@
 pub mod race;
 pub mod race_loader;
+pub mod sense;
+pub mod special_ability;
 
 pub use race::Race;
-pub use race_loader::load_default_races;
+pub use race_loader::{load_default_races, load_races};
+pub use sense::{visibility, LightLevel, Sense, Visibility};
+pub use special_ability::{SpecialAbility, SpecialAbilityEffect};
@@ -1,29 +1,109 @@
+use super::{Sense, SpecialAbility};
 use crate::abilities::Ability;
+use crate::bonuses::{Bonus, BonusTarget, BonusType};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+fn default_speed() -> i32 {
+    30
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Race {
     // Eventually, we will use a UUID to identify
     // Races, since this will come from a database.
     //    pub id: uuid::Uuid,
+    /// The code worlds refer to the race by, e.g. `dwarf`.
+    pub code: String,
     pub name: String,
+    #[serde(default)]
     pub ability_modifiers: HashMap<Ability, i32>,
-    pub special_abilities: Vec<String>,
+    #[serde(default)]
+    pub special_abilities: Vec<SpecialAbility>,
+    #[serde(default = "default_speed")]
     pub speed: i32,
+    /// The hit points a character of this race starts with, on top of their class.
+    #[serde(default)]
+    pub hit_points: i32,
 }
 
 impl Race {
     pub fn new(
         name: &str,
         ability_modifiers: HashMap<Ability, i32>,
-        special_abilities: Vec<String>,
+        special_abilities: Vec<SpecialAbility>,
         speed: i32,
     ) -> Self {
         Self {
+            code: name.to_lowercase(),
             name: name.to_string(),
             ability_modifiers,
             special_abilities,
             speed,
+            hit_points: 0,
         }
     }
+
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = code.to_string();
+        self
+    }
+
+    pub fn with_hit_points(mut self, hit_points: i32) -> Self {
+        self.hit_points = hit_points;
+        self
+    }
+
+    /// The senses the race's special abilities give, keeping the longest range of each.
+    pub fn senses(&self) -> Vec<(Sense, i32)> {
+        let mut senses: Vec<(Sense, i32)> = vec![];
+        for (sense, range) in self.special_abilities.iter().flat_map(|a| a.senses()) {
+            match senses.iter_mut().find(|(other, _)| *other == sense) {
+                Some((_, longest)) => *longest = (*longest).max(range),
+                None => senses.push((sense, range)),
+            }
+        }
+        senses
+    }
+
+    pub fn proficiencies(&self) -> Vec<&str> {
+        self.special_abilities
+            .iter()
+            .flat_map(|special_ability| special_ability.proficiencies())
+            .collect()
+    }
+
+    /// ## bonuses
+    /// The racial bonuses that always apply: the ability modifiers, with the race's name as
+    /// their source, and the skill bonuses from special abilities.
+    pub fn bonuses(&self) -> Vec<Bonus> {
+        let mut bonuses: Vec<Bonus> = Ability::all()
+            .into_iter()
+            .filter_map(|ability| {
+                self.ability_modifiers.get(&ability).map(|modifier| {
+                    Bonus::new(
+                        &self.name,
+                        BonusType::Racial,
+                        BonusTarget::Ability(ability),
+                        *modifier,
+                    )
+                })
+            })
+            .collect();
+        bonuses.extend(
+            self.special_abilities
+                .iter()
+                .flat_map(|special_ability| special_ability.skill_bonuses()),
+        );
+        bonuses
+    }
+
+    /// The bonuses from special abilities to a saving throw against a damage type or
+    /// condition, e.g. `poisoned`.
+    pub fn save_bonuses(&self, save: &str, against: &str) -> Vec<Bonus> {
+        self.special_abilities
+            .iter()
+            .flat_map(|special_ability| special_ability.save_bonuses(save, against))
+            .collect()
+    }
 }
@@ -1,76 +1,103 @@
-use crate::abilities::ability::Ability;
+use std::collections::HashMap;
+
+use serde::de::Error;
+use serde_json::Value;
+
 use crate::races::race::Race;
 
-pub fn load_default_races() -> std::collections::HashMap<String, Race> {
-    [
-        (
-            "Dwarf".to_string(),
-            Race::new(
-                "Dwarf",
-                [(Ability::Constitution, 2), (Ability::Charisma, -2)]
-                    .iter()
-                    .cloned()
-                    .collect(),
-                vec![
-                    "Darkvision".to_string(),
-                    "Dwarven Resilience".to_string(),
-                    "Dwarven Combat Training".to_string(),
-                    "Stonecunning".to_string(),
-                ],
-                25,
-            ),
-        ),
-        (
-            "Elf".to_string(),
-            Race::new(
-                "Elf",
-                [(Ability::Dexterity, 2), (Ability::Constitution, -2)]
-                    .iter()
-                    .cloned()
-                    .collect(),
-                vec![
-                    "Darkvision".to_string(),
-                    "Keen Senses".to_string(),
-                    "Fae Ancestry".to_string(),
-                    "Trance".to_string(),
-                ],
-                30,
-            ),
-        ),
-        (
-            "Halfling".to_string(),
-            Race::new(
-                "Halfling",
-                [(Ability::Constitution, 2)].iter().cloned().collect(),
-                vec![
-                    "Lucky".to_string(),
-                    "Brave".to_string(),
-                    "Halfling Nimbleness".to_string(),
-                ],
-                25,
-            ),
-        ),
-        (
-            "Human".to_string(),
-            Race::new(
-                "Human",
-                [
-                    (Ability::Strength, 1),
-                    (Ability::Dexterity, 1),
-                    (Ability::Constitution, 1),
-                    (Ability::Intelligence, 1),
-                    (Ability::Wisdom, 1),
-                    (Ability::Charisma, 1),
-                ]
-                .iter()
-                .cloned()
-                .collect(),
-                vec!["odd elections".to_string()],
-                30,
-            ),
-        ),
-    ]
-    .iter()
-    .cloned()
-    .collect()
+/// ## load_races
+/// Read races from JSON.  The JSON can be a single race, a world content document such as
+/// `{ "kind": "race", "apiVersion": "v1", "spec": { ... } }`, or an array of either.
+///
+/// ### Arguments
+/// * `json` - The races, e.g. the contents of a file in a world's `races` directory.
+///
+/// ### Returns
+/// The races in the order they were written, or an error if the JSON is not a race or a
+/// document is of another kind.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::races::load_races;
+///
+/// let races = load_races(r#"{
+///     "kind": "race",
+///     "apiVersion": "v1",
+///     "spec": {
+///         "code": "vesk",
+///         "name": "Vesk",
+///         "ability_modifiers": { "strength": 2, "constitution": 2, "intelligence": -2 },
+///         "hit_points": 6
+///     }
+/// }"#).unwrap();
+///
+/// assert_eq!(races[0].ability_modifiers[&Ability::Strength], 2);
+/// assert_eq!(races[0].speed, 30);
+/// ```
+pub fn load_races(json: &str) -> Result<Vec<Race>, serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+    match value {
+        Value::Array(values) => values.into_iter().map(race_from_value).collect(),
+        value => Ok(vec![race_from_value(value)?]),
+    }
+}
+
+fn race_from_value(value: Value) -> Result<Race, serde_json::Error> {
+    match value.get("kind").and_then(Value::as_str) {
+        Some("race") => serde_json::from_value(value["spec"].clone()),
+        Some(kind) => Err(serde_json::Error::custom(format!(
+            "expected a race, but found a {}",
+            kind
+        ))),
+        None => serde_json::from_value(value),
+    }
+}
+
+/// The races used when a world does not define its own, by name.
+pub fn load_default_races() -> HashMap<String, Race> {
+    load_races(include_str!("default_races.json"))
+        .expect("the default races are valid")
+        .into_iter()
+        .map(|race| (race.name.clone(), race))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Ability;
+
+    #[test]
+    fn test_default_races() {
+        let races = load_default_races();
+
+        assert_eq!(races.len(), 4);
+        assert_eq!(races["Dwarf"].code, "dwarf");
+        assert_eq!(races["Dwarf"].ability_modifiers[&Ability::Charisma], -2);
+        assert!(races["Human"].special_abilities.is_empty());
+    }
+
+    #[test]
+    fn test_load_races_rejects_other_kinds() {
+        let error = load_races(r#"[{ "kind": "item", "spec": {} }]"#).unwrap_err();
+        assert_eq!(error.to_string(), "expected a race, but found a item");
+
+        assert!(load_races(r#"{ "code": "ysoki" }"#).is_err());
+    }
+
+    #[test]
+    fn test_abilities_are_read_by_name_or_abbreviation() {
+        let races = load_races(
+            r#"{ "code": "vesk", "name": "Vesk", "ability_modifiers": { "STR": 2, "Constitution": 2 } }"#,
+        )
+        .unwrap();
+        assert_eq!(races[0].ability_modifiers[&Ability::Strength], 2);
+        assert_eq!(races[0].ability_modifiers[&Ability::Constitution], 2);
+
+        let error =
+            load_races(r#"{ "code": "vesk", "name": "Vesk", "ability_modifiers": { "luck": 1 } }"#)
+                .unwrap_err();
+        assert!(error.to_string().contains("unknown variant `luck`"));
+    }
 }
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// ## Sense
+/// A way of perceiving beyond normal sight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sense {
+    /// See in darkness, in black and white, within range.
+    Darkvision,
+    /// See in dim light as if it were bright.
+    LowLightVision,
+    /// Notice creatures within range without seeing them, but not make them out clearly.
+    Blindsense,
+    /// Perceive everything within range without needing light.
+    Blindsight,
+}
+
+/// ## LightLevel
+/// How well lit the space between an observer and what they are looking at is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightLevel {
+    Bright,
+    Dim,
+    Darkness,
+}
+
+/// ## Visibility
+/// How well an observer can see something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// It cannot be seen at all.
+    Hidden,
+    /// It can be seen, but not clearly, e.g. a shape in dim light.
+    Obscured,
+    Clear,
+}
+
+/// ## visibility
+/// How well an observer with the given senses sees something at a distance.
+///
+/// ### Arguments
+/// * `senses` - The observer's senses and their ranges in feet.
+/// * `light` - The light at the target.
+/// * `distance` - The distance to the target in feet.
+///
+/// ### Returns
+/// `Clear` in bright light; in dim light with low-light vision, or within range of darkvision
+/// or blindsight; and in darkness within range of darkvision or blindsight.  Otherwise dim
+/// light and blindsense within range give `Obscured`, and darkness `Hidden`.
+///
+/// ### Example
+/// ```
+/// use crate::logic::races::{visibility, LightLevel, Sense, Visibility};
+///
+/// let senses = [(Sense::Darkvision, 60)];
+/// assert_eq!(visibility(&senses, LightLevel::Darkness, 30), Visibility::Clear);
+/// assert_eq!(visibility(&senses, LightLevel::Darkness, 90), Visibility::Hidden);
+/// assert_eq!(visibility(&[], LightLevel::Dim, 10), Visibility::Obscured);
+/// ```
+pub fn visibility(senses: &[(Sense, i32)], light: LightLevel, distance: i32) -> Visibility {
+    let within = |sense: Sense| {
+        senses
+            .iter()
+            .any(|(other, range)| *other == sense && distance <= *range)
+    };
+    let has = |sense: Sense| senses.iter().any(|(other, _)| *other == sense);
+
+    let clear = match light {
+        LightLevel::Bright => true,
+        LightLevel::Dim => {
+            has(Sense::LowLightVision) || within(Sense::Darkvision) || within(Sense::Blindsight)
+        }
+        LightLevel::Darkness => within(Sense::Darkvision) || within(Sense::Blindsight),
+    };
+
+    if clear {
+        Visibility::Clear
+    } else if light == LightLevel::Dim || within(Sense::Blindsense) {
+        Visibility::Obscured
+    } else {
+        Visibility::Hidden
+    }
+}
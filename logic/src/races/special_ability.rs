@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+use super::Sense;
+use crate::bonuses::{Bonus, BonusTarget, BonusType};
+
+/// ## SpecialAbilityEffect
+/// Something a special ability does that the rules apply automatically.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "effect", rename_all = "snake_case")]
+pub enum SpecialAbilityEffect {
+    /// A sense with a range in feet, e.g. darkvision 60 ft.
+    Sense { sense: Sense, range: i32 },
+    /// A bonus to saving throws against a damage type or condition, e.g. +2 against poison.
+    SaveBonus {
+        /// The saving throw, e.g. `fortitude`, or every saving throw when missing.
+        #[serde(default)]
+        save: Option<String>,
+        /// The damage type or condition code the bonus applies against, e.g. `poisoned`.
+        against: String,
+        value: i32,
+    },
+    /// A bonus to a skill, by code.
+    SkillBonus { skill: String, value: i32 },
+    /// A proficiency, e.g. `Heavy Armor` or `battleaxe`.
+    Proficiency { proficiency: String },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpecialAbilityData {
+    Name(String),
+    Definition {
+        #[serde(default)]
+        code: Option<String>,
+        name: String,
+        #[serde(default)]
+        description: String,
+        #[serde(default)]
+        effects: Vec<SpecialAbilityEffect>,
+    },
+}
+
+impl From<SpecialAbilityData> for SpecialAbility {
+    fn from(data: SpecialAbilityData) -> Self {
+        match data {
+            SpecialAbilityData::Name(name) => SpecialAbility::new(&name),
+            SpecialAbilityData::Definition {
+                code,
+                name,
+                description,
+                effects,
+            } => SpecialAbility {
+                code: code.unwrap_or_else(|| ability_code(&name)),
+                name,
+                description,
+                effects,
+            },
+        }
+    }
+}
+
+fn ability_code(name: &str) -> String {
+    name.to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// ## SpecialAbility
+/// A racial special ability such as darkvision or dwarven resilience.  A special ability can be
+/// written as just its name, in which case it has no effects the rules apply.
+///
+/// ### Example
+/// ```
+/// use crate::logic::races::{Sense, SpecialAbility};
+///
+/// let abilities: Vec<SpecialAbility> = serde_json::from_str(r#"[
+///     "Stonecunning",
+///     {
+///         "name": "Darkvision",
+///         "effects": [{ "effect": "sense", "sense": "darkvision", "range": 60 }]
+///     }
+/// ]"#).unwrap();
+///
+/// assert_eq!(abilities[0].code, "stonecunning");
+/// assert!(abilities[0].effects.is_empty());
+/// assert_eq!(abilities[1].senses(), vec![(Sense::Darkvision, 60)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "SpecialAbilityData")]
+pub struct SpecialAbility {
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    pub effects: Vec<SpecialAbilityEffect>,
+}
+
+impl SpecialAbility {
+    /// A special ability with no effects, coded from its name, e.g. `Keen Senses` is
+    /// `keen-senses`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            code: ability_code(name),
+            name: name.to_string(),
+            description: String::new(),
+            effects: vec![],
+        }
+    }
+
+    pub fn with_effect(mut self, effect: SpecialAbilityEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    pub fn senses(&self) -> Vec<(Sense, i32)> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect {
+                SpecialAbilityEffect::Sense { sense, range } => Some((*sense, *range)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn proficiencies(&self) -> Vec<&str> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect {
+                SpecialAbilityEffect::Proficiency { proficiency } => Some(proficiency.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The racial skill bonuses the ability gives, with its name as their source.
+    pub fn skill_bonuses(&self) -> Vec<Bonus> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect {
+                SpecialAbilityEffect::SkillBonus { skill, value } => Some(Bonus::new(
+                    &self.name,
+                    BonusType::Racial,
+                    BonusTarget::Skill(skill.clone()),
+                    *value,
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The racial bonuses the ability gives to a saving throw against a damage type or
+    /// condition, with its name as their source.
+    pub fn save_bonuses(&self, save: &str, against: &str) -> Vec<Bonus> {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect {
+                SpecialAbilityEffect::SaveBonus {
+                    save: bonus_save,
+                    against: bonus_against,
+                    value,
+                } if bonus_against == against
+                    && bonus_save
+                        .as_deref()
+                        .is_none_or(|bonus_save| bonus_save == save) =>
+                {
+                    Some(Bonus::new(
+                        &self.name,
+                        BonusType::Racial,
+                        BonusTarget::Save(save.to_string()),
+                        *value,
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl From<&str> for SpecialAbility {
+    fn from(name: &str) -> Self {
+        SpecialAbility::new(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_bonuses_match_the_save_and_what_they_are_against() {
+        let resilience = SpecialAbility::new("Dwarven Resilience").with_effect(
+            SpecialAbilityEffect::SaveBonus {
+                save: Some("fortitude".to_string()),
+                against: "poisoned".to_string(),
+                value: 2,
+            },
+        );
+
+        assert_eq!(resilience.save_bonuses("fortitude", "poisoned")[0].value, 2);
+        assert!(resilience.save_bonuses("will", "poisoned").is_empty());
+        assert!(resilience.save_bonuses("fortitude", "stunned").is_empty());
+    }
+
+    #[test]
+    fn test_special_ability_round_trips() {
+        let keen_senses =
+            SpecialAbility::new("Keen Senses").with_effect(SpecialAbilityEffect::SkillBonus {
+                skill: "perception".to_string(),
+                value: 2,
+            });
+
+        let json = serde_json::to_string(&keen_senses).unwrap();
+        assert!(json.contains(r#"{"effect":"skill_bonus","skill":"perception","value":2}"#));
+        assert_eq!(
+            serde_json::from_str::<SpecialAbility>(&json).unwrap(),
+            keen_senses
+        );
+    }
+}
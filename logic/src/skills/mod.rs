@@ -0,0 +1,5 @@
+pub mod skill;
+pub mod skill_check;
+
+pub use skill::*;
+pub use skill_check::*;
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+
+/// The bonus a trained character gets on a class skill.
+pub const CLASS_SKILL_BONUS: i32 = 3;
+
+/// ## Skill
+/// A skill defined by a world, e.g. computers, piloting or engineering.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::skills::Skill;
+///
+/// let computers = Skill::new("computers", "Computers", Ability::Intelligence).trained_only();
+/// assert!(computers.trained_only);
+/// assert!(!computers.armor_penalty);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Skill {
+    pub code: String,
+    pub name: String,
+    /// The ability whose modifier is added to checks.
+    pub ability: Ability,
+    /// Checks cannot be attempted without at least one rank.
+    pub trained_only: bool,
+    /// The armor check penalty is subtracted from checks.
+    pub armor_penalty: bool,
+}
+
+impl Skill {
+    pub fn new(code: &str, name: &str, ability: Ability) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            ability,
+            trained_only: false,
+            armor_penalty: false,
+        }
+    }
+
+    pub fn trained_only(mut self) -> Self {
+        self.trained_only = true;
+        self
+    }
+
+    pub fn armor_penalty(mut self) -> Self {
+        self.armor_penalty = true;
+        self
+    }
+}
+
+/// ## CharacterSkill
+/// The ranks a character has put into a skill, and whether it is a class skill for them.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CharacterSkill {
+    pub skill_code: String,
+    pub ranks: i32,
+    pub is_class_skill: bool,
+}
+
+impl CharacterSkill {
+    pub fn new(skill_code: &str, ranks: i32, is_class_skill: bool) -> Self {
+        Self {
+            skill_code: skill_code.to_string(),
+            ranks,
+            is_class_skill,
+        }
+    }
+
+    /// Whether the character has at least one rank in the skill.
+    pub fn is_trained(&self) -> bool {
+        self.ranks > 0
+    }
+}
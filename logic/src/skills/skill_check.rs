@@ -0,0 +1,288 @@
+use std::{error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::{CharacterSkill, Skill, CLASS_SKILL_BONUS};
+use crate::abilities::AbilityScores;
+use crate::bonuses::BonusTarget;
+use crate::entities::Character;
+use crate::progression::DerivedValue;
+use crate::utilities::{d20_expression, roll_dice_check_with, DiceRng, DiceRollError, RollResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillCheckError {
+    /// The skill is trained only and the character has no ranks in it.
+    Untrained(String),
+    Roll(DiceRollError),
+}
+
+impl fmt::Display for SkillCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SkillCheckError::Untrained(skill) => {
+                write!(f, "{} cannot be used untrained", skill)
+            }
+            SkillCheckError::Roll(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl error::Error for SkillCheckError {}
+
+impl From<DiceRollError> for SkillCheckError {
+    fn from(error: DiceRollError) -> Self {
+        SkillCheckError::Roll(error)
+    }
+}
+
+/// ## SkillCheck
+/// The result of rolling a skill check against a DC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillCheck {
+    pub skill: String,
+    /// The total bonus added to the d20, and each part of it.
+    pub bonus: DerivedValue,
+    pub roll: RollResult,
+    pub success: bool,
+}
+
+impl fmt::Display for SkillCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} ({})",
+            self.skill,
+            self.roll,
+            if self.success { "success" } else { "failure" }
+        )
+    }
+}
+
+/// ## skill_bonus
+/// The total bonus a character adds to a check with a skill: their ranks, the skill's ability
+/// modifier, the class skill bonus and any skill bonuses, less the armor check penalty.
+///
+/// ### Arguments
+/// * `character` - The character making the check.
+/// * `skill` - The skill being used.
+/// * `trained` - The character's ranks in the skill, if they have any.
+/// * `armor_check_penalty` - The penalty from the character's armor, e.g. `2` or `-2` for a
+///   -2 penalty.  It only applies to skills with `armor_penalty`.
+///
+/// ### Returns
+/// The bonus with its breakdown.  The class skill bonus only applies once the character has a
+/// rank in the skill.  Using a `trained_only` skill without ranks is an error.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::entities::Character;
+/// use crate::logic::races::load_default_races;
+/// use crate::logic::skills::{skill_bonus, CharacterSkill, Skill};
+///
+/// let races = load_default_races();
+/// let character = Character::new(
+///     "Kira".to_string(), 3, "mechanic".to_string(), races["Human"].clone(),
+///     10, 10, 10, 16, 10, 10,
+/// );
+/// let engineering = Skill::new("engineering", "Engineering", Ability::Intelligence).trained_only();
+/// let ranks = CharacterSkill::new("engineering", 3, true);
+///
+/// let bonus = skill_bonus(&character, &engineering, Some(&ranks), 0).unwrap();
+/// assert_eq!(bonus.to_string(), "3 (ranks) + 3 (INT) + 3 (class skill) = 9");
+/// ```
+pub fn skill_bonus(
+    character: &Character,
+    skill: &Skill,
+    trained: Option<&CharacterSkill>,
+    armor_check_penalty: i32,
+) -> Result<DerivedValue, SkillCheckError> {
+    let ranks = trained.map(|trained| trained.ranks.max(0)).unwrap_or(0);
+    if skill.trained_only && ranks == 0 {
+        return Err(SkillCheckError::Untrained(skill.name.clone()));
+    }
+
+    let mut bonus = DerivedValue::default();
+    if ranks > 0 {
+        bonus.add("ranks".to_string(), ranks);
+    }
+    bonus.add(
+        skill.ability.abbreviation().to_string(),
+        character.get_modifier(skill.ability),
+    );
+    if ranks > 0 && trained.is_some_and(|trained| trained.is_class_skill) {
+        bonus.add("class skill".to_string(), CLASS_SKILL_BONUS);
+    }
+    for applied in character.applied_bonuses(&BonusTarget::Skill(skill.code.clone())) {
+        if applied.applied {
+            bonus.add(
+                format!("{} {}", applied.bonus.source, applied.bonus.bonus_type),
+                applied.bonus.value,
+            );
+        }
+    }
+    if skill.armor_penalty && armor_check_penalty != 0 {
+        bonus.add(
+            "armor check penalty".to_string(),
+            -armor_check_penalty.saturating_abs(),
+        );
+    }
+
+    Ok(bonus)
+}
+
+/// ## skill_check
+/// Roll a d20 plus a character's [`skill_bonus`] against a DC.
+///
+/// ### Arguments
+/// * `character` - The character making the check.
+/// * `skill` - The skill being used.
+/// * `trained` - The character's ranks in the skill, if they have any.
+/// * `armor_check_penalty` - The penalty from the character's armor.
+/// * `difficulty_check` - The DC the check must meet or exceed.
+/// * `rng` - The generator used for the d20.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::entities::Character;
+/// use crate::logic::races::load_default_races;
+/// use crate::logic::skills::{skill_check, CharacterSkill, Skill};
+/// use crate::logic::utilities::ScriptedRng;
+///
+/// let races = load_default_races();
+/// let character = Character::new(
+///     "Kira".to_string(), 1, "operative".to_string(), races["Human"].clone(),
+///     10, 14, 10, 10, 10, 10,
+/// );
+/// let piloting = Skill::new("piloting", "Piloting", Ability::Dexterity);
+/// let ranks = CharacterSkill::new("piloting", 1, true);
+///
+/// let check = skill_check(&character, &piloting, Some(&ranks), 0, 15, &mut ScriptedRng::new(vec![9]))
+///     .unwrap();
+/// assert!(check.success);
+/// assert_eq!(check.to_string(), "Piloting: 1d20 (9) + 6 = 15 vs DC 15 (success)");
+/// ```
+pub fn skill_check<R: DiceRng + ?Sized>(
+    character: &Character,
+    skill: &Skill,
+    trained: Option<&CharacterSkill>,
+    armor_check_penalty: i32,
+    difficulty_check: i32,
+    rng: &mut R,
+) -> Result<SkillCheck, SkillCheckError> {
+    let bonus = skill_bonus(character, skill, trained, armor_check_penalty)?;
+    let roll = roll_dice_check_with(d20_expression(bonus.total), difficulty_check, rng)?;
+    Ok(SkillCheck {
+        skill: skill.name.clone(),
+        bonus,
+        success: roll.is_success(),
+        roll: roll.result().clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Ability;
+    use crate::bonuses::{Bonus, BonusType};
+    use crate::races::load_default_races;
+    use crate::utilities::ScriptedRng;
+
+    fn character(dexterity: i32, intelligence: i32) -> Character {
+        Character::new(
+            "Test".to_string(),
+            1,
+            "mechanic".to_string(),
+            load_default_races()["Human"].clone(),
+            10,
+            dexterity,
+            10,
+            intelligence,
+            10,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_trained_only_skills_need_ranks() {
+        let computers = Skill::new("computers", "Computers", Ability::Intelligence).trained_only();
+
+        let error = skill_bonus(&character(10, 14), &computers, None, 0).unwrap_err();
+        assert_eq!(error, SkillCheckError::Untrained("Computers".to_string()));
+
+        let untrained = CharacterSkill::new("computers", 0, true);
+        assert!(skill_bonus(&character(10, 14), &computers, Some(&untrained), 0).is_err());
+    }
+
+    #[test]
+    fn test_class_skill_bonus_needs_a_rank() {
+        let piloting = Skill::new("piloting", "Piloting", Ability::Dexterity);
+        let untrained = CharacterSkill::new("piloting", 0, true);
+
+        let bonus = skill_bonus(&character(14, 10), &piloting, Some(&untrained), 0).unwrap();
+        assert_eq!(bonus.total, 2);
+        assert_eq!(bonus.breakdown, vec![("DEX".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_armor_check_penalty_only_applies_to_armor_skills() {
+        let athletics = Skill::new("athletics", "Athletics", Ability::Strength).armor_penalty();
+        let engineering = Skill::new("engineering", "Engineering", Ability::Intelligence);
+
+        let athletics = skill_bonus(&character(10, 10), &athletics, None, -3).unwrap();
+        assert_eq!(athletics.total, -3);
+        assert_eq!(
+            athletics.to_string(),
+            "0 (STR) + -3 (armor check penalty) = -3"
+        );
+        assert_eq!(
+            skill_bonus(&character(10, 10), &engineering, None, 3)
+                .unwrap()
+                .total,
+            0
+        );
+    }
+
+    #[test]
+    fn test_skill_bonuses_stack_by_type() {
+        let mut hacker = character(10, 16);
+        hacker.add_bonus(Bonus::new(
+            "hacking kit",
+            BonusType::Item,
+            BonusTarget::Skill("computers".to_string()),
+            2,
+        ));
+        hacker.add_bonus(Bonus::new(
+            "datajack",
+            BonusType::Item,
+            BonusTarget::Skill("computers".to_string()),
+            1,
+        ));
+        let computers = Skill::new("computers", "Computers", Ability::Intelligence).trained_only();
+        let ranks = CharacterSkill::new("computers", 1, false);
+
+        let bonus = skill_bonus(&hacker, &computers, Some(&ranks), 0).unwrap();
+        assert_eq!(bonus.total, 1 + 3 + 2);
+        assert_eq!(bonus.breakdown[2], ("hacking kit item".to_string(), 2));
+    }
+
+    #[test]
+    fn test_skill_check_rolls_against_the_dc() {
+        let athletics = Skill::new("athletics", "Athletics", Ability::Strength).armor_penalty();
+
+        let check = skill_check(
+            &character(10, 10),
+            &athletics,
+            None,
+            2,
+            10,
+            &mut ScriptedRng::new(vec![11]),
+        )
+        .unwrap();
+
+        assert!(!check.success);
+        assert_eq!(check.roll.total, 9);
+        assert_eq!(check.roll.expression, "1d20-2");
+    }
+}
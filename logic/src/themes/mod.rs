@@ -0,0 +1,3 @@
+pub mod theme;
+
+pub use theme::*;
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+use crate::bonuses::{Bonus, BonusTarget, BonusType};
+
+/// ## ThemeFeature
+/// A feature a theme grants when a character reaches `level`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemeFeature {
+    pub level: i32,
+    pub code: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// ## Theme
+/// A character's background, such as ace pilot or bounty hunter, with the ability bonuses and
+/// features it grants.
+///
+/// ### Example
+/// ```
+/// use crate::logic::abilities::Ability;
+/// use crate::logic::themes::{Theme, ThemeFeature};
+///
+/// let ace_pilot = Theme::new("ace-pilot", "Ace Pilot")
+///     .with_ability_bonus(Ability::Dexterity, 1)
+///     .with_feature(ThemeFeature {
+///         level: 1,
+///         code: "ace-pilot-theme-knowledge".to_string(),
+///         name: "Theme Knowledge".to_string(),
+///         description: "You know starships and vehicles.".to_string(),
+///     });
+///
+/// assert_eq!(ace_pilot.bonuses()[0].value, 1);
+/// assert_eq!(ace_pilot.features_up_to(6).count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub ability_bonuses: HashMap<Ability, i32>,
+    #[serde(default)]
+    pub features: Vec<ThemeFeature>,
+}
+
+impl Theme {
+    pub fn new(code: &str, name: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            ability_bonuses: HashMap::new(),
+            features: vec![],
+        }
+    }
+
+    pub fn with_ability_bonus(mut self, ability: Ability, value: i32) -> Self {
+        self.ability_bonuses.insert(ability, value);
+        self
+    }
+
+    pub fn with_feature(mut self, feature: ThemeFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// The features granted on reaching `level`.
+    pub fn features_at(&self, level: i32) -> impl Iterator<Item = &ThemeFeature> {
+        self.features
+            .iter()
+            .filter(move |feature| feature.level == level)
+    }
+
+    /// Every feature a character of `level` has from the theme.
+    pub fn features_up_to(&self, level: i32) -> impl Iterator<Item = &ThemeFeature> {
+        self.features
+            .iter()
+            .filter(move |feature| feature.level <= level)
+    }
+
+    /// The ability bonuses the theme gives, with its name as their source.
+    pub fn bonuses(&self) -> Vec<Bonus> {
+        Ability::all()
+            .into_iter()
+            .filter_map(|ability| {
+                self.ability_bonuses.get(&ability).map(|value| {
+                    Bonus::new(
+                        &self.name,
+                        BonusType::Untyped,
+                        BonusTarget::Ability(ability),
+                        *value,
+                    )
+                })
+            })
+            .collect()
+    }
+}
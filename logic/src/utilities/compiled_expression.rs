@@ -0,0 +1,1039 @@
+use std::{error, fmt};
+
+use super::{
+    parse_dice_expression_with, DiceExpression, DiceRng, EvaluationLimits, ExpressionContext,
+};
+
+/// ## ExpressionError
+/// An expression that could not be compiled or evaluated.  The position is the character index
+/// in the expression text, when the problem can be pinned to one place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    message: String,
+    position: Option<usize>,
+}
+
+impl ExpressionError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            position: None,
+        }
+    }
+
+    /// Create an error that points at the character at `position` in the expression.
+    pub fn at(message: &str, position: usize) -> Self {
+        Self {
+            message: message.to_string(),
+            position: Some(position),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(
+                f,
+                "invalid expression: {} at position {}",
+                self.message, position
+            ),
+            None => write!(f, "invalid expression: {}", self.message),
+        }
+    }
+}
+
+impl error::Error for ExpressionError {}
+
+/// ## Number
+/// The value of an expression while it is evaluated.  Integers stay integers, so `7 / 2` is `3`,
+/// until a decimal is involved, so `7 / 2.0` is `3.5`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Number::Int(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+
+    /// The value rounded to the nearest integer.
+    pub fn round(&self) -> i64 {
+        match self {
+            Number::Int(value) => *value,
+            Number::Float(value) => value.round() as i64,
+        }
+    }
+
+    pub(crate) fn parse(text: &str) -> Option<Number> {
+        let text = text.trim();
+        match text.parse::<i64>() {
+            Ok(value) => Some(Number::Int(value)),
+            Err(_) => text.parse::<f64>().ok().map(Number::Float),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Int(value) => write!(f, "{}", value),
+            Number::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// ## Operator
+/// The binary operators of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+impl Operator {
+    pub fn symbol(&self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+            Operator::Modulo => '%',
+        }
+    }
+
+    /// Apply the operator, returning a message instead of a value on division by zero or a
+    /// result that is too large to hold.
+    pub(crate) fn apply(&self, left: Number, right: Number) -> Result<Number, String> {
+        let is_division = matches!(self, Operator::Divide | Operator::Modulo);
+        if is_division && right.as_f64() == 0.0 {
+            return Err("division by zero".to_string());
+        }
+
+        let result = match (left, right) {
+            (Number::Int(l), Number::Int(r)) => match self {
+                Operator::Add => l.checked_add(r),
+                Operator::Subtract => l.checked_sub(r),
+                Operator::Multiply => l.checked_mul(r),
+                Operator::Divide => l.checked_div(r),
+                Operator::Modulo => l.checked_rem(r),
+            }
+            .map(Number::Int),
+            _ => {
+                let (l, r) = (left.as_f64(), right.as_f64());
+                let value = match self {
+                    Operator::Add => l + r,
+                    Operator::Subtract => l - r,
+                    Operator::Multiply => l * r,
+                    Operator::Divide => l / r,
+                    Operator::Modulo => l % r,
+                };
+                Some(Number::Float(value)).filter(|_| value.is_finite())
+            }
+        };
+        result.ok_or_else(|| format!("{} {} {} is too large", left, self.symbol(), right))
+    }
+}
+
+/// ## Function
+/// The functions that can be called in an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Min,
+    Max,
+    Floor,
+    Ceil,
+    Round,
+    Abs,
+}
+
+impl Function {
+    pub fn from_name(name: &str) -> Option<Function> {
+        match name.to_ascii_lowercase().as_str() {
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "floor" => Some(Function::Floor),
+            "ceil" => Some(Function::Ceil),
+            "round" => Some(Function::Round),
+            "abs" => Some(Function::Abs),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Floor => "floor",
+            Function::Ceil => "ceil",
+            Function::Round => "round",
+            Function::Abs => "abs",
+        }
+    }
+
+    fn accepts(&self, arguments: usize) -> bool {
+        match self {
+            Function::Min | Function::Max => arguments >= 1,
+            _ => arguments == 1,
+        }
+    }
+
+    pub(crate) fn apply(&self, arguments: &[Number]) -> Option<Number> {
+        let whole = |value: f64| Number::Int(value as i64);
+        let value = match self {
+            Function::Min => arguments
+                .iter()
+                .copied()
+                .reduce(|a, b| if b.as_f64() < a.as_f64() { b } else { a })
+                .unwrap_or(Number::Int(0)),
+            Function::Max => arguments
+                .iter()
+                .copied()
+                .reduce(|a, b| if b.as_f64() > a.as_f64() { b } else { a })
+                .unwrap_or(Number::Int(0)),
+            Function::Floor => match arguments[0] {
+                Number::Float(value) => whole(value.floor()),
+                int => int,
+            },
+            Function::Ceil => match arguments[0] {
+                Number::Float(value) => whole(value.ceil()),
+                int => int,
+            },
+            Function::Round => Number::Int(arguments[0].round()),
+            Function::Abs => match arguments[0] {
+                Number::Int(value) => Number::Int(value.checked_abs()?),
+                Number::Float(value) => Number::Float(value.abs()),
+            },
+        };
+        Some(value)
+    }
+}
+
+/// ## ExpressionNode
+/// A node of a compiled expression.  Every node remembers where it started in the text so that
+/// errors found while evaluating can point back at it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionNode {
+    Number(Number),
+    /// A variable written as `NAME`, `$NAME` or `${NAME}`.
+    Variable {
+        name: String,
+        position: usize,
+    },
+    /// A `roll(...)` call, already parsed into a dice expression.
+    Roll {
+        dice: DiceExpression,
+        position: usize,
+    },
+    Call {
+        function: Function,
+        arguments: Vec<ExpressionNode>,
+        position: usize,
+    },
+    Negate(Box<ExpressionNode>),
+    Binary {
+        operator: Operator,
+        left: Box<ExpressionNode>,
+        right: Box<ExpressionNode>,
+        position: usize,
+    },
+}
+
+impl ExpressionNode {
+    fn evaluate<R: DiceRng + ?Sized>(
+        &self,
+        context: &ExpressionContext,
+        rng: &mut R,
+    ) -> Result<Number, ExpressionError> {
+        match self {
+            ExpressionNode::Number(value) => Ok(*value),
+            ExpressionNode::Variable { name, position } => {
+                let value = context.get(name).ok_or_else(|| {
+                    ExpressionError::at(&format!("unknown variable '{}'", name), *position)
+                })?;
+                Number::parse(value).ok_or_else(|| {
+                    ExpressionError::at(
+                        &format!("variable '{}' is not a number ('{}')", name, value),
+                        *position,
+                    )
+                })
+            }
+            ExpressionNode::Roll { dice, position } => {
+                let total = dice
+                    .roll(rng)
+                    .map_err(|error| ExpressionError::at(error.message(), *position))?;
+                Ok(Number::Int(total.max(0) as i64))
+            }
+            ExpressionNode::Call {
+                function,
+                arguments,
+                position,
+            } => {
+                let values = arguments
+                    .iter()
+                    .map(|argument| argument.evaluate(context, rng))
+                    .collect::<Result<Vec<Number>, ExpressionError>>()?;
+                function.apply(&values).ok_or_else(|| {
+                    ExpressionError::at(
+                        &format!("the result of '{}' is too large", function.name()),
+                        *position,
+                    )
+                })
+            }
+            ExpressionNode::Negate(inner) => match inner.evaluate(context, rng)? {
+                Number::Int(value) => value
+                    .checked_neg()
+                    .map(Number::Int)
+                    .ok_or_else(|| ExpressionError::new(&format!("-({}) is too large", value))),
+                Number::Float(value) => Ok(Number::Float(-value)),
+            },
+            ExpressionNode::Binary {
+                operator,
+                left,
+                right,
+                position,
+            } => {
+                let left = left.evaluate(context, rng)?;
+                let right = right.evaluate(context, rng)?;
+                operator
+                    .apply(left, right)
+                    .map_err(|message| ExpressionError::at(&message, *position))
+            }
+        }
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut Vec<(&'a str, usize)>) {
+        match self {
+            ExpressionNode::Variable { name, position } => variables.push((name, *position)),
+            ExpressionNode::Call { arguments, .. } => arguments
+                .iter()
+                .for_each(|argument| argument.collect_variables(variables)),
+            ExpressionNode::Negate(inner) => inner.collect_variables(variables),
+            ExpressionNode::Binary { left, right, .. } => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+            ExpressionNode::Number(_) | ExpressionNode::Roll { .. } => {}
+        }
+    }
+
+    fn rolls_dice(&self) -> bool {
+        match self {
+            ExpressionNode::Roll { .. } => true,
+            ExpressionNode::Call { arguments, .. } => arguments.iter().any(Self::rolls_dice),
+            ExpressionNode::Negate(inner) => inner.rolls_dice(),
+            ExpressionNode::Binary { left, right, .. } => left.rolls_dice() || right.rolls_dice(),
+            ExpressionNode::Number(_) | ExpressionNode::Variable { .. } => false,
+        }
+    }
+}
+
+/// ## CompiledExpression
+/// An expression such as `10 + $CON` or `max(1, roll(1d8) + STR)` that is parsed once and can
+/// then be evaluated any number of times, against any context.
+///
+/// Expressions support integer and decimal numbers, `+ - * / %` with the usual precedence,
+/// parentheses, unary minus, variables (`CON`, `$CON` or `${CON}`), any number of
+/// `roll(...)` dice calls and the functions `min`, `max`, `floor`, `ceil`, `round` and `abs`.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::compiled_expression::CompiledExpression;
+/// use crate::logic::utilities::expression_parser::ExpressionContext;
+///
+/// let stamina = CompiledExpression::compile("10 + $CON").unwrap();
+///
+/// let mut context = ExpressionContext::new();
+/// context.set("CON", "2");
+/// assert_eq!(stamina.evaluate(&context).unwrap(), 12);
+///
+/// context.set("CON", "-1");
+/// assert_eq!(stamina.evaluate(&context).unwrap(), 9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledExpression {
+    source: String,
+    root: ExpressionNode,
+}
+
+impl CompiledExpression {
+    /// ## compile
+    /// Parse an expression into a `CompiledExpression`.
+    ///
+    /// ### Arguments
+    /// * `expression` - A string slice that holds the expression, e.g. `4 + INT`
+    ///
+    /// ### Returns
+    /// The compiled expression, or an `ExpressionError` that points at the offending character.
+    pub fn compile(expression: &str) -> Result<Self, ExpressionError> {
+        Self::compile_with(expression, &EvaluationLimits::default())
+    }
+
+    /// ## compile_with
+    /// Parse an expression, as [`CompiledExpression::compile`] does, rejecting any expression
+    /// that exceeds the given limits.  The dice limit is shared by every `roll(...)` call in the
+    /// expression.
+    ///
+    /// ### Arguments
+    /// * `expression` - A string slice that holds the expression, e.g. `roll(1d8) + CON`
+    /// * `limits` - The limits the expression must stay within.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::compiled_expression::CompiledExpression;
+    /// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+    ///
+    /// let limits = EvaluationLimits {
+    ///     max_dice: 4,
+    ///     ..EvaluationLimits::default()
+    /// };
+    /// assert!(CompiledExpression::compile_with("roll(2d6) + roll(2d6)", &limits).is_ok());
+    ///
+    /// let error = CompiledExpression::compile_with("roll(2d6) + roll(3d6)", &limits).unwrap_err();
+    /// assert_eq!(error.message(), "expression rolls more than 4 dice");
+    /// assert_eq!(error.position(), Some(12));
+    /// ```
+    pub fn compile_with(
+        expression: &str,
+        limits: &EvaluationLimits,
+    ) -> Result<Self, ExpressionError> {
+        let end = expression.chars().count();
+        if end > limits.max_expression_length {
+            return Err(ExpressionError::at(
+                &format!(
+                    "expression is longer than {} characters",
+                    limits.max_expression_length
+                ),
+                limits.max_expression_length,
+            ));
+        }
+
+        let tokens = tokenize(expression, limits)?;
+        if tokens.is_empty() {
+            return Err(ExpressionError::at("empty expression", 0));
+        }
+
+        let mut parser = Parser {
+            tokens,
+            index: 0,
+            end,
+            max_nesting: limits.max_nesting,
+            depth: 0,
+        };
+        let root = parser.parse_expression()?;
+        parser.expect_end()?;
+
+        Ok(Self {
+            source: expression.to_string(),
+            root,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn root(&self) -> &ExpressionNode {
+        &self.root
+    }
+
+    /// Every variable the expression reads, with the position it appears at, in order.
+    pub fn variables(&self) -> Vec<(&str, usize)> {
+        let mut variables = vec![];
+        self.root.collect_variables(&mut variables);
+        variables
+    }
+
+    /// Whether the expression has a `roll(...)` call, so that evaluating it twice can give
+    /// different results.
+    pub fn rolls_dice(&self) -> bool {
+        self.root.rolls_dice()
+    }
+
+    /// ## evaluate
+    /// Evaluate the expression against a context, rounding the result to the nearest integer.
+    /// Dice are rolled with the thread local generator.
+    pub fn evaluate(&self, context: &ExpressionContext) -> Result<i64, ExpressionError> {
+        self.evaluate_with(context, &mut rand::thread_rng())
+    }
+
+    /// ## evaluate_with
+    /// The same as [`CompiledExpression::evaluate`], but dice are rolled with `rng`.
+    pub fn evaluate_with<R: DiceRng + ?Sized>(
+        &self,
+        context: &ExpressionContext,
+        rng: &mut R,
+    ) -> Result<i64, ExpressionError> {
+        Ok(self.evaluate_number(context, rng)?.round())
+    }
+
+    /// Evaluate the expression without rounding the result.
+    pub fn evaluate_number<R: DiceRng + ?Sized>(
+        &self,
+        context: &ExpressionContext,
+        rng: &mut R,
+    ) -> Result<Number, ExpressionError> {
+        self.root.evaluate(context, rng)
+    }
+}
+
+impl fmt::Display for CompiledExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(Number),
+    Identifier(String),
+    Variable(String),
+    Roll(DiceExpression),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match &self.kind {
+            TokenKind::Number(value) => format!("'{}'", value),
+            TokenKind::Identifier(name) => format!("'{}'", name),
+            TokenKind::Variable(name) => format!("'${}'", name),
+            TokenKind::Roll(dice) => format!("'roll({})'", dice),
+            TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn tokenize(input: &str, limits: &EvaluationLimits) -> Result<Vec<Token>, ExpressionError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    let mut dice_count = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let start = index;
+
+        if c.is_whitespace() {
+            index += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            let text: String = chars[start..index].iter().collect();
+            let value = Number::parse(&text)
+                .ok_or_else(|| ExpressionError::at(&format!("invalid number '{}'", text), start))?;
+            tokens.push(Token {
+                kind: TokenKind::Number(value),
+                position: start,
+            });
+        } else if is_identifier_start(c) {
+            while index < chars.len() && is_identifier_char(chars[index]) {
+                index += 1;
+            }
+            let name: String = chars[start..index].iter().collect();
+
+            let mut open = index;
+            while open < chars.len() && chars[open].is_whitespace() {
+                open += 1;
+            }
+            if name.eq_ignore_ascii_case("roll") && chars.get(open) == Some(&'(') {
+                let (dice, after) = tokenize_roll(&chars, open, limits)?;
+                dice_count += dice.dice_count();
+                if dice_count > limits.max_dice {
+                    return Err(ExpressionError::at(
+                        &format!("expression rolls more than {} dice", limits.max_dice),
+                        start,
+                    ));
+                }
+                index = after;
+                tokens.push(Token {
+                    kind: TokenKind::Roll(dice),
+                    position: start,
+                });
+            } else {
+                tokens.push(Token {
+                    kind: TokenKind::Identifier(name),
+                    position: start,
+                });
+            }
+        } else if c == '$' {
+            index += 1;
+            let braced = chars.get(index) == Some(&'{');
+            if braced {
+                index += 1;
+            }
+            let name_start = index;
+            while index < chars.len() && is_identifier_char(chars[index]) {
+                index += 1;
+            }
+            if name_start == index {
+                return Err(ExpressionError::at("expected a variable name", index));
+            }
+            let name: String = chars[name_start..index].iter().collect();
+            if braced {
+                if chars.get(index) != Some(&'}') {
+                    return Err(ExpressionError::at("expected '}'", index));
+                }
+                index += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Variable(name),
+                position: start,
+            });
+        } else if "+-*/%(),".contains(c) {
+            index += 1;
+            tokens.push(Token {
+                kind: TokenKind::Symbol(c),
+                position: start,
+            });
+        } else {
+            return Err(ExpressionError::at(
+                &format!("unexpected character '{}'", c),
+                start,
+            ));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parse the dice notation between the parentheses of a `roll(...)` call that opens at `open`,
+/// returning the dice expression and the index just after the closing parenthesis.
+fn tokenize_roll(
+    chars: &[char],
+    open: usize,
+    limits: &EvaluationLimits,
+) -> Result<(DiceExpression, usize), ExpressionError> {
+    let mut depth = 0;
+    let mut close = open;
+    loop {
+        match chars.get(close) {
+            Some('(') => depth += 1,
+            Some(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            Some(_) => {}
+            None => return Err(ExpressionError::at("unclosed 'roll('", open)),
+        }
+        close += 1;
+    }
+
+    let notation: String = chars[open + 1..close].iter().collect();
+    let dice = parse_dice_expression_with(&notation, limits).map_err(|error| {
+        ExpressionError::at(error.message(), open + 1 + error.position().unwrap_or(0))
+    })?;
+    Ok((dice, close + 1))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: usize,
+    max_nesting: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Symbol(s), .. }) if *s == symbol)
+    }
+
+    fn position(&self) -> usize {
+        self.peek().map(|token| token.position).unwrap_or(self.end)
+    }
+
+    fn unexpected(&self) -> ExpressionError {
+        match self.peek() {
+            Some(token) => {
+                ExpressionError::at(&format!("unexpected {}", token.describe()), token.position)
+            }
+            None => ExpressionError::at("unexpected end of expression", self.end),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), ExpressionError> {
+        if self.is_symbol(symbol) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), ExpressionError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), ExpressionError> {
+        self.depth += 1;
+        if self.depth > self.max_nesting {
+            return Err(ExpressionError::at(
+                &format!(
+                    "expression is nested more than {} levels deep",
+                    self.max_nesting
+                ),
+                self.position(),
+            ));
+        }
+        Ok(())
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn parse_expression(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        let mut left = self.parse_term()?;
+        loop {
+            let operator = if self.is_symbol('+') {
+                Operator::Add
+            } else if self.is_symbol('-') {
+                Operator::Subtract
+            } else {
+                return Ok(left);
+            };
+            let position = self.position();
+            self.index += 1;
+            let right = self.parse_term()?;
+            left = ExpressionNode::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+                position,
+            };
+        }
+    }
+
+    // term := unary (('*' | '/' | '%') unary)*
+    fn parse_term(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let operator = if self.is_symbol('*') {
+                Operator::Multiply
+            } else if self.is_symbol('/') {
+                Operator::Divide
+            } else if self.is_symbol('%') {
+                Operator::Modulo
+            } else {
+                return Ok(left);
+            };
+            let position = self.position();
+            self.index += 1;
+            let right = self.parse_unary()?;
+            left = ExpressionNode::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+                position,
+            };
+        }
+    }
+
+    // unary := ('-' | '+') unary | primary
+    fn parse_unary(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        if self.is_symbol('-') {
+            self.enter()?;
+            self.index += 1;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(ExpressionNode::Negate(Box::new(inner)));
+        }
+        if self.is_symbol('+') {
+            self.enter()?;
+            self.index += 1;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(inner);
+        }
+        self.parse_primary()
+    }
+
+    // primary := NUMBER | VARIABLE | ROLL | IDENTIFIER | IDENTIFIER '(' arguments ')' | '(' expression ')'
+    fn parse_primary(&mut self) -> Result<ExpressionNode, ExpressionError> {
+        let token = match self.peek() {
+            Some(token) => token.clone(),
+            None => return Err(self.unexpected()),
+        };
+
+        match token.kind {
+            TokenKind::Number(value) => {
+                self.index += 1;
+                Ok(ExpressionNode::Number(value))
+            }
+            TokenKind::Variable(name) => {
+                self.index += 1;
+                Ok(ExpressionNode::Variable {
+                    name,
+                    position: token.position,
+                })
+            }
+            TokenKind::Roll(dice) => {
+                self.index += 1;
+                Ok(ExpressionNode::Roll {
+                    dice,
+                    position: token.position,
+                })
+            }
+            TokenKind::Identifier(name) => {
+                self.index += 1;
+                if !self.is_symbol('(') {
+                    return Ok(ExpressionNode::Variable {
+                        name,
+                        position: token.position,
+                    });
+                }
+                let function = Function::from_name(&name).ok_or_else(|| {
+                    ExpressionError::at(&format!("unknown function '{}'", name), token.position)
+                })?;
+                self.enter()?;
+                self.index += 1;
+                let arguments = self.parse_arguments()?;
+                self.depth -= 1;
+                if !function.accepts(arguments.len()) {
+                    return Err(ExpressionError::at(
+                        &format!(
+                            "'{}' does not take {} argument(s)",
+                            function.name(),
+                            arguments.len()
+                        ),
+                        token.position,
+                    ));
+                }
+                Ok(ExpressionNode::Call {
+                    function,
+                    arguments,
+                    position: token.position,
+                })
+            }
+            TokenKind::Symbol('(') => {
+                self.enter()?;
+                self.index += 1;
+                let inner = self.parse_expression()?;
+                self.expect_symbol(')')?;
+                self.depth -= 1;
+                Ok(inner)
+            }
+            TokenKind::Symbol(_) => Err(self.unexpected()),
+        }
+    }
+
+    // arguments := (expression (',' expression)*)? ')'
+    fn parse_arguments(&mut self) -> Result<Vec<ExpressionNode>, ExpressionError> {
+        let mut arguments = vec![];
+        if self.is_symbol(')') {
+            self.index += 1;
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.parse_expression()?);
+            if self.is_symbol(',') {
+                self.index += 1;
+            } else {
+                self.expect_symbol(')')?;
+                return Ok(arguments);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::ScriptedRng;
+
+    fn context(values: &[(&str, &str)]) -> ExpressionContext {
+        let mut context = ExpressionContext::new();
+        for (key, value) in values {
+            context.set(key, value);
+        }
+        context
+    }
+
+    #[test]
+    fn test_evaluate_many_contexts() {
+        let expression = CompiledExpression::compile("4 + INT").unwrap();
+
+        for modifier in -5..=5 {
+            let context = context(&[("INT", &modifier.to_string())]);
+            assert_eq!(expression.evaluate(&context).unwrap(), 4 + modifier);
+        }
+    }
+
+    #[test]
+    fn test_variable_forms() {
+        let expression = CompiledExpression::compile("CON + $CON + ${CON}").unwrap();
+        let context = context(&[("CON", "3")]);
+
+        assert_eq!(expression.evaluate(&context).unwrap(), 9);
+        assert_eq!(
+            expression.variables(),
+            vec![("CON", 0), ("CON", 6), ("CON", 13)]
+        );
+    }
+
+    #[test]
+    fn test_integer_and_decimal_arithmetic() {
+        let empty = ExpressionContext::new();
+        let evaluate = |text: &str| CompiledExpression::compile(text).unwrap().evaluate(&empty);
+
+        assert_eq!(evaluate("2 + 3 * 4").unwrap(), 14);
+        assert_eq!(evaluate("(2 + 3) * 4").unwrap(), 20);
+        assert_eq!(evaluate("7 / 2").unwrap(), 3);
+        assert_eq!(evaluate("7 / 2.0").unwrap(), 4);
+        assert_eq!(evaluate("10 * .7").unwrap(), 7);
+        assert_eq!(evaluate("-7 % 3").unwrap(), -1);
+        assert_eq!(evaluate("--2").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_functions() {
+        let empty = ExpressionContext::new();
+        let evaluate = |text: &str| CompiledExpression::compile(text).unwrap().evaluate(&empty);
+
+        assert_eq!(evaluate("min(4, 2, 9)").unwrap(), 2);
+        assert_eq!(evaluate("max(1, 10 - 12)").unwrap(), 1);
+        assert_eq!(evaluate("floor(7 / 2.0)").unwrap(), 3);
+        assert_eq!(evaluate("ceil(7 / 2.0)").unwrap(), 4);
+        assert_eq!(evaluate("round(2.5)").unwrap(), 3);
+        assert_eq!(evaluate("abs(3 - 10)").unwrap(), 7);
+    }
+
+    #[test]
+    fn test_every_roll_is_evaluated() {
+        let expression = CompiledExpression::compile("roll(1d6) + roll(2d4) * 2 + STR").unwrap();
+        let mut rng = ScriptedRng::new(vec![5, 1, 3]);
+
+        let result = expression
+            .evaluate_with(&context(&[("STR", "2")]), &mut rng)
+            .unwrap();
+
+        assert_eq!(result, 5 + (1 + 3) * 2 + 2);
+        assert!(rng.is_empty());
+    }
+
+    #[test]
+    fn test_compile_errors_have_positions() {
+        let error = CompiledExpression::compile("10 + ").unwrap_err();
+        assert_eq!(error.position(), Some(5));
+
+        let error = CompiledExpression::compile("10 + sqrt(4)").unwrap_err();
+        assert_eq!(error.message(), "unknown function 'sqrt'");
+        assert_eq!(error.position(), Some(5));
+
+        let error = CompiledExpression::compile("2 + roll(2d6+x)").unwrap_err();
+        assert_eq!(error.position(), Some(13));
+
+        let error = CompiledExpression::compile("floor(1, 2)").unwrap_err();
+        assert_eq!(error.position(), Some(0));
+
+        let error = CompiledExpression::compile("${CON").unwrap_err();
+        assert_eq!(error.position(), Some(5));
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        let expression = CompiledExpression::compile("10 + WIS").unwrap();
+        let error = expression.evaluate(&ExpressionContext::new()).unwrap_err();
+        assert_eq!(error.message(), "unknown variable 'WIS'");
+        assert_eq!(error.position(), Some(5));
+
+        let error = expression
+            .evaluate(&context(&[("WIS", "wise")]))
+            .unwrap_err();
+        assert_eq!(error.position(), Some(5));
+
+        let error = CompiledExpression::compile("10 / (WIS - 2)")
+            .unwrap()
+            .evaluate(&context(&[("WIS", "2")]))
+            .unwrap_err();
+        assert_eq!(error.message(), "division by zero");
+        assert_eq!(error.position(), Some(3));
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let empty = ExpressionContext::new();
+        let evaluate = |text: &str| CompiledExpression::compile(text).unwrap().evaluate(&empty);
+
+        let error = evaluate("9223372036854775807 + 1").unwrap_err();
+        assert_eq!(error.message(), "9223372036854775807 + 1 is too large");
+        assert_eq!(error.position(), Some(20));
+
+        assert!(evaluate("3037000500 * 3037000500").is_err());
+        assert!(evaluate("abs(-9223372036854775807 - 1)").is_err());
+        assert_eq!(evaluate("5 % 0").unwrap_err().message(), "division by zero");
+
+        let error = evaluate("2 + roll(1d6 / 0)").unwrap_err();
+        assert!(error.message().starts_with("division by zero"));
+        assert_eq!(error.position(), Some(4));
+    }
+
+    #[test]
+    fn test_limits_are_enforced_when_compiling() {
+        let error = CompiledExpression::compile("roll(100000000d1000000)").unwrap_err();
+        assert_eq!(error.message(), "expression rolls more than 1000 dice");
+        assert_eq!(error.position(), Some(5));
+
+        let error = CompiledExpression::compile("roll(600d6) + roll(600d6)").unwrap_err();
+        assert_eq!(error.message(), "expression rolls more than 1000 dice");
+        assert_eq!(error.position(), Some(14));
+
+        let limits = EvaluationLimits {
+            max_nesting: 2,
+            ..EvaluationLimits::default()
+        };
+        assert!(CompiledExpression::compile_with("max(1, (CON))", &limits).is_ok());
+        let error = CompiledExpression::compile_with("max(1, -(CON))", &limits).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expression is nested more than 2 levels deep"
+        );
+        assert_eq!(error.position(), Some(8));
+
+        let deep = format!("{}1{}", "(".repeat(500), ")".repeat(500));
+        assert!(CompiledExpression::compile(&deep).is_err());
+        let long = "1+".repeat(600) + "1";
+        assert_eq!(
+            CompiledExpression::compile(&long).unwrap_err().position(),
+            Some(1024)
+        );
+    }
+}
@@ -0,0 +1,660 @@
+use std::collections::BTreeMap;
+
+use super::{
+    parse_dice_expression, CompiledExpression, DiceExpression, DiceGroup, DiceModifier,
+    DiceOperator, DiceRollError, DiceSelection, EvaluationLimits, ExpressionContext,
+    ExpressionError, ExpressionNode, Function, Number, MAX_EXPLOSIONS,
+};
+
+/// Exploding chains less likely than this are left out of a distribution, so a `1d100!` does
+/// not expand into every one of its hundred possible explosions.
+const NEGLIGIBLE_PROBABILITY: f64 = 1e-15;
+
+/// ## DiceDistribution
+/// The exact probability of every total a dice expression can produce, worked out without
+/// rolling.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_distribution::analyze_dice;
+///
+/// let laser_sword = analyze_dice("1d12").unwrap();
+/// let katana = analyze_dice("1d8").unwrap();
+/// assert!(laser_sword.mean() > katana.mean());
+/// let hit = analyze_dice("1d20+5").unwrap().probability_at_least(15);
+/// assert!((hit - 0.55).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiceDistribution {
+    pmf: BTreeMap<i64, f64>,
+}
+
+impl DiceDistribution {
+    /// A distribution that always produces `value`.
+    pub fn constant(value: i64) -> Self {
+        Self {
+            pmf: BTreeMap::from([(value, 1.0)]),
+        }
+    }
+
+    /// ## of
+    /// Work out the distribution of a parsed expression.  Totals are not clamped at zero, see
+    /// [`analyze_dice`] for the distribution that matches [`super::roll_dice`].
+    ///
+    /// ### Returns
+    /// The distribution, or a `DiceRollError` if the expression could divide by zero or takes
+    /// more than the default [`EvaluationLimits::max_analysis_steps`].
+    pub fn of(expression: &DiceExpression) -> Result<Self, DiceRollError> {
+        Self::of_with(expression, &EvaluationLimits::default())
+    }
+
+    /// ## of_with
+    /// The same as [`DiceDistribution::of`], but the work is limited by `limits` instead of the
+    /// default [`EvaluationLimits`].
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::dice_distribution::DiceDistribution;
+    /// use crate::logic::utilities::dice_expression::parse_dice_expression;
+    /// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+    ///
+    /// let limits = EvaluationLimits {
+    ///     max_analysis_steps: 1000,
+    ///     ..EvaluationLimits::default()
+    /// };
+    /// let expression = parse_dice_expression("3d6").unwrap();
+    /// assert!(DiceDistribution::of_with(&expression, &limits).is_ok());
+    ///
+    /// let expression = parse_dice_expression("10d20").unwrap();
+    /// let error = DiceDistribution::of_with(&expression, &limits).unwrap_err();
+    /// assert_eq!(
+    ///     error.message(),
+    ///     "working out the distribution takes more than 1000 steps"
+    /// );
+    /// ```
+    pub fn of_with(
+        expression: &DiceExpression,
+        limits: &EvaluationLimits,
+    ) -> Result<Self, DiceRollError> {
+        dice_distribution(expression, &mut Budget::new(limits))
+            .map_err(|message| DiceRollError::new(&message))
+    }
+
+    /// The probability of each possible total, in ascending order of total.
+    pub fn pmf(&self) -> &BTreeMap<i64, f64> {
+        &self.pmf
+    }
+
+    pub fn probability(&self, total: i64) -> f64 {
+        self.pmf.get(&total).copied().unwrap_or(0.0)
+    }
+
+    /// The probability that the total meets or beats a difficulty check.
+    pub fn probability_at_least(&self, difficulty_check: i64) -> f64 {
+        self.pmf.range(difficulty_check..).map(|(_, p)| p).sum()
+    }
+
+    /// The probability that the total is `value` or lower.
+    pub fn probability_at_most(&self, value: i64) -> f64 {
+        self.pmf.range(..=value).map(|(_, p)| p).sum()
+    }
+
+    pub fn min(&self) -> i64 {
+        *self.pmf.keys().next().unwrap_or(&0)
+    }
+
+    pub fn max(&self) -> i64 {
+        *self.pmf.keys().next_back().unwrap_or(&0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.pmf.iter().map(|(value, p)| *value as f64 * p).sum()
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.pmf
+            .iter()
+            .map(|(value, p)| (*value as f64 - mean).powi(2) * p)
+            .sum()
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// ## percentile
+    /// The smallest total that is reached or beaten in at least `fraction` of rolls from the
+    /// bottom, e.g. `percentile(0.5)` is the median.
+    ///
+    /// ### Arguments
+    /// * `fraction` - A value between 0.0 and 1.0.
+    pub fn percentile(&self, fraction: f64) -> i64 {
+        let mut cumulative = 0.0;
+        for (value, p) in &self.pmf {
+            cumulative += p;
+            // allow for rounding in the sums, so the 100th percentile is still the maximum.
+            if cumulative + 1e-9 >= fraction {
+                return *value;
+            }
+        }
+        self.max()
+    }
+
+    /// The same distribution with every negative total counted as zero.
+    pub fn clamped_at_zero(&self) -> Self {
+        self.map(|value| value.max(0))
+    }
+
+    fn map(&self, f: impl Fn(i64) -> i64) -> Self {
+        let mut pmf = BTreeMap::new();
+        for (value, p) in &self.pmf {
+            *pmf.entry(f(*value)).or_insert(0.0) += p;
+        }
+        Self { pmf }
+    }
+
+    /// The distribution of `f` applied to every total, or the first error `f` returns.
+    fn try_map(
+        &self,
+        budget: &mut Budget,
+        f: impl Fn(i64) -> Result<i64, String>,
+    ) -> Result<Self, String> {
+        budget.spend(self.pmf.len())?;
+        let mut pmf = BTreeMap::new();
+        for (value, p) in &self.pmf {
+            *pmf.entry(f(*value)?).or_insert(0.0) += p;
+        }
+        Ok(Self { pmf })
+    }
+
+    /// The distribution of `f` applied to every pair of totals, or the first error `f` returns.
+    fn combine(
+        &self,
+        other: &Self,
+        budget: &mut Budget,
+        f: impl Fn(i64, i64) -> Result<i64, String>,
+    ) -> Result<Self, String> {
+        budget.spend(self.pmf.len().saturating_mul(other.pmf.len()))?;
+        let mut pmf = BTreeMap::new();
+        for (left, p) in &self.pmf {
+            for (right, q) in &other.pmf {
+                *pmf.entry(f(*left, *right)?).or_insert(0.0) += p * q;
+            }
+        }
+        Ok(Self { pmf })
+    }
+}
+
+/// The steps left for working out a distribution, see
+/// [`EvaluationLimits::max_analysis_steps`].
+struct Budget {
+    limit: u64,
+    left: u64,
+}
+
+impl Budget {
+    fn new(limits: &EvaluationLimits) -> Self {
+        Self {
+            limit: limits.max_analysis_steps,
+            left: limits.max_analysis_steps,
+        }
+    }
+
+    fn spend(&mut self, steps: usize) -> Result<(), String> {
+        let steps = steps as u64;
+        if steps > self.left {
+            return Err(format!(
+                "working out the distribution takes more than {} steps",
+                self.limit
+            ));
+        }
+        self.left -= steps;
+        Ok(())
+    }
+}
+
+/// ## analyze_dice
+/// Parse a dice expression and work out the distribution of the totals that
+/// [`super::roll_dice`] would return, including clamping negative totals to zero.
+///
+/// ### Arguments
+/// * `dice_expression` - A string slice that holds the dice expression, e.g. `4d6kh3`
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_distribution::analyze_dice;
+///
+/// let distribution = analyze_dice("2d6").unwrap();
+/// assert_eq!(distribution.percentile(0.5), 7);
+/// assert!((distribution.probability(7) - 6.0 / 36.0).abs() < 1e-12);
+/// ```
+pub fn analyze_dice(dice_expression: &str) -> Result<DiceDistribution, DiceRollError> {
+    let expression = parse_dice_expression(dice_expression)?;
+    Ok(DiceDistribution::of(&expression)?.clamped_at_zero())
+}
+
+/// ## analyze_dice_with
+/// Work out the distribution of an expression with variables, e.g. `roll(1d6) + CON`, the way
+/// [`CompiledExpression::evaluate`] would resolve it against `context`.  Each `roll(...)` counts
+/// negative totals as zero, as it does when it is rolled, and the context's limits bound both
+/// the expression and the work of analyzing it.
+///
+/// ### Arguments
+/// * `expression` - A string slice that holds the expression, e.g. `roll(1d8) + STR`
+/// * `context` - The variables the expression reads, e.g. a character's ability modifiers.
+///
+/// ### Returns
+/// The distribution, or an `ExpressionError` if the expression cannot be compiled, reads a
+/// variable that is missing or not a whole number, could divide by zero or takes more than
+/// [`EvaluationLimits::max_analysis_steps`].
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_distribution::analyze_dice_with;
+/// use crate::logic::utilities::expression_parser::ExpressionContext;
+///
+/// let mut context = ExpressionContext::new();
+/// context.set("CON", "2");
+///
+/// let distribution = analyze_dice_with("roll(1d6) + CON", &context).unwrap();
+/// assert_eq!((distribution.min(), distribution.max()), (3, 8));
+/// assert!((distribution.mean() - 5.5).abs() < 1e-12);
+/// ```
+pub fn analyze_dice_with(
+    expression: &str,
+    context: &ExpressionContext,
+) -> Result<DiceDistribution, ExpressionError> {
+    let compiled = CompiledExpression::compile_with(expression, context.limits())?;
+    node_distribution(compiled.root(), context, &mut Budget::new(context.limits()))
+}
+
+fn dice_distribution(
+    expression: &DiceExpression,
+    budget: &mut Budget,
+) -> Result<DiceDistribution, String> {
+    match expression {
+        DiceExpression::Number(value) => Ok(DiceDistribution::constant(*value as i64)),
+        DiceExpression::Dice(group) => group_distribution(group, budget),
+        DiceExpression::Negate(inner) => {
+            dice_distribution(inner, budget)?.try_map(budget, |value| Ok(-value))
+        }
+        DiceExpression::Group(inner) => dice_distribution(inner, budget),
+        DiceExpression::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            let left = dice_distribution(left, budget)?;
+            let right = dice_distribution(right, budget)?;
+            if *operator == DiceOperator::Divide && right.probability(0) > 0.0 {
+                return Err(format!("'{}' can divide by zero", expression));
+            }
+            // the same checked arithmetic as rolling, so anything that overflows there is an
+            // error here too
+            left.combine(&right, budget, |l, r| {
+                let too_large = |value: i64| format!("{} is too large", value);
+                let l = i32::try_from(l).map_err(|_| too_large(l))?;
+                let r = i32::try_from(r).map_err(|_| too_large(r))?;
+                operator
+                    .apply(l, r)
+                    .map(i64::from)
+                    .map_err(|e| e.message().to_string())
+            })
+        }
+    }
+}
+
+fn node_distribution(
+    node: &ExpressionNode,
+    context: &ExpressionContext,
+    budget: &mut Budget,
+) -> Result<DiceDistribution, ExpressionError> {
+    match node {
+        ExpressionNode::Number(value) => {
+            whole(*value).map_err(|message| ExpressionError::new(&message))
+        }
+        ExpressionNode::Variable { name, position } => {
+            let value = context.get(name).ok_or_else(|| {
+                ExpressionError::at(&format!("unknown variable '{}'", name), *position)
+            })?;
+            Number::parse(value)
+                .ok_or_else(|| format!("variable '{}' is not a number ('{}')", name, value))
+                .and_then(whole)
+                .map_err(|message| ExpressionError::at(&message, *position))
+        }
+        ExpressionNode::Roll { dice, position } => dice_distribution(dice, budget)
+            .map(|distribution| distribution.clamped_at_zero())
+            .map_err(|message| ExpressionError::at(&message, *position)),
+        ExpressionNode::Call {
+            function,
+            arguments,
+            position,
+        } => {
+            let call = |values: &[i64]| {
+                let values: Vec<Number> = values.iter().map(|value| Number::Int(*value)).collect();
+                function
+                    .apply(&values)
+                    .ok_or_else(|| format!("the result of '{}' is too large", function.name()))
+                    .map(|value| value.round())
+            };
+            let distributions = arguments
+                .iter()
+                .map(|argument| node_distribution(argument, context, budget))
+                .collect::<Result<Vec<DiceDistribution>, ExpressionError>>()?;
+            // min and max take any number of arguments, the other functions exactly one
+            let (first, rest) = distributions.split_first().ok_or_else(|| {
+                ExpressionError::at(
+                    &format!("'{}' needs an argument", function.name()),
+                    *position,
+                )
+            })?;
+            let mut result = match function {
+                Function::Min | Function::Max => Ok(first.clone()),
+                _ => first.try_map(budget, |value| call(&[value])),
+            }
+            .map_err(|message| ExpressionError::at(&message, *position))?;
+            for distribution in rest {
+                result = result
+                    .combine(distribution, budget, |l, r| call(&[l, r]))
+                    .map_err(|message| ExpressionError::at(&message, *position))?;
+            }
+            Ok(result)
+        }
+        ExpressionNode::Negate(inner) => node_distribution(inner, context, budget)?
+            .try_map(budget, |value| {
+                value
+                    .checked_neg()
+                    .ok_or_else(|| format!("-({}) is too large", value))
+            })
+            .map_err(|message| ExpressionError::new(&message)),
+        ExpressionNode::Binary {
+            operator,
+            left,
+            right,
+            position,
+        } => {
+            let left = node_distribution(left, context, budget)?;
+            let right = node_distribution(right, context, budget)?;
+            left.combine(&right, budget, |l, r| {
+                operator
+                    .apply(Number::Int(l), Number::Int(r))
+                    .map(|value| value.round())
+            })
+            .map_err(|message| ExpressionError::at(&message, *position))
+        }
+    }
+}
+
+/// A number as a distribution.  Decimals are refused, since a distribution only holds whole
+/// totals and integer arithmetic would not round the way evaluating the expression does.
+fn whole(value: Number) -> Result<DiceDistribution, String> {
+    match value {
+        Number::Int(value) => Ok(DiceDistribution::constant(value)),
+        Number::Float(value) => Err(format!(
+            "'{}' is not a whole number and cannot be analyzed",
+            value
+        )),
+    }
+}
+
+fn group_distribution(group: &DiceGroup, budget: &mut Budget) -> Result<DiceDistribution, String> {
+    if group.count <= 0 || group.sides <= 0 {
+        return Ok(DiceDistribution::constant(0));
+    }
+
+    let die = die_distribution(group);
+    let score = |value: i64| match group.success_condition() {
+        Some((comparison, target)) => comparison.matches(value as i32, target) as i64,
+        None => value,
+    };
+
+    let count = group.count as usize;
+    let (keep_highest, keep) = match group.selection() {
+        Some(DiceModifier::Keep(DiceSelection::Highest, n)) => (true, n),
+        Some(DiceModifier::Keep(DiceSelection::Lowest, n)) => (false, n),
+        Some(DiceModifier::Drop(DiceSelection::Highest, n)) => (false, group.count - n),
+        Some(DiceModifier::Drop(DiceSelection::Lowest, n)) => (true, group.count - n),
+        _ => {
+            let single = die.map(score);
+            let mut total = DiceDistribution::constant(0);
+            for _ in 0..count {
+                total = total.combine(&single, budget, |l, r| Ok(l + r))?;
+            }
+            return Ok(total);
+        }
+    };
+    let keep = keep.clamp(0, group.count) as usize;
+
+    // Walk the faces from the most to the least preferred.  `states[m]` holds the distribution
+    // of the kept score once `m` dice have been given a face; the first `keep` dice placed are
+    // the ones that are kept.
+    let mut faces: Vec<(i64, f64)> = die.pmf.iter().map(|(v, p)| (*v, *p)).collect();
+    if keep_highest {
+        faces.reverse();
+    }
+
+    let binomial = binomial_table(count);
+    let mut states: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); count + 1];
+    states[0].insert(0, 1.0);
+
+    for (value, p) in faces {
+        let mut next: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); count + 1];
+        for (placed, state) in states.iter().enumerate() {
+            let remaining = count - placed;
+            budget.spend(state.len().saturating_mul(remaining + 1))?;
+            for (score_so_far, q) in state {
+                for showing in 0..=remaining {
+                    let weight = binomial[remaining][showing] * p.powi(showing as i32);
+                    if weight == 0.0 {
+                        continue;
+                    }
+                    let kept = showing.min(keep.saturating_sub(placed)) as i64;
+                    *next[placed + showing]
+                        .entry(score_so_far + kept * score(value))
+                        .or_insert(0.0) += q * weight;
+                }
+            }
+        }
+        states = next;
+    }
+
+    Ok(DiceDistribution {
+        pmf: states.swap_remove(count),
+    })
+}
+
+/// The value of a single die after rerolls and explosions, before keep/drop.
+fn die_distribution(group: &DiceGroup) -> DiceDistribution {
+    let sides = group.sides as i64;
+    let lowest = group.reroll_threshold().unwrap_or(0).max(0) as i64 + 1;
+    let first = 1.0 / (sides - lowest + 1) as f64;
+    let each = 1.0 / sides as f64;
+
+    let mut pmf = BTreeMap::new();
+    for face in lowest..=sides {
+        if face < sides || !group.explodes() {
+            *pmf.entry(face).or_insert(0.0) += first;
+            continue;
+        }
+
+        // a maximum face keeps rolling until it stops on something lower or hits the cap.
+        let mut total = sides;
+        let mut chance = first;
+        let mut faces = 1;
+        while faces <= MAX_EXPLOSIONS && chance * each >= NEGLIGIBLE_PROBABILITY {
+            for next in 1..sides {
+                *pmf.entry(total + next).or_insert(0.0) += chance * each;
+            }
+            total += sides;
+            chance *= each;
+            faces += 1;
+        }
+        *pmf.entry(total).or_insert(0.0) += chance;
+    }
+
+    DiceDistribution { pmf }
+}
+
+fn binomial_table(n: usize) -> Vec<Vec<f64>> {
+    let mut table = vec![vec![0.0; n + 1]; n + 1];
+    for row in 0..=n {
+        table[row][0] = 1.0;
+        for column in 1..=row {
+            table[row][column] = table[row - 1][column - 1] + table[row - 1][column];
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_single_die() {
+        let distribution = analyze_dice("1d12").unwrap();
+
+        assert_eq!(distribution.pmf().len(), 12);
+        assert_close(distribution.probability(1), 1.0 / 12.0);
+        assert_close(distribution.mean(), 6.5);
+        assert_close(distribution.variance(), 143.0 / 12.0);
+        assert_eq!((distribution.min(), distribution.max()), (1, 12));
+    }
+
+    #[test]
+    fn test_sum_of_dice() {
+        let distribution = analyze_dice("2d6").unwrap();
+
+        assert_close(distribution.probability(7), 6.0 / 36.0);
+        assert_close(distribution.probability(2), 1.0 / 36.0);
+        assert_close(distribution.pmf().values().sum(), 1.0);
+        assert_eq!(distribution.percentile(0.0), 2);
+        assert_eq!(distribution.percentile(0.5), 7);
+        assert_eq!(distribution.percentile(1.0), 12);
+    }
+
+    #[test]
+    fn test_probability_against_dc() {
+        let distribution = analyze_dice("1d20+5").unwrap();
+
+        assert_close(distribution.probability_at_least(15), 11.0 / 20.0);
+        assert_close(distribution.probability_at_least(6), 1.0);
+        assert_close(distribution.probability_at_least(26), 0.0);
+        assert_close(distribution.probability_at_most(10), 5.0 / 20.0);
+    }
+
+    #[test]
+    fn test_clamped_at_zero() {
+        let distribution = analyze_dice("1d4-5").unwrap();
+        assert_close(distribution.probability(0), 1.0);
+
+        let raw = DiceDistribution::of(&parse_dice_expression("1d4-5").unwrap()).unwrap();
+        assert_close(raw.mean(), -2.5);
+    }
+
+    #[test]
+    fn test_keep_highest() {
+        let distribution = analyze_dice("4d6kh3").unwrap();
+
+        assert_close(distribution.mean(), 15869.0 / 1296.0);
+        assert_close(distribution.probability(18), 21.0 / 1296.0);
+        assert_close(distribution.probability(3), 1.0 / 1296.0);
+        assert_eq!(analyze_dice("4d6dl1").unwrap(), distribution);
+    }
+
+    #[test]
+    fn test_keep_lowest() {
+        let distribution = analyze_dice("2d20kl1").unwrap();
+
+        assert_close(distribution.probability(20), 1.0 / 400.0);
+        assert_close(distribution.probability(1), 39.0 / 400.0);
+    }
+
+    #[test]
+    fn test_reroll_and_explode() {
+        assert_close(analyze_dice("2d10r1").unwrap().mean(), 12.0);
+
+        let exploding = analyze_dice("1d6!").unwrap();
+        assert_close(exploding.mean(), 4.2);
+        assert_close(exploding.probability(6), 0.0);
+        assert_close(exploding.probability(8), 1.0 / 36.0);
+    }
+
+    #[test]
+    fn test_success_counting() {
+        let distribution = analyze_dice("6d10>=7").unwrap();
+
+        assert_close(distribution.mean(), 2.4);
+        assert_close(distribution.variance(), 6.0 * 0.4 * 0.6);
+        assert_close(distribution.probability(6), 0.4_f64.powi(6));
+    }
+
+    #[test]
+    fn test_arithmetic_between_dice() {
+        let distribution = analyze_dice("(1d4+1)*2").unwrap();
+        assert_eq!(
+            distribution.pmf().keys().copied().collect::<Vec<i64>>(),
+            vec![4, 6, 8, 10]
+        );
+
+        assert!(analyze_dice("10/(1d4-1)").is_err());
+
+        let error = analyze_dice("2147483647*2147483647*2147483647").unwrap_err();
+        assert_eq!(error.message(), "2147483647*2147483647 is too large");
+        assert!(analyze_dice("1d6*2147483647").is_err());
+    }
+
+    #[test]
+    fn test_expression_with_variables() {
+        let mut context = ExpressionContext::new();
+        context.set("CON", "2");
+        context.set("STR", "-3");
+
+        let damage = analyze_dice_with("roll(1d6) + CON", &context).unwrap();
+        assert_eq!((damage.min(), damage.max()), (3, 8));
+        assert_close(damage.probability(5), 1.0 / 6.0);
+
+        let weak = analyze_dice_with("max(1, roll(1d4) + STR)", &context).unwrap();
+        assert_close(weak.probability(1), 1.0);
+
+        let error = analyze_dice_with("roll(1d6) + DEX", &context).unwrap_err();
+        assert_eq!(error.message(), "unknown variable 'DEX'");
+        assert_eq!(error.position(), Some(12));
+
+        let error = analyze_dice_with("roll(1d6) * 1.5", &context).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "'1.5' is not a whole number and cannot be analyzed"
+        );
+
+        let error = analyze_dice_with("10 / (roll(1d4) - 1)", &context).unwrap_err();
+        assert_eq!(error.message(), "division by zero");
+    }
+
+    #[test]
+    fn test_analysis_is_limited() {
+        assert!(analyze_dice("100d6").is_ok());
+        let error = analyze_dice("1000d1000").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "working out the distribution takes more than 1000000 steps"
+        );
+
+        let context = ExpressionContext::new().with_limits(EvaluationLimits {
+            max_analysis_steps: 100,
+            ..EvaluationLimits::default()
+        });
+        assert!(analyze_dice_with("roll(1d20) + 5", &context).is_ok());
+        assert!(analyze_dice_with("roll(4d6kh3)", &context).is_err());
+    }
+}
@@ -0,0 +1,852 @@
+use std::fmt;
+
+use super::{
+    Comparison, DiceGroup, DiceModifier, DiceRng, DiceRollError, DiceSelection, EvaluationLimits,
+};
+
+/// ## DiceOperator
+/// The arithmetic operators that can join two terms of a dice expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl DiceOperator {
+    pub fn symbol(&self) -> char {
+        match self {
+            DiceOperator::Add => '+',
+            DiceOperator::Subtract => '-',
+            DiceOperator::Multiply => '*',
+            DiceOperator::Divide => '/',
+        }
+    }
+
+    /// Apply the operator, failing on division by zero or a result that does not fit in an
+    /// `i32`.
+    pub(crate) fn apply(&self, left: i32, right: i32) -> Result<i32, DiceRollError> {
+        let result = match self {
+            DiceOperator::Add => left.checked_add(right),
+            DiceOperator::Subtract => left.checked_sub(right),
+            DiceOperator::Multiply => left.checked_mul(right),
+            DiceOperator::Divide if right == 0 => {
+                return Err(DiceRollError::new(&format!(
+                    "division by zero in {}/0",
+                    left
+                )))
+            }
+            DiceOperator::Divide => left.checked_div(right),
+        };
+        result.ok_or_else(|| {
+            DiceRollError::new(&format!("{}{}{} is too large", left, self.symbol(), right))
+        })
+    }
+}
+
+/// ## DiceExpression
+/// A parsed dice expression such as `2d6+1d4+3`, `4d6kh3` or `(1d8+2)*2`.
+///
+/// Expressions are built by [`parse_dice_expression`] and can be rolled as many times as needed
+/// without parsing the text again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiceExpression {
+    Number(i32),
+    Dice(DiceGroup),
+    Negate(Box<DiceExpression>),
+    Group(Box<DiceExpression>),
+    Binary {
+        operator: DiceOperator,
+        left: Box<DiceExpression>,
+        right: Box<DiceExpression>,
+    },
+}
+
+impl DiceExpression {
+    /// ## dice_count
+    /// The number of dice the expression rolls, counted across all of its dice groups and
+    /// before any reroll or explosion.
+    pub fn dice_count(&self) -> i64 {
+        match self {
+            DiceExpression::Number(_) => 0,
+            DiceExpression::Dice(group) => group.count.max(0) as i64,
+            DiceExpression::Negate(inner) | DiceExpression::Group(inner) => inner.dice_count(),
+            DiceExpression::Binary { left, right, .. } => left.dice_count() + right.dice_count(),
+        }
+    }
+
+    /// Roll every dice group in the expression and return the arithmetic total.
+    /// A dice group with no dice or no sides, such as `0d6` or `1d0`, rolls 0.
+    /// Dividing by zero, or a total that does not fit in an `i32`, is an error.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::dice_expression::parse_dice_expression;
+    /// let expression = parse_dice_expression("2d6+1d4+3").unwrap();
+    /// let total = expression.roll(&mut rand::thread_rng()).unwrap();
+    /// assert!((6..=19).contains(&total));
+    /// ```
+    pub fn roll<R: DiceRng + ?Sized>(&self, rng: &mut R) -> Result<i32, DiceRollError> {
+        match self {
+            DiceExpression::Number(value) => Ok(*value),
+            DiceExpression::Dice(group) => Ok(group.roll(rng)?.total),
+            DiceExpression::Negate(inner) => {
+                let value = inner.roll(rng)?;
+                value
+                    .checked_neg()
+                    .ok_or_else(|| DiceRollError::new(&format!("-({}) is too large", value)))
+            }
+            DiceExpression::Group(inner) => inner.roll(rng),
+            DiceExpression::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let left = left.roll(rng)?;
+                let right = right.roll(rng)?;
+                operator.apply(left, right)
+            }
+        }
+    }
+
+    /// ## modifier
+    /// The part of the expression that follows its leading dice group, e.g. `+5-2` for
+    /// `1d20+5-2`.  Returns `+0` when there is nothing after the dice, and the whole expression
+    /// when it does not start with a dice group.
+    pub fn modifier(&self) -> String {
+        let notation = self.to_string();
+        let modifier = match self.leftmost() {
+            DiceExpression::Dice(_) => notation[self.leftmost().to_string().len()..].to_string(),
+            _ => notation,
+        };
+
+        if modifier.is_empty() {
+            "+0".to_string()
+        } else {
+            modifier
+        }
+    }
+
+    fn leftmost(&self) -> &DiceExpression {
+        match self {
+            DiceExpression::Binary { left, .. } => left.leftmost(),
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for DiceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiceExpression::Number(value) => write!(f, "{}", value),
+            DiceExpression::Dice(group) => write!(f, "{}", group),
+            DiceExpression::Negate(inner) => write!(f, "-{}", inner),
+            DiceExpression::Group(inner) => write!(f, "({})", inner),
+            DiceExpression::Binary {
+                operator,
+                left,
+                right,
+            } => write!(f, "{}{}{}", left, operator.symbol(), right),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(i32),
+    Word(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match &self.kind {
+            TokenKind::Number(value) => format!("'{}'", value),
+            TokenKind::Word(word) => format!("'{}'", word),
+            TokenKind::Symbol(symbol) => format!("'{}'", symbol),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, DiceRollError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let start = index;
+
+        if c.is_whitespace() {
+            index += 1;
+        } else if c.is_ascii_digit() {
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            let text: String = chars[start..index].iter().collect();
+            let value = text.parse::<i32>().map_err(|_| {
+                DiceRollError::at(&format!("number '{}' is too large", text), start)
+            })?;
+            tokens.push(Token {
+                kind: TokenKind::Number(value),
+                position: start,
+            });
+        } else if c.is_ascii_alphabetic() {
+            while index < chars.len() && chars[index].is_ascii_alphabetic() {
+                index += 1;
+            }
+            let word: String = chars[start..index].iter().collect();
+            tokens.push(Token {
+                kind: TokenKind::Word(word.to_ascii_lowercase()),
+                position: start,
+            });
+        } else if "+-*/()!<>=".contains(c) {
+            index += 1;
+            tokens.push(Token {
+                kind: TokenKind::Symbol(c),
+                position: start,
+            });
+        } else {
+            return Err(DiceRollError::at(
+                &format!("unexpected character '{}'", c),
+                start,
+            ));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: usize,
+    limits: EvaluationLimits,
+    depth: usize,
+    dice: i64,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|token| &token.kind)
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek_kind() == Some(&TokenKind::Symbol(symbol))
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek_kind(), Some(TokenKind::Word(w)) if w == word)
+    }
+
+    fn unexpected(&self) -> DiceRollError {
+        match self.peek() {
+            Some(token) => {
+                DiceRollError::at(&format!("unexpected {}", token.describe()), token.position)
+            }
+            None => DiceRollError::at("unexpected end of expression", self.end),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), DiceRollError> {
+        if self.is_symbol(symbol) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), DiceRollError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), DiceRollError> {
+        self.depth += 1;
+        if self.depth > self.limits.max_nesting {
+            return Err(DiceRollError::at(
+                &format!(
+                    "expression is nested more than {} levels deep",
+                    self.limits.max_nesting
+                ),
+                self.peek().map(|token| token.position).unwrap_or(self.end),
+            ));
+        }
+        Ok(())
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn parse_expression(&mut self) -> Result<DiceExpression, DiceRollError> {
+        let mut left = self.parse_term()?;
+        loop {
+            let operator = if self.is_symbol('+') {
+                DiceOperator::Add
+            } else if self.is_symbol('-') {
+                DiceOperator::Subtract
+            } else {
+                return Ok(left);
+            };
+            self.index += 1;
+            let right = self.parse_term()?;
+            left = DiceExpression::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn parse_term(&mut self) -> Result<DiceExpression, DiceRollError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let operator = if self.is_symbol('*') {
+                DiceOperator::Multiply
+            } else if self.is_symbol('/') {
+                DiceOperator::Divide
+            } else {
+                return Ok(left);
+            };
+            self.index += 1;
+            let right = self.parse_unary()?;
+            left = DiceExpression::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    // unary := '-' unary | primary
+    fn parse_unary(&mut self) -> Result<DiceExpression, DiceRollError> {
+        if self.is_symbol('-') {
+            self.enter()?;
+            self.index += 1;
+            let inner = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(DiceExpression::Negate(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    // primary := NUMBER | NUMBER? 'd' NUMBER modifier* | '(' expression ')'
+    fn parse_primary(&mut self) -> Result<DiceExpression, DiceRollError> {
+        if self.is_symbol('(') {
+            self.enter()?;
+            self.index += 1;
+            let inner = self.parse_expression()?;
+            self.expect_symbol(')')?;
+            self.depth -= 1;
+            return Ok(DiceExpression::Group(Box::new(inner)));
+        }
+
+        let position = self.peek().map(|token| token.position).unwrap_or(self.end);
+        if self.is_word("d") {
+            self.index += 1;
+            return self.parse_dice(1, position);
+        }
+
+        match self.peek_kind() {
+            Some(TokenKind::Number(value)) => {
+                let value = *value;
+                self.index += 1;
+                if self.is_word("d") {
+                    self.index += 1;
+                    self.parse_dice(value, position)
+                } else {
+                    Ok(DiceExpression::Number(value))
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_dice(&mut self, count: i32, position: usize) -> Result<DiceExpression, DiceRollError> {
+        self.dice += count as i64;
+        if self.dice > self.limits.max_dice {
+            return Err(DiceRollError::at(
+                &format!("expression rolls more than {} dice", self.limits.max_dice),
+                position,
+            ));
+        }
+
+        let sides_position = self.peek().map(|token| token.position).unwrap_or(self.end);
+        let sides = self.expect_number()?;
+        if sides > self.limits.max_sides {
+            return Err(DiceRollError::at(
+                &format!(
+                    "a die cannot have more than {} sides",
+                    self.limits.max_sides
+                ),
+                sides_position,
+            ));
+        }
+        let mut group = DiceGroup::new(count, sides);
+
+        while let Some(position) = self.peek().map(|token| token.position) {
+            let modifier = match self.parse_modifier()? {
+                Some(modifier) => modifier,
+                None => break,
+            };
+            check_modifier(&group, &modifier, position)?;
+            group.modifiers.push(modifier);
+        }
+
+        Ok(DiceExpression::Dice(group))
+    }
+
+    // modifier := ('kh' | 'k' | 'kl' | 'dh' | 'dl' | 'r') NUMBER | '!' | ('>=' | '>' | '<=' | '<' | '=') NUMBER
+    fn parse_modifier(&mut self) -> Result<Option<DiceModifier>, DiceRollError> {
+        let kind = match self.peek_kind() {
+            Some(kind) => kind.clone(),
+            None => return Ok(None),
+        };
+
+        let modifier = match kind {
+            TokenKind::Word(word) => {
+                let build: fn(i32) -> DiceModifier = match word.as_str() {
+                    "kh" | "k" => |n| DiceModifier::Keep(DiceSelection::Highest, n),
+                    "kl" => |n| DiceModifier::Keep(DiceSelection::Lowest, n),
+                    "dh" => |n| DiceModifier::Drop(DiceSelection::Highest, n),
+                    "dl" => |n| DiceModifier::Drop(DiceSelection::Lowest, n),
+                    "r" => DiceModifier::RerollBelow,
+                    _ => return Ok(None),
+                };
+                self.index += 1;
+                build(self.expect_number()?)
+            }
+            TokenKind::Symbol('!') => {
+                self.index += 1;
+                DiceModifier::Explode
+            }
+            TokenKind::Symbol(symbol @ ('<' | '>' | '=')) => {
+                self.index += 1;
+                let or_equal = symbol != '=' && self.is_symbol('=');
+                if or_equal {
+                    self.index += 1;
+                }
+                let comparison = match (symbol, or_equal) {
+                    ('>', true) => Comparison::GreaterOrEqual,
+                    ('>', false) => Comparison::Greater,
+                    ('<', true) => Comparison::LessOrEqual,
+                    ('<', false) => Comparison::Less,
+                    _ => Comparison::Equal,
+                };
+                DiceModifier::CountSuccesses(comparison, self.expect_number()?)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(modifier))
+    }
+
+    fn expect_number(&mut self) -> Result<i32, DiceRollError> {
+        match self.peek_kind() {
+            Some(TokenKind::Number(value)) => {
+                let value = *value;
+                self.index += 1;
+                Ok(value)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+fn check_modifier(
+    group: &DiceGroup,
+    modifier: &DiceModifier,
+    position: usize,
+) -> Result<(), DiceRollError> {
+    let same_kind = group.modifiers.iter().any(|existing| {
+        matches!(
+            (existing, modifier),
+            (
+                DiceModifier::Keep(..) | DiceModifier::Drop(..),
+                DiceModifier::Keep(..) | DiceModifier::Drop(..)
+            ) | (DiceModifier::Explode, DiceModifier::Explode)
+                | (DiceModifier::RerollBelow(_), DiceModifier::RerollBelow(_))
+                | (
+                    DiceModifier::CountSuccesses(..),
+                    DiceModifier::CountSuccesses(..)
+                )
+        )
+    });
+    if same_kind {
+        return Err(DiceRollError::at(
+            &format!(
+                "'{}' repeats a modifier already applied to {}",
+                modifier, group
+            ),
+            position,
+        ));
+    }
+
+    match modifier {
+        DiceModifier::Explode if group.sides < 2 => Err(DiceRollError::at(
+            &format!("a d{} cannot explode", group.sides),
+            position,
+        )),
+        DiceModifier::RerollBelow(threshold) if *threshold >= group.sides => {
+            Err(DiceRollError::at(
+                &format!(
+                    "'{}' would reroll every face of a d{}",
+                    modifier, group.sides
+                ),
+                position,
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ## parse_dice_expression
+/// Parse a dice expression into a [`DiceExpression`] that can be rolled many times.
+///
+/// The expression may be wrapped in `roll(...)`, and supports any number of dice groups (`NdM`,
+/// or `dM` for a single die), integer constants, `+ - * /` with the usual precedence,
+/// parentheses and unary minus.  Whitespace is ignored.
+///
+/// Each dice group may be followed by modifiers, see [`DiceModifier`]:
+/// keep/drop (`4d6kh3`, `4d6dl1`), exploding (`1d6!`), reroll (`2d10r1`) and success counting
+/// (`6d10>=7`).  A group accepts at most one modifier of each kind.
+///
+/// ### Arguments
+/// * `dice_expression` - A string slice that holds the dice expression, e.g. `roll(2d6+1d4+3)`
+///
+/// ### Returns
+/// The parsed expression, or a `DiceRollError` that carries the position of the offending
+/// character.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_expression::parse_dice_expression;
+/// let expression = parse_dice_expression("roll(1d20 + 5 - 2)").unwrap();
+/// assert_eq!(expression.to_string(), "1d20+5-2");
+///
+/// let error = parse_dice_expression("1d8+1d6 fire").unwrap_err();
+/// assert_eq!(error.position(), Some(8));
+/// ```
+pub fn parse_dice_expression(dice_expression: &str) -> Result<DiceExpression, DiceRollError> {
+    parse_dice_expression_with(dice_expression, &EvaluationLimits::default())
+}
+
+/// ## parse_dice_expression_with
+/// Parse a dice expression, as [`parse_dice_expression`] does, rejecting any expression that
+/// exceeds the given limits: too long, nested too deeply, too many dice or dice with too many
+/// sides.
+///
+/// ### Arguments
+/// * `dice_expression` - A string slice that holds the dice expression, e.g. `roll(2d6+1d4+3)`
+/// * `limits` - The limits the expression must stay within.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_expression::parse_dice_expression_with;
+/// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+///
+/// let error = parse_dice_expression_with("1d20+1d10000", &EvaluationLimits::default())
+///     .unwrap_err();
+/// assert_eq!(error.message(), "a die cannot have more than 1000 sides");
+/// assert_eq!(error.position(), Some(7));
+/// ```
+pub fn parse_dice_expression_with(
+    dice_expression: &str,
+    limits: &EvaluationLimits,
+) -> Result<DiceExpression, DiceRollError> {
+    let end = dice_expression.chars().count();
+    if end > limits.max_expression_length {
+        return Err(DiceRollError::at(
+            &format!(
+                "expression is longer than {} characters",
+                limits.max_expression_length
+            ),
+            limits.max_expression_length,
+        ));
+    }
+
+    let tokens = tokenize(dice_expression)?;
+    if tokens.is_empty() {
+        return Err(DiceRollError::at("empty dice expression", 0));
+    }
+
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end,
+        limits: *limits,
+        depth: 0,
+        dice: 0,
+    };
+
+    let wrapped = parser.is_word("roll")
+        && parser.tokens.get(1).map(|token| &token.kind) == Some(&TokenKind::Symbol('('));
+    if wrapped {
+        parser.index = 2;
+    }
+
+    let expression = parser.parse_expression()?;
+
+    if wrapped {
+        parser.expect_symbol(')')?;
+    }
+    parser.expect_end()?;
+
+    Ok(expression)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dice(count: i32, sides: i32) -> Box<DiceExpression> {
+        Box::new(DiceExpression::Dice(DiceGroup::new(count, sides)))
+    }
+
+    #[test]
+    fn test_parse_single_dice_group() {
+        let expression = parse_dice_expression("roll(2d6)").unwrap();
+        assert_eq!(expression, *dice(2, 6));
+    }
+
+    #[test]
+    fn test_parse_implicit_single_die() {
+        let expression = parse_dice_expression("d20").unwrap();
+        assert_eq!(expression, *dice(1, 20));
+    }
+
+    #[test]
+    fn test_parse_multiple_dice_groups() {
+        let expression = parse_dice_expression("roll(2d6+1d4+3)").unwrap();
+        assert_eq!(
+            expression,
+            DiceExpression::Binary {
+                operator: DiceOperator::Add,
+                left: Box::new(DiceExpression::Binary {
+                    operator: DiceOperator::Add,
+                    left: dice(2, 6),
+                    right: dice(1, 4),
+                }),
+                right: Box::new(DiceExpression::Number(3)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_respects_precedence() {
+        let expression = parse_dice_expression("2 + 3 * 4").unwrap();
+        assert_eq!(expression.roll(&mut rand::thread_rng()).unwrap(), 14);
+    }
+
+    #[test]
+    fn test_parse_parentheses_and_unary_minus() {
+        let expression = parse_dice_expression("-(2 + 3) * -(1 - 4)").unwrap();
+        assert_eq!(expression.roll(&mut rand::thread_rng()).unwrap(), -15);
+        assert_eq!(expression.to_string(), "-(2+3)*-(1-4)");
+    }
+
+    #[test]
+    fn test_parse_ignores_whitespace() {
+        let expression = parse_dice_expression(" roll( 1d20 + 5 - 2 ) ").unwrap();
+        assert_eq!(expression.to_string(), "1d20+5-2");
+        assert_eq!(expression.modifier(), "+5-2");
+    }
+
+    #[test]
+    fn test_modifier_without_trailing_terms() {
+        let expression = parse_dice_expression("3d8").unwrap();
+        assert_eq!(expression.modifier(), "+0");
+    }
+
+    #[test]
+    fn test_error_points_at_trailing_word() {
+        let error = parse_dice_expression("1d8+1d6 fire").unwrap_err();
+        assert_eq!(error.position(), Some(8));
+        assert_eq!(error.message(), "unexpected 'fire'");
+    }
+
+    #[test]
+    fn test_error_points_at_missing_operand() {
+        let error = parse_dice_expression("roll(2d6+5-)").unwrap_err();
+        assert_eq!(error.position(), Some(11));
+    }
+
+    #[test]
+    fn test_error_on_unbalanced_parentheses() {
+        let error = parse_dice_expression("(1d6+2").unwrap_err();
+        assert_eq!(error.position(), Some(6));
+        assert_eq!(error.message(), "unexpected end of expression");
+    }
+
+    #[test]
+    fn test_error_on_missing_sides() {
+        let error = parse_dice_expression("2d+1").unwrap_err();
+        assert_eq!(error.position(), Some(2));
+    }
+
+    #[test]
+    fn test_error_on_unknown_character() {
+        let error = parse_dice_expression("2d6 % 3").unwrap_err();
+        assert_eq!(error.position(), Some(4));
+    }
+
+    #[test]
+    fn test_parse_dice_modifiers() {
+        let expression = parse_dice_expression("4d6kh3").unwrap();
+        assert_eq!(
+            expression,
+            DiceExpression::Dice(DiceGroup {
+                count: 4,
+                sides: 6,
+                modifiers: vec![DiceModifier::Keep(DiceSelection::Highest, 3)],
+            })
+        );
+
+        let expression = parse_dice_expression("roll(6d10r1!>=7 + 2)").unwrap();
+        assert_eq!(expression.to_string(), "6d10r1!>=7+2");
+        assert_eq!(expression.modifier(), "+2");
+
+        for notation in [
+            "4d6dl1", "4d6kl1", "4d6dh1", "1d6!", "2d10r1", "6d10<3", "6d10=10",
+        ] {
+            assert_eq!(
+                parse_dice_expression(notation).unwrap().to_string(),
+                notation
+            );
+        }
+        assert_eq!(
+            parse_dice_expression("4d6k3").unwrap().to_string(),
+            "4d6kh3"
+        );
+    }
+
+    #[test]
+    fn test_roll_with_dice_modifiers() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let keep = parse_dice_expression("4d6kh3")
+                .unwrap()
+                .roll(&mut rng)
+                .unwrap();
+            assert!((3..=18).contains(&keep));
+
+            let successes = parse_dice_expression("6d10>=7")
+                .unwrap()
+                .roll(&mut rng)
+                .unwrap();
+            assert!((0..=6).contains(&successes));
+
+            let reroll = parse_dice_expression("2d10r1")
+                .unwrap()
+                .roll(&mut rng)
+                .unwrap();
+            assert!((4..=20).contains(&reroll));
+
+            let explode = parse_dice_expression("1d6!")
+                .unwrap()
+                .roll(&mut rng)
+                .unwrap();
+            assert!(explode >= 1 && explode % 6 != 0);
+        }
+    }
+
+    #[test]
+    fn test_error_on_repeated_modifier() {
+        let error = parse_dice_expression("4d6kh3dl1").unwrap_err();
+        assert_eq!(error.position(), Some(6));
+
+        let error = parse_dice_expression("1d6!!").unwrap_err();
+        assert_eq!(error.position(), Some(4));
+    }
+
+    #[test]
+    fn test_error_on_invalid_modifier() {
+        let error = parse_dice_expression("1d6r6").unwrap_err();
+        assert_eq!(error.position(), Some(3));
+        assert_eq!(error.message(), "'r6' would reroll every face of a d6");
+
+        let error = parse_dice_expression("2d1!").unwrap_err();
+        assert_eq!(error.position(), Some(3));
+
+        let error = parse_dice_expression("4d6kh").unwrap_err();
+        assert_eq!(error.message(), "unexpected end of expression");
+    }
+
+    #[test]
+    fn test_error_on_empty_expression() {
+        assert!(parse_dice_expression("   ").is_err());
+    }
+
+    #[test]
+    fn test_limits_reject_too_many_dice_and_sides() {
+        let error = parse_dice_expression("roll(100000000d1000000)").unwrap_err();
+        assert_eq!(error.message(), "expression rolls more than 1000 dice");
+        assert_eq!(error.position(), Some(5));
+
+        let error = parse_dice_expression("600d6+600d6").unwrap_err();
+        assert_eq!(error.position(), Some(6));
+
+        let error = parse_dice_expression("1d1000000").unwrap_err();
+        assert_eq!(error.message(), "a die cannot have more than 1000 sides");
+        assert_eq!(error.position(), Some(2));
+    }
+
+    #[test]
+    fn test_limits_reject_long_and_deeply_nested_expressions() {
+        let limits = EvaluationLimits {
+            max_nesting: 3,
+            max_expression_length: 20,
+            ..EvaluationLimits::default()
+        };
+
+        assert!(parse_dice_expression_with("((-1d6))", &limits).is_ok());
+        let error = parse_dice_expression_with("((-(1d6)))", &limits).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expression is nested more than 3 levels deep"
+        );
+        assert_eq!(error.position(), Some(3));
+
+        let error = parse_dice_expression_with("1+1+1+1+1+1+1+1+1+1+1", &limits).unwrap_err();
+        assert_eq!(error.position(), Some(20));
+
+        let deep = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        assert!(parse_dice_expression(&deep).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_errors_instead_of_panicking() {
+        let mut rng = rand::thread_rng();
+
+        let error = parse_dice_expression("1d6/0")
+            .unwrap()
+            .roll(&mut rng)
+            .unwrap_err();
+        assert!(error.message().starts_with("division by zero"));
+
+        let error = parse_dice_expression("2147483647+1")
+            .unwrap()
+            .roll(&mut rng)
+            .unwrap_err();
+        assert_eq!(error.message(), "2147483647+1 is too large");
+
+        let error = parse_dice_expression("-(-2147483647-1)")
+            .unwrap()
+            .roll(&mut rng)
+            .unwrap_err();
+        assert!(error.message().contains("too large"));
+    }
+}
@@ -0,0 +1,389 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{DiceRng, DiceRollError};
+
+/// The most times a single exploding die is re-rolled before it stops exploding.
+pub const MAX_EXPLOSIONS: usize = 100;
+
+/// ## DiceSelection
+/// Which end of a sorted dice pool a keep or drop modifier works from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceSelection {
+    Highest,
+    Lowest,
+}
+
+/// ## Comparison
+/// How a die is compared against the target number of a success-counting modifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    pub fn matches(&self, value: i32, target: i32) -> bool {
+        match self {
+            Comparison::Equal => value == target,
+            Comparison::Greater => value > target,
+            Comparison::GreaterOrEqual => value >= target,
+            Comparison::Less => value < target,
+            Comparison::LessOrEqual => value <= target,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Equal => "=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+        }
+    }
+}
+
+/// ## DiceModifier
+/// A modifier written after a dice group, such as the `kh3` in `4d6kh3`.
+///
+/// * `Keep` - `khN` / `kN` keeps the N highest dice, `klN` keeps the N lowest.
+/// * `Drop` - `dhN` drops the N highest dice, `dlN` drops the N lowest.
+/// * `Explode` - `!` rolls a die again every time it shows its maximum and adds the new face to
+///   that die.
+/// * `RerollBelow` - `rN` rerolls a die for as long as it shows N or lower.
+/// * `CountSuccesses` - `>=N`, `>N`, `<=N`, `<N` or `=N` makes the group count the dice that
+///   match instead of summing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceModifier {
+    Keep(DiceSelection, i32),
+    Drop(DiceSelection, i32),
+    Explode,
+    RerollBelow(i32),
+    CountSuccesses(Comparison, i32),
+}
+
+impl fmt::Display for DiceModifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiceModifier::Keep(DiceSelection::Highest, n) => write!(f, "kh{}", n),
+            DiceModifier::Keep(DiceSelection::Lowest, n) => write!(f, "kl{}", n),
+            DiceModifier::Drop(DiceSelection::Highest, n) => write!(f, "dh{}", n),
+            DiceModifier::Drop(DiceSelection::Lowest, n) => write!(f, "dl{}", n),
+            DiceModifier::Explode => write!(f, "!"),
+            DiceModifier::RerollBelow(n) => write!(f, "r{}", n),
+            DiceModifier::CountSuccesses(comparison, n) => {
+                write!(f, "{}{}", comparison.symbol(), n)
+            }
+        }
+    }
+}
+
+/// ## DiceGroup
+/// A number of dice of the same size, e.g. `4d6kh3`, together with its modifiers.
+///
+/// Modifiers are always applied in the same order no matter how they are written:
+/// rerolls first, then explosions, then keep/drop, and finally success counting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceGroup {
+    pub count: i32,
+    pub sides: i32,
+    pub modifiers: Vec<DiceModifier>,
+}
+
+/// ## DieRoll
+/// The outcome of a single die in a group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieRoll {
+    /// The faces that count towards this die: the first face plus any explosions.
+    pub faces: Vec<i32>,
+    /// Faces that were rolled and then thrown away by a reroll modifier.
+    pub rerolled: Vec<i32>,
+    /// Whether a keep or drop modifier removed this die from the total.
+    pub dropped: bool,
+}
+
+impl DieRoll {
+    pub fn value(&self) -> i32 {
+        self.faces.iter().sum()
+    }
+}
+
+/// ## DiceGroupRoll
+/// Every die rolled for a [`DiceGroup`], and the value the group contributed to the expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceGroupRoll {
+    pub group: DiceGroup,
+    pub dice: Vec<DieRoll>,
+    pub total: i32,
+}
+
+impl DiceGroup {
+    pub fn new(count: i32, sides: i32) -> Self {
+        Self {
+            count,
+            sides,
+            modifiers: vec![],
+        }
+    }
+
+    pub fn explodes(&self) -> bool {
+        self.modifiers.contains(&DiceModifier::Explode)
+    }
+
+    pub fn reroll_threshold(&self) -> Option<i32> {
+        self.modifiers.iter().find_map(|modifier| match modifier {
+            DiceModifier::RerollBelow(threshold) => Some(*threshold),
+            _ => None,
+        })
+    }
+
+    pub fn selection(&self) -> Option<DiceModifier> {
+        self.modifiers
+            .iter()
+            .find(|modifier| matches!(modifier, DiceModifier::Keep(..) | DiceModifier::Drop(..)))
+            .copied()
+    }
+
+    pub fn success_condition(&self) -> Option<(Comparison, i32)> {
+        self.modifiers.iter().find_map(|modifier| match modifier {
+            DiceModifier::CountSuccesses(comparison, target) => Some((*comparison, *target)),
+            _ => None,
+        })
+    }
+
+    /// ## roll
+    /// Roll every die in the group and apply its modifiers.
+    /// A group with no dice or no sides, such as `0d6` or `1d0`, rolls no dice and totals 0.
+    /// A total that does not fit in an `i32` is an error.
+    pub fn roll<R: DiceRng + ?Sized>(&self, rng: &mut R) -> Result<DiceGroupRoll, DiceRollError> {
+        if self.count <= 0 || self.sides <= 0 {
+            return Ok(DiceGroupRoll {
+                group: self.clone(),
+                dice: vec![],
+                total: 0,
+            });
+        }
+
+        let mut dice: Vec<DieRoll> = (0..self.count).map(|_| self.roll_die(rng)).collect();
+        self.apply_selection(&mut dice);
+
+        let kept = dice.iter().filter(|die| !die.dropped);
+        let total = match self.success_condition() {
+            Some((comparison, target)) => kept
+                .filter(|die| comparison.matches(die.value(), target))
+                .count() as i32,
+            None => kept
+                .flat_map(|die| die.faces.iter())
+                .try_fold(0i32, |total, face| total.checked_add(*face))
+                .ok_or_else(|| {
+                    DiceRollError::new(&format!("the total of {} is too large", self))
+                })?,
+        };
+
+        Ok(DiceGroupRoll {
+            group: self.clone(),
+            dice,
+            total,
+        })
+    }
+
+    fn roll_die<R: DiceRng + ?Sized>(&self, rng: &mut R) -> DieRoll {
+        let mut rerolled = vec![];
+        let mut face = rng.roll_die(self.sides);
+
+        if let Some(threshold) = self.reroll_threshold() {
+            while face <= threshold {
+                rerolled.push(face);
+                face = rng.roll_die(self.sides);
+            }
+        }
+
+        let mut faces = vec![face];
+        if self.explodes() {
+            while face == self.sides && faces.len() <= MAX_EXPLOSIONS {
+                face = rng.roll_die(self.sides);
+                faces.push(face);
+            }
+        }
+
+        DieRoll {
+            faces,
+            rerolled,
+            dropped: false,
+        }
+    }
+
+    fn apply_selection(&self, dice: &mut [DieRoll]) {
+        let (keep_highest, amount) = match self.selection() {
+            Some(DiceModifier::Keep(DiceSelection::Highest, n)) => (true, n),
+            Some(DiceModifier::Keep(DiceSelection::Lowest, n)) => (false, n),
+            Some(DiceModifier::Drop(DiceSelection::Highest, n)) => (false, self.count - n),
+            Some(DiceModifier::Drop(DiceSelection::Lowest, n)) => (true, self.count - n),
+            _ => return,
+        };
+
+        let mut order: Vec<usize> = (0..dice.len()).collect();
+        order.sort_by_key(|&index| dice[index].value());
+        if keep_highest {
+            order.reverse();
+        }
+
+        let keep = amount.clamp(0, self.count) as usize;
+        for &index in order.iter().skip(keep) {
+            dice[index].dropped = true;
+        }
+    }
+}
+
+impl fmt::Display for DiceGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+        for modifier in &self.modifiers {
+            write!(f, "{}", modifier)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::ScriptedRng;
+
+    fn group(count: i32, sides: i32, modifiers: Vec<DiceModifier>) -> DiceGroup {
+        DiceGroup {
+            count,
+            sides,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn test_keep_highest_drops_lowest_dice() {
+        let roll = group(4, 6, vec![DiceModifier::Keep(DiceSelection::Highest, 3)])
+            .roll(&mut rand::thread_rng())
+            .unwrap();
+
+        assert_eq!(roll.dice.len(), 4);
+        let dropped: Vec<&DieRoll> = roll.dice.iter().filter(|die| die.dropped).collect();
+        assert_eq!(dropped.len(), 1);
+        assert!(roll
+            .dice
+            .iter()
+            .all(|die| die.value() >= dropped[0].value()));
+        assert!((3..=18).contains(&roll.total));
+    }
+
+    #[test]
+    fn test_drop_lowest_matches_keep_highest() {
+        let roll = group(4, 6, vec![DiceModifier::Drop(DiceSelection::Lowest, 1)])
+            .roll(&mut rand::thread_rng())
+            .unwrap();
+
+        let kept: i32 = roll
+            .dice
+            .iter()
+            .filter(|die| !die.dropped)
+            .map(|die| die.value())
+            .sum();
+        assert_eq!(roll.dice.iter().filter(|die| die.dropped).count(), 1);
+        assert_eq!(roll.total, kept);
+    }
+
+    #[test]
+    fn test_keep_more_dice_than_rolled() {
+        let roll = group(2, 6, vec![DiceModifier::Keep(DiceSelection::Lowest, 5)])
+            .roll(&mut rand::thread_rng())
+            .unwrap();
+        assert!(roll.dice.iter().all(|die| !die.dropped));
+    }
+
+    #[test]
+    fn test_reroll_below_never_keeps_low_faces() {
+        let roll = group(50, 10, vec![DiceModifier::RerollBelow(2)])
+            .roll(&mut rand::thread_rng())
+            .unwrap();
+        assert!(roll.dice.iter().all(|die| die.faces[0] > 2));
+        assert!(roll
+            .dice
+            .iter()
+            .all(|die| die.rerolled.iter().all(|face| *face <= 2)));
+    }
+
+    #[test]
+    fn test_exploding_dice_continue_on_maximum() {
+        let roll = group(50, 2, vec![DiceModifier::Explode])
+            .roll(&mut rand::thread_rng())
+            .unwrap();
+        for die in &roll.dice {
+            let (last, exploded) = die.faces.split_last().unwrap();
+            assert_eq!(*last, 1);
+            assert!(exploded.iter().all(|face| *face == 2));
+        }
+    }
+
+    #[test]
+    fn test_count_successes() {
+        let roll = group(
+            6,
+            10,
+            vec![DiceModifier::CountSuccesses(Comparison::GreaterOrEqual, 7)],
+        )
+        .roll(&mut rand::thread_rng())
+        .unwrap();
+
+        let successes = roll.dice.iter().filter(|die| die.value() >= 7).count() as i32;
+        assert_eq!(roll.total, successes);
+        assert!((0..=6).contains(&roll.total));
+    }
+
+    #[test]
+    fn test_scripted_modifiers_apply_in_order() {
+        // rerolls the 1, explodes the 10 into a 3, then keeps the two highest dice.
+        let mut rng = ScriptedRng::new(vec![1, 5, 10, 3, 2, 7]);
+        let roll = group(
+            3,
+            10,
+            vec![
+                DiceModifier::Keep(DiceSelection::Highest, 2),
+                DiceModifier::Explode,
+                DiceModifier::RerollBelow(1),
+            ],
+        )
+        .roll(&mut rng)
+        .unwrap();
+
+        assert_eq!(roll.dice[0].rerolled, vec![1]);
+        assert_eq!(roll.dice[0].faces, vec![5]);
+        assert_eq!(roll.dice[1].faces, vec![10, 3]);
+        assert_eq!(roll.dice[2].rerolled, Vec::<i32>::new());
+        assert!(roll.dice[2].dropped);
+        assert_eq!(roll.total, 18);
+        assert_eq!(rng.remaining(), 1);
+    }
+
+    #[test]
+    fn test_total_overflow_is_an_error() {
+        let mut rng = ScriptedRng::new(vec![i32::MAX, 1]);
+        let error = group(2, i32::MAX, vec![]).roll(&mut rng).unwrap_err();
+        assert!(error.message().contains("too large"));
+    }
+
+    #[test]
+    fn test_display_keeps_written_modifiers() {
+        let dice = group(
+            6,
+            10,
+            vec![
+                DiceModifier::Explode,
+                DiceModifier::CountSuccesses(Comparison::Greater, 7),
+            ],
+        );
+        assert_eq!(dice.to_string(), "6d10!>7");
+    }
+}
@@ -1,22 +1,68 @@
-use std::{cmp, fmt};
+use std::{error, fmt};
 
-use rand::Rng;
-use regex::Regex;
+use super::{parse_dice_expression_with, DiceRng, EvaluationLimits, RollResult};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiceRollError {
     message: String,
+    position: Option<usize>,
+}
+
+impl DiceRollError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            position: None,
+        }
+    }
+
+    /// Create an error that points at the character at `position` in the dice expression.
+    pub fn at(message: &str, position: usize) -> Self {
+        Self {
+            message: message.to_string(),
+            position: Some(position),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
 }
 
 impl fmt::Display for DiceRollError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "invalid dice expression: {}", self.message)
+        match self.position {
+            Some(position) => write!(
+                f,
+                "invalid dice expression: {} at position {}",
+                self.message, position
+            ),
+            None => write!(f, "invalid dice expression: {}", self.message),
+        }
     }
 }
 
+impl error::Error for DiceRollError {}
+
 pub enum DiceRoll {
-    Success(i32, i32, String),
-    Failure(i32, i32, String),
+    Success(RollResult),
+    Failure(RollResult),
+}
+
+impl DiceRoll {
+    pub fn result(&self) -> &RollResult {
+        match self {
+            DiceRoll::Success(result) | DiceRoll::Failure(result) => result,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, DiceRoll::Success(_))
+    }
 }
 
 pub type DiceRollResult = Result<DiceRoll, DiceRollError>;
@@ -24,7 +70,8 @@ pub type DiceRollResult = Result<DiceRoll, DiceRollError>;
 /// ## roll_dice_check
 /// roll_dice_check will take a dice expression and a difficulty check and return a DiceRollResult
 /// The dice expression will be a string that represents a dice roll, such as
-/// "2d6", "1d20", "2d10+5", "2d4*8", "10d20 - 8" etc.
+/// "2d6", "1d20", "2d10+5", "2d4*8", "10d20 - 8", "roll(2d6+1d4+3)", "(1d8+2)*2" or "4d6kh3".
+/// See [`parse_dice_expression`] for the full notation.
 /// The difficulty check will be an integer that represents the target number that the dice roll
 /// must meet or exceed to be considered a success.
 /// The DiceRollResult will be a Success or Failure enum that contains the result of the dice roll
@@ -33,15 +80,16 @@ pub type DiceRollResult = Result<DiceRoll, DiceRollError>;
 ///
 /// * `dice_expression` - A string that represents a dice roll
 /// * `difficulty_check` - An integer that represents the target number that the dice roll must meet
-///     or exceed to be considered a success
+///   or exceed to be considered a success
 ///
 /// ### Returns
 ///
 /// A DiceRollResult that contains the result of the dice roll.  If the dice roll meets or exceeds
-/// the dc then the result will be a Success enum that contains the total of the dice roll, the dc,
-/// and the modifier.  If the dice roll does not meet the dc then the result will be a Failure enum
-/// that contains the total of the dice roll, the dc, and the modifier.
+/// the dc then the result will be a Success enum, otherwise it will be a Failure enum.  Both carry
+/// a [`RollResult`] with the total, the dc, the modifier and every die that was rolled.
+/// The modifier is everything after the leading dice group, e.g. `+5-2` for `1d20+5-2`.
 /// The total can never be less than 0. `1d4-5` will return 0.
+/// An invalid expression returns a `DiceRollError` that points at the offending character.
 ///
 /// ### Example
 ///
@@ -52,83 +100,101 @@ pub type DiceRollResult = Result<DiceRoll, DiceRollError>;
 /// match result {
 ///     Err(_) => println!("Invalid dice expression"),
 ///     Ok(roll) => match roll {
-///         DiceRoll::Success(total, dc, modifier) => {
-///             println!("Success! Total: {}, DC: {}, Modifier: {}", total, dc, modifier);
-///         },
-///         DiceRoll::Failure(total, dc, modifier) => {
-///             println!("Failure! Total: {}, DC: {}, Modifier: {}", total, dc, modifier);
-///         },
+///         DiceRoll::Success(result) => println!("Success! {}", result),
+///         DiceRoll::Failure(result) => println!("Failure! {}", result),
 ///     },
 /// }
 /// ```
 pub fn roll_dice_check(dice_expression: String, difficulty_check: i32) -> DiceRollResult {
-    let mut rng = rand::thread_rng();
-
-    let sanitized_dice_expression = dice_expression.replace(" ", "");
-    let re = Regex::new(r"^roll\((\d*)d(\d+)([\+\-\*\/]\d+)?\)$").unwrap();
-
-    let captures = match re.captures(&sanitized_dice_expression) {
-        Some(caps) => caps,
-        None => {
-            return Err(DiceRollError {
-                message: "invalid dice expression".to_string(),
-            });
-        }
-    };
-
-    let num_dice: i32 = captures
-        .get(1)
-        .map_or(1, |m| m.as_str().parse().unwrap_or(1));
-    let die_size: i32 = captures
-        .get(2)
-        .map_or(1, |m| m.as_str().parse().unwrap_or(1));
-    let modifier_str = captures.get(3).map_or("+0", |m| m.as_str());
-
-    let (modifier_operator, modifier_value) = if modifier_str.len() > 1 {
-        let operator = &modifier_str[0..1];
-        let value = modifier_str[1..].parse().unwrap_or(0);
-        (operator, value)
-    } else {
-        ("+", 0)
-    };
-
-    if num_dice == 0 || die_size == 0 {
-        return Ok(DiceRoll::Failure(0, difficulty_check, "+0".to_string()));
-    }
+    roll_dice_check_with(dice_expression, difficulty_check, &mut rand::thread_rng())
+}
 
-    let mut total = 0;
+/// ## roll_dice_check_with
+/// The same as [`roll_dice_check`], but every die is rolled with `rng` instead of the thread
+/// local generator.  Pass a [`super::SeededRng`] to make the roll reproducible, or a
+/// [`super::ScriptedRng`] to choose the faces.
+///
+/// ### Example
+///
+/// ```
+/// use crate::logic::utilities::dice_rng::ScriptedRng;
+/// use crate::logic::utilities::dice_roller::*;
+///
+/// let mut rng = ScriptedRng::new(vec![14]);
+/// match roll_dice_check_with("1d20+3".to_string(), 15, &mut rng) {
+///     Ok(DiceRoll::Success(result)) => assert_eq!(result.to_string(), "1d20 (14) + 3 = 17 vs DC 15"),
+///     _ => panic!("expected a success"),
+/// }
+/// ```
+pub fn roll_dice_check_with<R: DiceRng + ?Sized>(
+    dice_expression: String,
+    difficulty_check: i32,
+    rng: &mut R,
+) -> DiceRollResult {
+    roll_dice_check_with_limits(
+        dice_expression,
+        difficulty_check,
+        &EvaluationLimits::default(),
+        rng,
+    )
+}
 
-    for _ in 0..num_dice {
-        total += rng.gen_range(1..=die_size);
-    }
+/// ## roll_dice_check_with_limits
+/// The same as [`roll_dice_check_with`], but the expression must stay within `limits` instead
+/// of the default [`EvaluationLimits`].
+///
+/// ### Example
+///
+/// ```
+/// use crate::logic::utilities::dice_roller::*;
+/// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+///
+/// let limits = EvaluationLimits {
+///     max_sides: 20,
+///     ..EvaluationLimits::default()
+/// };
+/// match roll_dice_check_with_limits("1d100".to_string(), 50, &limits, &mut rand::thread_rng()) {
+///     Err(error) => assert_eq!(error.message(), "a die cannot have more than 20 sides"),
+///     Ok(_) => panic!("expected a d100 to be rejected"),
+/// }
+/// ```
+pub fn roll_dice_check_with_limits<R: DiceRng + ?Sized>(
+    dice_expression: String,
+    difficulty_check: i32,
+    limits: &EvaluationLimits,
+    rng: &mut R,
+) -> DiceRollResult {
+    let expression = parse_dice_expression_with(&dice_expression, limits)?;
+    let result = RollResult::roll(&expression, Some(difficulty_check), rng)?;
 
-    match modifier_operator {
-        "+" => total += modifier_value,
-        "-" => total -= modifier_value,
-        "*" => total *= modifier_value,
-        "/" => total /= modifier_value,
-        _ => (),
+    if result.is_success() {
+        Ok(DiceRoll::Success(result))
+    } else {
+        Ok(DiceRoll::Failure(result))
     }
+}
 
-    if total >= difficulty_check {
-        Ok(DiceRoll::Success(
-            cmp::max(total, 0),
-            difficulty_check,
-            modifier_str.to_string(),
-        ))
-    } else {
-        Ok(DiceRoll::Failure(
-            cmp::max(total, 0),
-            difficulty_check,
-            modifier_str.to_string(),
-        ))
+/// ## d20_expression
+/// The expression for a d20 roll with a bonus, e.g. `1d20+5`, `1d20-2` or `1d20`.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_roller::d20_expression;
+///
+/// assert_eq!(d20_expression(-2), "1d20-2");
+/// ```
+pub fn d20_expression(bonus: i32) -> String {
+    match bonus {
+        0 => "1d20".to_string(),
+        bonus if bonus < 0 => format!("1d20-{}", bonus.unsigned_abs()),
+        bonus => format!("1d20+{}", bonus),
     }
 }
 
 /// ## roll_dice
 /// roll_dice will take a dice expression and return the total of the dice roll
 /// The dice expression will be a string that represents a dice roll, such as
-/// "2d6", "1d20", "2d10+5", "2d4*8", "10d20 - 8" etc.
+/// "2d6", "1d20", "2d10+5", "2d4*8", "10d20 - 8", "2d6+1d4+3" etc.
 /// The total can never be less than 0. `1d4-5` will return 0.
 /// If the dice expression is invalid, the function will return 0.
 /// If the dice expression is valid, the function will return the total of the dice roll.
@@ -145,8 +211,15 @@ pub fn roll_dice_check(dice_expression: String, difficulty_check: i32) -> DiceRo
 /// println!("Total: {}", total);
 /// ```
 pub fn roll_dice(dice_expression: String) -> i32 {
-    match roll_dice_check(dice_expression, 0) {
-        Ok(DiceRoll::Success(total, _, _)) | Ok(DiceRoll::Failure(total, _, _)) => total,
+    roll_dice_with(dice_expression, &mut rand::thread_rng())
+}
+
+/// ## roll_dice_with
+/// The same as [`roll_dice`], but every die is rolled with `rng` instead of the thread local
+/// generator.
+pub fn roll_dice_with<R: DiceRng + ?Sized>(dice_expression: String, rng: &mut R) -> i32 {
+    match roll_dice_check_with(dice_expression, 0, rng) {
+        Ok(roll) => roll.result().total,
         Err(_) => 0,
     }
 }
@@ -154,23 +227,23 @@ pub fn roll_dice(dice_expression: String) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utilities::{ScriptedRng, SeededRng};
 
     #[test]
     fn test_roll_dice() {
         let result = roll_dice_check("roll(2d6)".to_string(), 10);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 2 is expected
-                assert!(2 <= total);
+                assert!(2 <= result.total);
                 // a maximum of 12 is expected
-                assert!(12 >= total);
+                assert!(12 >= result.total);
                 // the difficulty check is 10
-                assert_eq!(dc, 10);
+                assert_eq!(result.difficulty_check, Some(10));
                 // the modifier is +0
-                assert_eq!(modifier, "+0");
+                assert_eq!(result.modifier, "+0");
             }
-            Err(_) => assert!(false, "Expected a valid dice roll result"),
+            Err(_) => panic!("Expected a valid dice roll result"),
         }
     }
 
@@ -178,16 +251,15 @@ mod tests {
     fn test_roll_dice_with_addition_modifier() {
         let result = roll_dice_check("roll(2d6+5)".to_string(), 10);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 7 is expected
-                assert!(7 <= total);
+                assert!(7 <= result.total);
                 // a maximum of 17 is expected
-                assert!(17 >= total);
+                assert!(17 >= result.total);
                 // the difficulty check is 10
-                assert_eq!(dc, 10);
+                assert_eq!(result.difficulty_check, Some(10));
                 // the modifier is +5
-                assert_eq!(modifier, "+5");
+                assert_eq!(result.modifier, "+5");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -197,16 +269,15 @@ mod tests {
     fn test_roll_dice_with_multiplication_modifier() {
         let result = roll_dice_check("roll(2d6*5)".to_string(), 10);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 10 is expected
-                assert!(10 <= total);
+                assert!(10 <= result.total);
                 // a maximum of 60 is expected
-                assert!(60 >= total);
+                assert!(60 >= result.total);
                 // the difficulty check is 10
-                assert_eq!(dc, 10);
+                assert_eq!(result.difficulty_check, Some(10));
                 // the modifier is *5
-                assert_eq!(modifier, "*5");
+                assert_eq!(result.modifier, "*5");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -216,16 +287,15 @@ mod tests {
     fn test_roll_dice_with_subtraction_modifier() {
         let result = roll_dice_check("roll(2d6-5)".to_string(), 10);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 0 is expected
-                assert!(0 <= total);
+                assert!(0 <= result.total);
                 // a maximum of 7 is expected
-                assert!(12 >= total);
+                assert!(12 >= result.total);
                 // the difficulty check is 10
-                assert_eq!(dc, 10);
+                assert_eq!(result.difficulty_check, Some(10));
                 // the modifier is -5
-                assert_eq!(modifier, "-5");
+                assert_eq!(result.modifier, "-5");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -235,27 +305,105 @@ mod tests {
     fn test_roll_dice_with_division_modifier() {
         let result = roll_dice_check("roll(2d20/5)".to_string(), 10);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 0 is expected
-                assert!(0 <= total);
+                assert!(0 <= result.total);
                 // a maximum of 8 is expected
-                assert!(8 >= total);
+                assert!(8 >= result.total);
                 // the difficulty check is 10
-                assert_eq!(dc, 10);
+                assert_eq!(result.difficulty_check, Some(10));
                 // the modifier is /5
-                assert_eq!(modifier, "/5");
+                assert_eq!(result.modifier, "/5");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
     }
 
+    #[test]
+    fn test_roll_dice_with_multiple_modifiers() {
+        let result = roll_dice_check("roll(1d20+5-2)".to_string(), 10);
+        match result {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
+                // a minimum of 4 is expected
+                assert!(4 <= result.total);
+                // a maximum of 23 is expected
+                assert!(23 >= result.total);
+                // the difficulty check is 10
+                assert_eq!(result.difficulty_check, Some(10));
+                // the modifier is +5-2
+                assert_eq!(result.modifier, "+5-2");
+            }
+            Err(_) => panic!("Expected a valid dice roll result"),
+        }
+    }
+
+    #[test]
+    fn test_roll_dice_with_keep_highest() {
+        let result = roll_dice_check("roll(4d6kh3+2)".to_string(), 10);
+        match result {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
+                // a minimum of 5 is expected
+                assert!(5 <= result.total);
+                // a maximum of 20 is expected
+                assert!(20 >= result.total);
+                // the difficulty check is 10
+                assert_eq!(result.difficulty_check, Some(10));
+                // the modifier is +2, the keep is part of the dice group
+                assert_eq!(result.modifier, "+2");
+            }
+            Err(_) => panic!("Expected a valid dice roll result"),
+        }
+    }
+
+    #[test]
+    fn test_roll_dice_with_multiple_dice_groups() {
+        let result = roll_dice_check("roll(2d6+1d4+3)".to_string(), 10);
+        match result {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
+                // a minimum of 6 is expected
+                assert!(6 <= result.total);
+                // a maximum of 19 is expected
+                assert!(19 >= result.total);
+                // the modifier is +1d4+3
+                assert_eq!(result.modifier, "+1d4+3");
+            }
+            Err(_) => panic!("Expected a valid dice roll result"),
+        }
+    }
+
+    #[test]
+    fn test_roll_dice_with_parentheses() {
+        let result = roll_dice_check("roll((1d4+1)*2)".to_string(), 0);
+        match result {
+            Ok(DiceRoll::Success(result)) => {
+                assert!(4 <= result.total);
+                assert!(10 >= result.total);
+                assert_eq!(result.total % 2, 0);
+            }
+            _ => panic!("Expected a successful dice roll result"),
+        }
+    }
+
     #[test]
     fn test_roll_dice_with_invalid_dice_expression() {
-        let result = roll_dice_check("roll(2d6+5+5)".to_string(), 10);
+        let result = roll_dice_check("roll(2d6+5+)".to_string(), 10);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_roll_dice_error_points_at_offending_character() {
+        match roll_dice_check("roll(2d6+x)".to_string(), 10) {
+            Err(e) => {
+                assert_eq!(e.position(), Some(9));
+                assert_eq!(
+                    e.to_string(),
+                    "invalid dice expression: unexpected 'x' at position 9"
+                );
+            }
+            Ok(_) => panic!("Expected an invalid dice expression"),
+        }
+    }
+
     #[test]
     fn test_roll_dice_with_invalid_dice_expression_2() {
         let result = roll_dice_check("roll(2d6+5-)".to_string(), 10);
@@ -266,16 +414,15 @@ mod tests {
     fn test_roll_dice_with_single_die() {
         let result = roll_dice_check("roll(1d6)".to_string(), 4);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 1 is expected
-                assert!(1 <= total);
+                assert!(1 <= result.total);
                 // a maximum of 6 is expected
-                assert!(6 >= total);
+                assert!(6 >= result.total);
                 // the difficulty check is 4
-                assert_eq!(dc, 4);
+                assert_eq!(result.difficulty_check, Some(4));
                 // the modifier is +0
-                assert_eq!(modifier, "+0");
+                assert_eq!(result.modifier, "+0");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -285,16 +432,15 @@ mod tests {
     fn test_roll_dice_with_large_number_of_dice() {
         let result = roll_dice_check("roll(100d6)".to_string(), 300);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // a minimum of 100 is expected
-                assert!(100 <= total);
+                assert!(100 <= result.total);
                 // a maximum of 600 is expected
-                assert!(600 >= total);
+                assert!(600 >= result.total);
                 // the difficulty check is 300
-                assert_eq!(dc, 300);
+                assert_eq!(result.difficulty_check, Some(300));
                 // the modifier is +0
-                assert_eq!(modifier, "+0");
+                assert_eq!(result.modifier, "+0");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -304,13 +450,13 @@ mod tests {
     fn test_roll_dice_with_zero_dice() {
         let result = roll_dice_check("roll(0d6)".to_string(), 1);
         match result {
-            Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Failure(result)) => {
                 // the total should be 0
-                assert_eq!(total, 0);
+                assert_eq!(result.total, 0);
                 // the difficulty check is 1
-                assert_eq!(dc, 1);
+                assert_eq!(result.difficulty_check, Some(1));
                 // the modifier is +0
-                assert_eq!(modifier, "+0");
+                assert_eq!(result.modifier, "+0");
             }
             _ => panic!("Expected a valid dice roll result"),
         }
@@ -320,14 +466,13 @@ mod tests {
     fn test_roll_dice_with_zero_sided_die() {
         let result = roll_dice_check("roll(1d0)".to_string(), 1);
         match result {
-            Ok(DiceRoll::Success(total, dc, modifier))
-            | Ok(DiceRoll::Failure(total, dc, modifier)) => {
+            Ok(DiceRoll::Success(result)) | Ok(DiceRoll::Failure(result)) => {
                 // the total should be 0
-                assert_eq!(total, 0);
+                assert_eq!(result.total, 0);
                 // the difficulty check is 1
-                assert_eq!(dc, 1);
+                assert_eq!(result.difficulty_check, Some(1));
                 // the modifier is +0
-                assert_eq!(modifier, "+0");
+                assert_eq!(result.modifier, "+0");
             }
             Err(_) => panic!("Expected a valid dice roll result"),
         }
@@ -338,4 +483,32 @@ mod tests {
         let result = roll_dice("roll(2d6)".to_string());
         assert!(result >= 0);
     }
+
+    #[test]
+    fn test_roll_dice_check_with_scripted_rng() {
+        let mut rng = ScriptedRng::new(vec![2, 3, 6]);
+        match roll_dice_check_with("roll(2d6+1d6-4)".to_string(), 8, &mut rng) {
+            Ok(DiceRoll::Failure(result)) => {
+                // 2 + 3 + 6 - 4
+                assert_eq!(result.total, 7);
+                assert_eq!(result.difficulty_check, Some(8));
+                assert_eq!(result.modifier, "+1d6-4");
+            }
+            _ => panic!("Expected a failed dice roll"),
+        }
+    }
+
+    #[test]
+    fn test_roll_dice_with_seed_is_reproducible() {
+        let first: Vec<i32> = {
+            let mut rng = SeededRng::new(2024);
+            (0..10)
+                .map(|_| roll_dice_with("roll(3d8+2)".to_string(), &mut rng))
+                .collect()
+        };
+        let mut rng = SeededRng::new(2024);
+        for total in first {
+            assert_eq!(roll_dice_with("roll(3d8+2)".to_string(), &mut rng), total);
+        }
+    }
 }
@@ -0,0 +1,51 @@
+/// ## EvaluationLimits
+/// Bounds on how much work a single dice or arithmetic expression may ask for.  Expressions come
+/// from data that anyone can PUT to the server, so they are checked against these limits when
+/// they are parsed, before anything is rolled.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::dice_expression::parse_dice_expression_with;
+/// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+///
+/// let limits = EvaluationLimits {
+///     max_dice: 10,
+///     ..EvaluationLimits::default()
+/// };
+/// assert!(parse_dice_expression_with("4d6+6d6", &limits).is_ok());
+/// assert!(parse_dice_expression_with("4d6+7d6", &limits).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvaluationLimits {
+    /// The most dice an expression may roll, counted across all of its dice groups.
+    pub max_dice: i64,
+    /// The most sides a single die may have.
+    pub max_sides: i32,
+    /// The longest expression text, in characters.
+    pub max_expression_length: usize,
+    /// How deeply parentheses, function calls and unary minus may be nested.
+    pub max_nesting: usize,
+    /// The most steps working out an expression's distribution may take, counted as the pairs
+    /// of totals combined across the whole expression.
+    pub max_analysis_steps: u64,
+}
+
+impl EvaluationLimits {
+    pub const DEFAULT_MAX_DICE: i64 = 1000;
+    pub const DEFAULT_MAX_SIDES: i32 = 1000;
+    pub const DEFAULT_MAX_EXPRESSION_LENGTH: usize = 1024;
+    pub const DEFAULT_MAX_NESTING: usize = 32;
+    pub const DEFAULT_MAX_ANALYSIS_STEPS: u64 = 1_000_000;
+}
+
+impl Default for EvaluationLimits {
+    fn default() -> Self {
+        Self {
+            max_dice: Self::DEFAULT_MAX_DICE,
+            max_sides: Self::DEFAULT_MAX_SIDES,
+            max_expression_length: Self::DEFAULT_MAX_EXPRESSION_LENGTH,
+            max_nesting: Self::DEFAULT_MAX_NESTING,
+            max_analysis_steps: Self::DEFAULT_MAX_ANALYSIS_STEPS,
+        }
+    }
+}
@@ -1,23 +1,58 @@
-use evalexpr::eval;
-use regex::Regex;
 use std::collections::HashMap;
 use std::error::Error;
 use subst::substitute;
 
-use super::roll_dice;
+use crate::abilities::{Ability, AbilityScores};
+use crate::races::Race;
 
-#[derive(Debug, Default)]
+use super::{
+    CompiledExpression, DiceRng, EvaluationLimits, CLASS_VARIABLE, LEVEL_VARIABLE, RACE_VARIABLE,
+    SCORE_SUFFIX, SPEED_VARIABLE,
+};
+
+#[derive(Debug, Clone, Default)]
 pub struct ExpressionContext {
     data: HashMap<String, String>,
+    limits: EvaluationLimits,
 }
 
 impl ExpressionContext {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
+            limits: EvaluationLimits::default(),
         }
     }
 
+    /// ## with_limits
+    /// Use `limits` instead of the default [`EvaluationLimits`] for every expression resolved
+    /// with this context.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::evaluation_limits::EvaluationLimits;
+    /// use crate::logic::utilities::expression_parser::ExpressionContext;
+    ///
+    /// let context = ExpressionContext::new().with_limits(EvaluationLimits {
+    ///     max_dice: 2,
+    ///     ..EvaluationLimits::default()
+    /// });
+    /// assert!(context.resolve_calculate("roll(2d6)").is_ok());
+    /// assert!(context.resolve_calculate("roll(3d6)").is_err());
+    /// ```
+    pub fn with_limits(mut self, limits: EvaluationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn set_limits(&mut self, limits: EvaluationLimits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> &EvaluationLimits {
+        &self.limits
+    }
+
     /// ## set
     /// Set a key-value pair in the context.
     ///
@@ -35,6 +70,65 @@ impl ExpressionContext {
         self.data.insert(key.to_string(), value.to_string());
     }
 
+    /// ## from_ability_scores
+    /// Build a context from anything that has ability scores.  Each ability abbreviation holds
+    /// the modifier and the abbreviation with a `_SCORE` suffix holds the score, so a
+    /// constitution of 14 sets `CON` to `2` and `CON_SCORE` to `14`.
+    ///
+    /// ### Arguments
+    /// * `scores` - The ability scores to read, e.g. a `Character`.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::abilities::{ability_modifier, Ability, AbilityScores, DetailedAbilityScore};
+    /// use crate::logic::utilities::expression_parser::ExpressionContext;
+    ///
+    /// struct Fixed(i32);
+    /// impl AbilityScores for Fixed {
+    ///     fn get_ability_score(&self, _: Ability) -> i32 { self.0 }
+    ///     fn get_raw_ability_score(&self, _: Ability) -> i32 { self.0 }
+    ///     fn get_detail_ability_score(&self, ability: Ability) -> DetailedAbilityScore {
+    ///         DetailedAbilityScore::new(ability, self.0, vec![])
+    ///     }
+    ///     fn get_modifier(&self, _: Ability) -> i32 { ability_modifier(self.0) }
+    /// }
+    ///
+    /// let context = ExpressionContext::from_ability_scores(&Fixed(14)).with_level(3);
+    /// assert_eq!(context.resolve_calculate("10 + CON + LEVEL").unwrap(), 15);
+    /// assert_eq!(context.get("CON_SCORE").unwrap(), "14");
+    /// ```
+    pub fn from_ability_scores<T: AbilityScores + ?Sized>(scores: &T) -> Self {
+        let mut context = Self::new();
+        for ability in Ability::all() {
+            let abbreviation = ability.abbreviation();
+            context.set(abbreviation, &scores.get_modifier(ability).to_string());
+            context.set(
+                &format!("{}{}", abbreviation, SCORE_SUFFIX),
+                &scores.get_ability_score(ability).to_string(),
+            );
+        }
+        context
+    }
+
+    /// Set `LEVEL` in the context.
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.set(LEVEL_VARIABLE, &level.to_string());
+        self
+    }
+
+    /// Set `CLASS` to the name or code of a class.
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.set(CLASS_VARIABLE, class);
+        self
+    }
+
+    /// Set `RACE` to the race name and `SPEED` to its speed.
+    pub fn with_race(mut self, race: &Race) -> Self {
+        self.set(RACE_VARIABLE, &race.name);
+        self.set(SPEED_VARIABLE, &race.speed.to_string());
+        self
+    }
+
     /// ## get
     /// Get a value from the context by key.
     /// Returns a reference to the value if it exists.
@@ -80,6 +174,8 @@ impl ExpressionContext {
     /// Resolve an expression that contains arithmetic operations.
     /// The expression can contain variables that are resolved from the context.
     /// The expression can also contain dice rolls.
+    /// The expression is compiled on every call, use a [`CompiledExpression`] to evaluate the
+    /// same expression many times.
     /// Returns the result of the expression as an integer.
     /// Returns an error if the expression cannot be resolved.
     ///
@@ -94,39 +190,41 @@ impl ExpressionContext {
     /// let result = context.resolve_calculate("2 + 2");
     /// ```
     pub fn resolve_calculate(&self, expression: &str) -> Result<i64, Box<dyn Error>> {
-        // first do the substitution as needed.
-        let substituted_expression = substitute(expression, &self.data)?;
-
-        // our expression could contain a dice roll, if that's the case we need to resolve it first.
-        let re = Regex::new(r".*(?P<roll>roll\(.*\)).*").unwrap();
-        // we might *not* have a dice roll, so account for that possibility.
-        let resolved_expression = match re.captures(substituted_expression.as_str()) {
-            Some(captures) => {
-                let roll = captures.name("roll").unwrap().as_str();
-                let roll_result = roll_dice(roll.to_string());
-                substituted_expression.replace(roll, roll_result.to_string().as_str())
-            }
-            None => substituted_expression,
-        };
-
-        // now that we have resolved our dice roll, we can evaluate the expression.
-        let result = match eval(resolved_expression.as_str()) {
-            Ok(v) => v,
-            Err(e) => return Err(Box::new(e)),
-        };
-
-        let computed_value = match result.as_number() {
-            Ok(v) => v.round() as i64,
-            Err(e) => return Err(Box::new(e)),
-        };
-
-        Ok(computed_value)
+        self.resolve_calculate_with(expression, &mut rand::thread_rng())
+    }
+
+    /// ## resolve_calculate_with
+    /// The same as [`ExpressionContext::resolve_calculate`], but any dice roll in the expression
+    /// is rolled with `rng`.
+    ///
+    /// ### Arguments
+    /// * `expression` - A string slice that holds the expression to resolve.
+    /// * `rng` - The generator used for dice rolls, e.g. a `SeededRng` or `ScriptedRng`.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::dice_rng::ScriptedRng;
+    /// use crate::logic::utilities::expression_parser::ExpressionContext;
+    /// let mut context = ExpressionContext::new();
+    /// context.set("CON", "2");
+    /// let mut rng = ScriptedRng::new(vec![4, 6]);
+    /// let result = context.resolve_calculate_with("$CON + roll(2d6)", &mut rng).unwrap();
+    /// assert_eq!(result, 12);
+    /// ```
+    pub fn resolve_calculate_with<R: DiceRng + ?Sized>(
+        &self,
+        expression: &str,
+        rng: &mut R,
+    ) -> Result<i64, Box<dyn Error>> {
+        let compiled = CompiledExpression::compile_with(expression, &self.limits)?;
+        Ok(compiled.evaluate_with(self, rng)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utilities::{ScriptedRng, SeededRng};
 
     #[test]
     fn test_resolve() {
@@ -187,6 +285,48 @@ mod tests {
         assert!((22..=34).contains(&result));
     }
 
+    #[test]
+    fn test_resolve_calculate_with_dice_modifiers() {
+        let context = ExpressionContext::new();
+
+        let result = context.resolve_calculate("roll(4d6kh3) + 1").unwrap();
+        assert!((4..=19).contains(&result));
+
+        let result = context.resolve_calculate("roll(6d10>=7)").unwrap();
+        assert!((0..=6).contains(&result));
+
+        let result = context.resolve_calculate("roll(2d10r1) * 2").unwrap();
+        assert!((8..=40).contains(&result));
+    }
+
+    #[test]
+    fn test_resolve_calculate_with_seeded_rng() {
+        let mut context = ExpressionContext::new();
+        context.set("CON", "3");
+
+        let first = context
+            .resolve_calculate_with("10 + $CON + roll(1d8)", &mut SeededRng::new(99))
+            .unwrap();
+        let second = context
+            .resolve_calculate_with("10 + $CON + roll(1d8)", &mut SeededRng::new(99))
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert!((14..=21).contains(&first));
+    }
+
+    #[test]
+    fn test_resolve_calculate_with_several_dice_rolls() {
+        let context = ExpressionContext::new();
+        let mut rng = ScriptedRng::new(vec![6, 2, 2]);
+
+        let result = context
+            .resolve_calculate_with("roll(1d6) + roll(1d4) * roll(1d4)", &mut rng)
+            .unwrap();
+
+        assert_eq!(result, 10);
+    }
+
     #[test]
     fn test_resolve_calculate_with_decimals() {
         let mut context = ExpressionContext::new();
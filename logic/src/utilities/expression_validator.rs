@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+
+use crate::abilities::Ability;
+
+use super::{CompiledExpression, ExpressionError};
+
+/// The variable that holds a character's level.
+pub const LEVEL_VARIABLE: &str = "LEVEL";
+
+/// The variable that holds the name of a character's class.  It is text, so it can only be
+/// used with [`super::ExpressionContext::resolve`].
+pub const CLASS_VARIABLE: &str = "CLASS";
+
+/// The variable that holds the name of a character's race.
+pub const RACE_VARIABLE: &str = "RACE";
+
+/// The variable that holds a character's racial speed.
+pub const SPEED_VARIABLE: &str = "SPEED";
+
+/// The suffix of the variables that hold raw ability scores, e.g. `CON_SCORE`.  The bare
+/// abbreviation, e.g. `CON`, is the ability modifier.
+pub const SCORE_SUFFIX: &str = "_SCORE";
+
+/// ## character_variables
+/// The variables every character provides to an expression: each ability modifier (`STR`,
+/// `DEX`, `CON`, `INT`, `WIS`, `CHA`), each ability score (`STR_SCORE`, ...), `LEVEL` and
+/// `SPEED`.  `CLASS` and `RACE` are left out because they are not numbers.
+pub fn character_variables() -> Vec<String> {
+    let mut variables: Vec<String> = Ability::all()
+        .iter()
+        .flat_map(|ability| {
+            [
+                ability.abbreviation().to_string(),
+                format!("{}{}", ability.abbreviation(), SCORE_SUFFIX),
+            ]
+        })
+        .collect();
+    variables.push(LEVEL_VARIABLE.to_string());
+    variables.push(SPEED_VARIABLE.to_string());
+    variables
+}
+
+/// ## ExpressionValidator
+/// Checks an expression without evaluating it: it must compile and may only read variables
+/// from a known set.
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::expression_validator::ExpressionValidator;
+///
+/// let validator = ExpressionValidator::for_character();
+/// assert!(validator.validate("10 + CON").is_ok());
+///
+/// let errors = validator.validate("7 + CNO").unwrap_err();
+/// assert_eq!(errors[0].position(), Some(4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpressionValidator {
+    variables: BTreeSet<String>,
+}
+
+impl ExpressionValidator {
+    pub fn new<I, S>(variables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            variables: variables.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// A validator for expressions evaluated against a character, see [`character_variables`].
+    pub fn for_character() -> Self {
+        Self::new(character_variables())
+    }
+
+    /// Allow another variable on top of the ones the validator already knows.
+    pub fn with_variable(mut self, variable: &str) -> Self {
+        self.variables.insert(variable.to_string());
+        self
+    }
+
+    pub fn is_known(&self, variable: &str) -> bool {
+        self.variables.contains(variable)
+    }
+
+    /// ## validate
+    /// Compile the expression and check every variable it reads.
+    ///
+    /// ### Returns
+    /// The compiled expression, or every problem found.  A syntax error stops the check, so it
+    /// is always reported on its own; otherwise there is one error per unknown variable.
+    pub fn validate(&self, expression: &str) -> Result<CompiledExpression, Vec<ExpressionError>> {
+        let compiled = CompiledExpression::compile(expression).map_err(|error| vec![error])?;
+
+        let errors: Vec<ExpressionError> = compiled
+            .variables()
+            .into_iter()
+            .filter(|(name, _)| !self.is_known(name))
+            .map(|(name, position)| {
+                let message = match self.suggest(name) {
+                    Some(suggestion) => format!(
+                        "unknown variable '{}', did you mean '{}'?",
+                        name, suggestion
+                    ),
+                    None => format!("unknown variable '{}'", name),
+                };
+                ExpressionError::at(&message, position)
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(compiled)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// A known variable that is only a different case or order of the same letters, e.g. `CON`
+    /// for `CNO` or `con`.
+    fn suggest(&self, name: &str) -> Option<&str> {
+        let letters = |text: &str| {
+            let mut letters: Vec<char> = text.to_ascii_uppercase().chars().collect();
+            letters.sort_unstable();
+            letters
+        };
+        let target = letters(name);
+        self.variables
+            .iter()
+            .find(|known| letters(known) == target)
+            .map(|known| known.as_str())
+    }
+}
+
+/// ## validate_character_expression
+/// Check an expression that is evaluated against a character, such as a class
+/// `stamina_expression`, and return a readable message for every problem.
+///
+/// ### Arguments
+/// * `expression` - A string slice that holds the expression, e.g. `10 + CON`
+///
+/// ### Example
+/// ```
+/// use crate::logic::utilities::expression_validator::validate_character_expression;
+///
+/// assert!(validate_character_expression("4 + INT").is_ok());
+/// assert_eq!(
+///     validate_character_expression("7 + CNO").unwrap_err(),
+///     vec!["invalid expression: unknown variable 'CNO', did you mean 'CON'? at position 4"]
+/// );
+/// ```
+pub fn validate_character_expression(expression: &str) -> Result<(), Vec<String>> {
+    ExpressionValidator::for_character()
+        .validate(expression)
+        .map(|_| ())
+        .map_err(|errors| errors.iter().map(|error| error.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_character_variables() {
+        let validator = ExpressionValidator::for_character();
+
+        for expression in [
+            "10 + CON",
+            "4 + INT",
+            "max(1, $STR + ${DEX})",
+            "floor(CON_SCORE / 2) + LEVEL",
+            "roll(1d8) + WIS + CHA",
+        ] {
+            assert!(validator.validate(expression).is_ok(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_every_unknown_variable_is_reported() {
+        let errors = ExpressionValidator::for_character()
+            .validate("CNO + LUCK + STR")
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].message(),
+            "unknown variable 'CNO', did you mean 'CON'?"
+        );
+        assert_eq!(errors[0].position(), Some(0));
+        assert_eq!(errors[1].message(), "unknown variable 'LUCK'");
+        assert_eq!(errors[1].position(), Some(6));
+    }
+
+    #[test]
+    fn test_syntax_errors_are_reported() {
+        let errors = ExpressionValidator::for_character()
+            .validate("10 + * CON")
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].position(), Some(5));
+    }
+
+    #[test]
+    fn test_custom_variables() {
+        let validator = ExpressionValidator::new(["BAB"]).with_variable("RANKS");
+
+        assert!(validator.validate("BAB + RANKS").is_ok());
+        assert!(validator.validate("BAB + CON").is_err());
+    }
+}
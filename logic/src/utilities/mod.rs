@@ -1,5 +1,21 @@
+pub mod compiled_expression;
+pub mod dice_distribution;
+pub mod dice_expression;
+pub mod dice_group;
+pub mod dice_rng;
 pub mod dice_roller;
+pub mod evaluation_limits;
 pub mod expression_parser;
+pub mod expression_validator;
+pub mod roll_result;
 
+pub use compiled_expression::*;
+pub use dice_distribution::*;
+pub use dice_expression::*;
+pub use dice_group::*;
+pub use dice_rng::*;
 pub use dice_roller::*;
+pub use evaluation_limits::*;
 pub use expression_parser::*;
+pub use expression_validator::*;
+pub use roll_result::*;
@@ -0,0 +1,271 @@
+use std::{cmp, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::{DiceExpression, DiceRng, DiceRollError, DieRoll};
+
+/// ## GroupResult
+/// The dice rolled for one dice group of an expression, e.g. the `4d6kh3` in `4d6kh3+2`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupResult {
+    /// The group as written, including its modifiers, e.g. `4d6kh3`.
+    pub notation: String,
+    /// The modifiers that were applied to the group, e.g. `["kh3"]`.
+    pub modifiers: Vec<String>,
+    pub sides: i32,
+    pub dice: Vec<DieRoll>,
+    /// What the group added to the expression: the sum of the kept dice, or the number of
+    /// successes for a success-counting group.
+    pub subtotal: i32,
+}
+
+/// ## RollResult
+/// Everything that happened during a single roll of a dice expression, so that it can be shown
+/// to players as `2d6 (3, 5) + 4 = 12 vs DC 10`.
+///
+/// In the breakdown, dice dropped by a keep/drop modifier are shown in square brackets and an
+/// exploding die shows each of its faces, e.g. `4d6kh3! (6+2, 5, 3, [1])`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollResult {
+    /// The normalized expression, e.g. `2d6+4`.
+    pub expression: String,
+    /// The part of the expression after its leading dice group, e.g. `+4`.
+    pub modifier: String,
+    /// The expression with each dice group followed by its faces, e.g. `2d6 (3, 5) + 4`.
+    pub breakdown: String,
+    pub groups: Vec<GroupResult>,
+    /// The total before it was clamped at zero.
+    pub raw_total: i32,
+    pub total: i32,
+    /// Whether a negative total was raised to zero.
+    pub clamped: bool,
+    /// Every kept die showed its highest face, e.g. a natural 20 on `1d20+5`.
+    pub natural_max: bool,
+    /// Every kept die showed a 1.
+    pub natural_min: bool,
+    pub difficulty_check: Option<i32>,
+}
+
+impl RollResult {
+    /// ## roll
+    /// Roll an expression and record every die.  Fails, as [`DiceExpression::roll`] does, on
+    /// division by zero or overflow.
+    ///
+    /// ### Arguments
+    /// * `expression` - The parsed dice expression to roll.
+    /// * `difficulty_check` - The DC the total is compared against, if any.
+    /// * `rng` - The generator used for every die.
+    ///
+    /// ### Example
+    /// ```
+    /// use crate::logic::utilities::dice_expression::parse_dice_expression;
+    /// use crate::logic::utilities::dice_rng::ScriptedRng;
+    /// use crate::logic::utilities::roll_result::RollResult;
+    ///
+    /// let expression = parse_dice_expression("2d6+4").unwrap();
+    /// let result =
+    ///     RollResult::roll(&expression, Some(10), &mut ScriptedRng::new(vec![3, 5])).unwrap();
+    /// assert_eq!(result.to_string(), "2d6 (3, 5) + 4 = 12 vs DC 10");
+    /// ```
+    pub fn roll<R: DiceRng + ?Sized>(
+        expression: &DiceExpression,
+        difficulty_check: Option<i32>,
+        rng: &mut R,
+    ) -> Result<Self, DiceRollError> {
+        let mut groups = vec![];
+        let (raw_total, breakdown) = evaluate(expression, rng, &mut groups)?;
+        let total = cmp::max(raw_total, 0);
+
+        let kept: Vec<(&DieRoll, i32)> = groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .dice
+                    .iter()
+                    .filter(|die| !die.dropped)
+                    .map(move |die| (die, group.sides))
+            })
+            .collect();
+        let natural_max =
+            !kept.is_empty() && kept.iter().all(|(die, sides)| die.faces[0] == *sides);
+        let natural_min = !kept.is_empty() && kept.iter().all(|(die, _)| die.faces[0] == 1);
+
+        Ok(Self {
+            expression: expression.to_string(),
+            modifier: expression.modifier(),
+            breakdown,
+            groups,
+            raw_total,
+            total,
+            clamped: raw_total < 0,
+            natural_max,
+            natural_min,
+            difficulty_check,
+        })
+    }
+
+    /// Whether the total meets or exceeds the difficulty check.  A roll without a DC always
+    /// succeeds.
+    pub fn is_success(&self) -> bool {
+        self.total >= self.difficulty_check.unwrap_or(0)
+    }
+
+    /// Every face of every die, in the order they were rolled, including dropped dice.
+    pub fn faces(&self) -> Vec<i32> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.dice.iter().flat_map(|die| die.faces.clone()))
+            .collect()
+    }
+}
+
+impl fmt::Display for RollResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.breakdown, self.total)?;
+        if self.clamped {
+            write!(f, " (clamped from {})", self.raw_total)?;
+        }
+        if let Some(difficulty_check) = self.difficulty_check {
+            write!(f, " vs DC {}", difficulty_check)?;
+        }
+        Ok(())
+    }
+}
+
+fn evaluate<R: DiceRng + ?Sized>(
+    expression: &DiceExpression,
+    rng: &mut R,
+    groups: &mut Vec<GroupResult>,
+) -> Result<(i32, String), DiceRollError> {
+    match expression {
+        DiceExpression::Number(value) => Ok((*value, value.to_string())),
+        DiceExpression::Dice(group) => {
+            let roll = group.roll(rng)?;
+            let faces: Vec<String> = roll.dice.iter().map(describe_die).collect();
+            let text = format!("{} ({})", group, faces.join(", "));
+            groups.push(GroupResult {
+                notation: group.to_string(),
+                modifiers: group.modifiers.iter().map(|m| m.to_string()).collect(),
+                sides: group.sides,
+                dice: roll.dice,
+                subtotal: roll.total,
+            });
+            Ok((roll.total, text))
+        }
+        DiceExpression::Negate(inner) => {
+            let (value, text) = evaluate(inner, rng, groups)?;
+            let value = value
+                .checked_neg()
+                .ok_or_else(|| DiceRollError::new(&format!("-({}) is too large", value)))?;
+            Ok((value, format!("-{}", text)))
+        }
+        DiceExpression::Group(inner) => {
+            let (value, text) = evaluate(inner, rng, groups)?;
+            Ok((value, format!("({})", text)))
+        }
+        DiceExpression::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            let (left_value, left_text) = evaluate(left, rng, groups)?;
+            let (right_value, right_text) = evaluate(right, rng, groups)?;
+            Ok((
+                operator.apply(left_value, right_value)?,
+                format!("{} {} {}", left_text, operator.symbol(), right_text),
+            ))
+        }
+    }
+}
+
+fn describe_die(die: &DieRoll) -> String {
+    let faces: Vec<String> = die.faces.iter().map(|face| face.to_string()).collect();
+    let faces = faces.join("+");
+    if die.dropped {
+        format!("[{}]", faces)
+    } else {
+        faces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::{parse_dice_expression, ScriptedRng};
+
+    fn roll(notation: &str, difficulty_check: Option<i32>, faces: Vec<i32>) -> RollResult {
+        let expression = parse_dice_expression(notation).unwrap();
+        RollResult::roll(&expression, difficulty_check, &mut ScriptedRng::new(faces)).unwrap()
+    }
+
+    #[test]
+    fn test_breakdown_lists_each_group() {
+        let result = roll("1d20+1d4+3", None, vec![12, 2]);
+
+        assert_eq!(result.breakdown, "1d20 (12) + 1d4 (2) + 3");
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.groups[0].subtotal, 12);
+        assert_eq!(result.groups[1].subtotal, 2);
+        assert_eq!(result.modifier, "+1d4+3");
+        assert_eq!(result.total, 17);
+        assert_eq!(result.to_string(), "1d20 (12) + 1d4 (2) + 3 = 17");
+    }
+
+    #[test]
+    fn test_breakdown_marks_dropped_and_exploded_dice() {
+        let result = roll("4d6kh3!", None, vec![6, 2, 5, 3, 1]);
+
+        assert_eq!(result.breakdown, "4d6kh3! (6+2, 5, 3, [1])");
+        assert_eq!(result.groups[0].modifiers, vec!["kh3", "!"]);
+        assert_eq!(result.faces(), vec![6, 2, 5, 3, 1]);
+        assert_eq!(result.total, 16);
+    }
+
+    #[test]
+    fn test_clamped_total() {
+        let result = roll("1d4-5", Some(1), vec![2]);
+
+        assert!(result.clamped);
+        assert_eq!(result.raw_total, -3);
+        assert_eq!(result.total, 0);
+        assert!(!result.is_success());
+        assert_eq!(
+            result.to_string(),
+            "1d4 (2) - 5 = 0 (clamped from -3) vs DC 1"
+        );
+    }
+
+    #[test]
+    fn test_natural_max_and_min() {
+        let critical = roll("1d20+2", Some(25), vec![20]);
+        assert!(critical.natural_max);
+        assert!(!critical.natural_min);
+
+        let fumble = roll("1d20+2", Some(5), vec![1]);
+        assert!(fumble.natural_min);
+        assert!(!fumble.natural_max);
+
+        let constant = roll("5", None, vec![]);
+        assert!(!constant.natural_max && !constant.natural_min);
+    }
+
+    #[test]
+    fn test_division_by_zero_is_an_error() {
+        let expression = parse_dice_expression("1d6/(2-2)").unwrap();
+        let error =
+            RollResult::roll(&expression, None, &mut ScriptedRng::new(vec![4])).unwrap_err();
+
+        assert_eq!(error.message(), "division by zero in 4/0");
+    }
+
+    #[test]
+    fn test_serializes_with_serde() {
+        let result = roll("2d6+4", Some(10), vec![3, 5]);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: RollResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, result);
+        assert!(json.contains("\"breakdown\":\"2d6 (3, 5) + 4\""));
+    }
+}
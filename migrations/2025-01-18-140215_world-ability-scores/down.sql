@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+alter table game.worlds drop column point_buy_budget;
+alter table game.worlds drop column ability_score_method;
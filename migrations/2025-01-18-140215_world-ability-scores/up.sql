@@ -0,0 +1,3 @@
+-- Your SQL goes here
+alter table game.worlds add column ability_score_method varchar(32) not null default 'point_buy';
+alter table game.worlds add column point_buy_budget integer not null default 27;
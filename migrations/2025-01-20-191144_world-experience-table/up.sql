@@ -0,0 +1,2 @@
+-- Your SQL goes here
+alter table game.worlds add column experience_table bigint[] null;
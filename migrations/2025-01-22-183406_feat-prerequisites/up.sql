@@ -0,0 +1,2 @@
+-- Your SQL goes here
+alter table game.feats add column prerequisites text null;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+alter table game.races drop column special_abilities;
+alter table game.races drop column hit_points;
+alter table game.races drop column speed;
+alter table game.races drop column ability_modifiers;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+alter table game.races add column ability_modifiers jsonb not null default '{}';
+alter table game.races add column speed integer not null default 30;
+alter table game.races add column hit_points integer not null default 0;
+alter table game.races add column special_abilities jsonb not null default '[]';
@@ -0,0 +1,31 @@
+-- Your SQL goes here
+create table game.themes (
+    id bigserial not null,
+    world_id bigint not null,
+    code varchar(32) not null,
+    name varchar(32) not null,
+    description text not null,
+    ability_bonuses jsonb not null default '{}',
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    constraint pk_themes_id primary key (id),
+    constraint fk_themes_worlds foreign key (world_id) references game.worlds (id)
+);
+
+create unique index idx_themes_world_id_code on game.themes (world_id, code);
+create index idx_themes_world_id on game.themes (world_id);
+
+create table game.theme_features (
+    id bigserial not null,
+    theme_id bigint not null,
+    level int not null,
+    code varchar(32) not null,
+    name varchar(32) not null,
+    description text not null,
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    constraint pk_theme_features_id primary key (id),
+    constraint fk_theme_features_themes foreign key (theme_id) references game.themes (id)
+);
+
+create unique index idx_theme_features_theme_id_code on game.theme_features (theme_id, code);
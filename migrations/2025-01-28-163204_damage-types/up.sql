@@ -0,0 +1,15 @@
+-- Your SQL goes here
+create table game.damage_types (
+    id bigserial not null,
+    world_id bigint not null,
+    code varchar(32) not null,
+    name varchar(32) not null,
+    description text not null default '',
+    created_at timestamp not null default now(),
+    updated_at timestamp not null default now(),
+    constraint pk_damage_types_id primary key (id),
+    constraint fk_damage_types_worlds foreign key (world_id) references game.worlds (id)
+);
+
+create unique index idx_damage_types_world_id_code on game.damage_types (world_id, code);
+create index idx_damage_types_world_id on game.damage_types (world_id);
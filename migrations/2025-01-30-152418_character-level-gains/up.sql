@@ -0,0 +1,2 @@
+-- Your SQL goes here
+alter table player.characters add column level_gains jsonb not null default '[]';
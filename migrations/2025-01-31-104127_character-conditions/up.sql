@@ -0,0 +1,2 @@
+-- Your SQL goes here
+alter table player.characters add column conditions jsonb not null default '{"active": []}';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+alter table game.enemies drop column damage_modifiers;
+alter table game.enemies drop column charisma;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+alter table game.enemies add column charisma int not null default 10;
+alter table game.enemies add column damage_modifiers jsonb not null default '[]';
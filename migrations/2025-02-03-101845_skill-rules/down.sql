@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+alter table game.skills drop column armor_penalty;
+alter table game.skills drop column trained_only;
+alter table game.skills drop column ability;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+alter table game.skills add column ability varchar(16) not null default '';
+alter table game.skills alter column ability drop default;
+alter table game.skills add column trained_only boolean not null default false;
+alter table game.skills add column armor_penalty boolean not null default false;
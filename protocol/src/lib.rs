@@ -1,6 +1,9 @@
 pub mod types;
 
 pub use types::character_class::*;
+pub use types::damage_type::*;
 pub use types::item::*;
+pub use types::race::*;
 pub use types::signature::*;
+pub use types::theme::*;
 pub use types::world::*;
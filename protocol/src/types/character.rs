@@ -1,5 +1,8 @@
+use logic::{Ability, Prerequisites};
 use serde::{Deserialize, Serialize};
 
+use super::Valid;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Character {
     pub id: Option<i64>,
@@ -7,6 +10,9 @@ pub struct Character {
     pub world_id: Option<i64>,
     pub user_id: Option<i64>,
     pub name: String,
+    /// The code of the character's race.
+    pub race: String,
+    /// The code of the character's class.
     pub class: String,
     pub theme: String,
     pub level: i32,
@@ -16,6 +22,8 @@ pub struct Character {
     pub abilities: AbilityScores,
     pub feats: Vec<Feat>,
     pub skills: Vec<CharacterSkill>,
+    #[serde(default)]
+    pub conditions: logic::Conditions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,10 +55,15 @@ pub struct Skill {
     pub code: Option<String>,
     pub world_id: Option<i64>,
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    /// The ability added to checks, by name or abbreviation, e.g. `intelligence` or `INT`.
     pub ability: String,
+    #[serde(default)]
     pub trained_only: bool,
+    #[serde(default)]
     pub armor_penalty: bool,
+    #[serde(default)]
     pub source: String,
 }
 
@@ -60,3 +73,131 @@ pub struct CharacterSkill {
     pub is_class_skill: bool,
     pub ranks: i32,
 }
+
+impl Feat {
+    /// The feat's prerequisites in the form [`Prerequisites`] reads, e.g. `STR 13, level 3`.
+    pub fn parsed_prerequisites(&self) -> Result<Prerequisites, logic::PrerequisiteError> {
+        match &self.prerequisites {
+            Some(prerequisites) => prerequisites.parse(),
+            None => Ok(Prerequisites::default()),
+        }
+    }
+}
+
+impl Valid for Feat {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+
+        if self.name.is_empty() {
+            errors.push("Name is required".to_string());
+        }
+
+        if let Err(error) = self.parsed_prerequisites() {
+            errors.push(format!("Prerequisites: {}", error));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl TryFrom<&Skill> for logic::Skill {
+    type Error = String;
+
+    fn try_from(skill: &Skill) -> Result<Self, Self::Error> {
+        let code = match &skill.code {
+            Some(code) => code,
+            None => return Err(format!("skill {} has no code", skill.name)),
+        };
+        let ability = match Ability::from_name(&skill.ability) {
+            Some(ability) => ability,
+            None => {
+                return Err(format!(
+                    "skill {} uses an unknown ability '{}'",
+                    code, skill.ability
+                ))
+            }
+        };
+
+        Ok(logic::Skill {
+            code: code.clone(),
+            name: skill.name.clone(),
+            ability,
+            trained_only: skill.trained_only,
+            armor_penalty: skill.armor_penalty,
+        })
+    }
+}
+
+impl CharacterSkill {
+    /// The character's ranks in `skill`, which must be the skill `skill_id` refers to.
+    pub fn to_logic(&self, skill: &logic::Skill) -> logic::CharacterSkill {
+        logic::CharacterSkill::new(&skill.code, self.ranks, self.is_class_skill)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feat_prerequisites_are_validated() {
+        let mut feat = Feat {
+            id: None,
+            code: Some("weapon-focus".to_string()),
+            world_id: None,
+            name: "Weapon Focus".to_string(),
+            description: "You are skilled with one type of weapon.".to_string(),
+            prerequisites: Some("level 1, class soldier".to_string()),
+            benefits: "+1 to attack rolls.".to_string(),
+            special: None,
+            source: "core".to_string(),
+        };
+        assert!(feat.is_valid());
+
+        feat.prerequisites = Some("level one".to_string());
+        assert_eq!(
+            feat.validate().unwrap_err(),
+            vec!["Prerequisites: invalid prerequisite 'level one': 'one' is not a number"]
+        );
+    }
+
+    #[test]
+    fn test_skill_converts_to_logic() {
+        let skill = Skill {
+            id: Some(1),
+            code: Some("piloting".to_string()),
+            world_id: Some(1),
+            name: "Piloting".to_string(),
+            description: "Fly starships and drive vehicles.".to_string(),
+            ability: "DEX".to_string(),
+            trained_only: false,
+            armor_penalty: true,
+            source: "core".to_string(),
+        };
+
+        let logic_skill = logic::Skill::try_from(&skill).unwrap();
+        assert_eq!(logic_skill.ability, Ability::Dexterity);
+        assert!(logic_skill.armor_penalty);
+
+        let ranks = CharacterSkill {
+            skill_id: 1,
+            is_class_skill: true,
+            ranks: 2,
+        }
+        .to_logic(&logic_skill);
+        assert_eq!(ranks, logic::CharacterSkill::new("piloting", 2, true));
+
+        let unknown = Skill {
+            ability: "luck".to_string(),
+            ..skill
+        };
+        assert_eq!(
+            logic::Skill::try_from(&unknown).unwrap_err(),
+            "skill piloting uses an unknown ability 'luck'"
+        );
+    }
+}
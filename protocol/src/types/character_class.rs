@@ -1,3 +1,4 @@
+use logic::validate_character_expression;
 use serde::{Deserialize, Serialize};
 
 use super::{TypeSignature, Valid};
@@ -62,10 +63,22 @@ impl Valid for CharacterClass {
 
         if self.stamina_expression.is_empty() {
             errors.push("Stamina Expression is required".to_string());
+        } else if let Err(expression_errors) =
+            validate_character_expression(&self.stamina_expression)
+        {
+            for error in expression_errors {
+                errors.push(format!("Stamina Expression: {}", error));
+            }
         }
 
         if self.skillpoint_expression.is_empty() {
             errors.push("Skillpoint Expression is required".to_string());
+        } else if let Err(expression_errors) =
+            validate_character_expression(&self.skillpoint_expression)
+        {
+            for error in expression_errors {
+                errors.push(format!("Skillpoint Expression: {}", error));
+            }
         }
 
         if let Some(proficiencies) = &self.proficiencies {
@@ -121,6 +134,34 @@ impl Valid for CharacterClassFeature {
     }
 }
 
+impl TryFrom<&CharacterClass> for logic::CharacterClass {
+    type Error = Vec<String>;
+
+    fn try_from(character_class: &CharacterClass) -> Result<Self, Self::Error> {
+        character_class.validate()?;
+
+        let mut class = logic::CharacterClass::new(
+            character_class.code.as_deref().unwrap_or_default(),
+            &character_class.name,
+            character_class.hit_points,
+            &character_class.stamina_expression,
+            &character_class.skillpoint_expression,
+        )
+        .map_err(|e| vec![e.to_string()])?;
+
+        for feature in character_class.features.iter().flatten() {
+            class = class.with_feature(logic::ClassFeature {
+                level: feature.level,
+                code: feature.code.clone(),
+                name: feature.name.clone(),
+                description: feature.description.clone(),
+            });
+        }
+
+        Ok(class)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,5 +233,33 @@ mod tests {
 
         let mechanic: CharacterClass = serde_json::from_str(mechanic_json).unwrap();
         assert!(mechanic.is_valid());
+
+        let logic_mechanic = logic::CharacterClass::try_from(&mechanic).unwrap();
+        assert_eq!(logic_mechanic.code, "mechanic");
+        assert_eq!(logic_mechanic.features_at(1).count(), 2);
+    }
+
+    #[test]
+    fn test_character_class_with_unknown_expression_variable() {
+        let class = CharacterClass {
+            id: None,
+            world_id: Some(1),
+            code: Some("soldier".to_string()),
+            name: "Soldier".to_string(),
+            description: "A trained fighter.".to_string(),
+            hit_points: 7,
+            stamina_expression: "7 + CNO".to_string(),
+            skillpoint_expression: "4 + INT".to_string(),
+            proficiencies: None,
+            features: Some(vec![]),
+        };
+
+        let errors = class.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "Stamina Expression: invalid expression: unknown variable 'CNO', did you mean 'CON'? at position 4"
+            ]
+        );
     }
 }
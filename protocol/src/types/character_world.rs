@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use logic::{AbilityScores as _, CharacterIdentity};
+
+use super::{AbilityScores, Character, CharacterSkill, Feat, Skill};
+
+/// ## ReferenceError
+/// Something a character refers to by code or id that the world does not have, or a value
+/// that does not fit the other side of a conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceError {
+    Race(String),
+    Class(String),
+    Theme(String),
+    Feat(String),
+    Skill(String),
+    SkillId(i64),
+    /// A value that cannot be converted, e.g. experience too large for the wire format.
+    Value(String),
+}
+
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReferenceError::Race(code) => write!(f, "race '{}' does not exist", code),
+            ReferenceError::Class(code) => write!(f, "class '{}' does not exist", code),
+            ReferenceError::Theme(code) => write!(f, "theme '{}' does not exist", code),
+            ReferenceError::Feat(code) => write!(f, "feat '{}' does not exist", code),
+            ReferenceError::Skill(code) => write!(f, "skill '{}' does not exist", code),
+            ReferenceError::SkillId(id) => write!(f, "skill {} does not exist", id),
+            ReferenceError::Value(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// ## CharacterWorld
+/// The races, classes, themes, feats and skills of a world that characters refer to by code.
+#[derive(Debug, Clone, Default)]
+pub struct CharacterWorld {
+    pub races: HashMap<String, logic::Race>,
+    pub classes: HashMap<String, logic::CharacterClass>,
+    pub themes: HashMap<String, logic::Theme>,
+    pub feats: HashMap<String, Feat>,
+    pub skills: Vec<Skill>,
+}
+
+impl CharacterWorld {
+    pub fn with_race(mut self, race: logic::Race) -> Self {
+        self.races.insert(race.code.clone(), race);
+        self
+    }
+
+    pub fn with_class(mut self, class: logic::CharacterClass) -> Self {
+        self.classes.insert(class.code.clone(), class);
+        self
+    }
+
+    pub fn with_theme(mut self, theme: logic::Theme) -> Self {
+        self.themes.insert(theme.code.clone(), theme);
+        self
+    }
+
+    /// Add a feat.  Feats without a code cannot be referred to and are ignored.
+    pub fn with_feat(mut self, feat: Feat) -> Self {
+        if let Some(code) = feat.code.clone() {
+            self.feats.insert(code, feat);
+        }
+        self
+    }
+
+    pub fn with_skill(mut self, skill: Skill) -> Self {
+        self.skills.push(skill);
+        self
+    }
+
+    /// ## to_logic
+    /// Convert a wire character into one the rules can use.  The same as
+    /// `logic::Character::try_from(WorldCharacter { character, world })`.
+    pub fn to_logic(&self, character: &Character) -> Result<logic::Character, Vec<ReferenceError>> {
+        logic::Character::try_from(WorldCharacter {
+            character,
+            world: self,
+        })
+    }
+
+    /// ## to_protocol
+    /// Convert a character back to the wire format.  The same as
+    /// `Character::try_from((character, world))`.
+    pub fn to_protocol(
+        &self,
+        character: &logic::Character,
+    ) -> Result<Character, Vec<ReferenceError>> {
+        Character::try_from((character, self))
+    }
+}
+
+/// ## WorldCharacter
+/// A wire character with the world it belongs to, so that its references can be resolved.
+pub struct WorldCharacter<'a> {
+    pub character: &'a Character,
+    pub world: &'a CharacterWorld,
+}
+
+impl TryFrom<WorldCharacter<'_>> for logic::Character {
+    type Error = Vec<ReferenceError>;
+
+    fn try_from(value: WorldCharacter<'_>) -> Result<Self, Self::Error> {
+        let WorldCharacter { character, world } = value;
+        let mut errors = vec![];
+
+        let race = world.races.get(&character.race);
+        if race.is_none() {
+            errors.push(ReferenceError::Race(character.race.clone()));
+        }
+        let class = world.classes.get(&character.class);
+        if class.is_none() {
+            errors.push(ReferenceError::Class(character.class.clone()));
+        }
+        // a character does not need a theme, but one they have must exist
+        let theme = world.themes.get(&character.theme);
+        if theme.is_none() && !character.theme.is_empty() {
+            errors.push(ReferenceError::Theme(character.theme.clone()));
+        }
+
+        let mut feats = vec![];
+        for feat in &character.feats {
+            match &feat.code {
+                Some(code) if world.feats.contains_key(code) => feats.push(code.clone()),
+                Some(code) => errors.push(ReferenceError::Feat(code.clone())),
+                None => errors.push(ReferenceError::Feat(feat.name.clone())),
+            }
+        }
+
+        let mut skills = vec![];
+        for skill in &character.skills {
+            let code = world
+                .skills
+                .iter()
+                .find(|world_skill| world_skill.id == Some(skill.skill_id))
+                .and_then(|world_skill| world_skill.code.clone());
+            match code {
+                Some(code) => skills.push(logic::CharacterSkill::new(
+                    &code,
+                    skill.ranks,
+                    skill.is_class_skill,
+                )),
+                None => errors.push(ReferenceError::SkillId(skill.skill_id)),
+            }
+        }
+
+        let (race, class) = match (race, class) {
+            (Some(race), Some(class)) if errors.is_empty() => (race, class),
+            _ => return Err(errors),
+        };
+
+        let abilities = &character.abilities;
+        let mut converted = logic::Character::new(
+            character.name.clone(),
+            character.level,
+            class.code.clone(),
+            race.clone(),
+            abilities.strength,
+            abilities.dexterity,
+            abilities.constitution,
+            abilities.intelligence,
+            abilities.wisdom,
+            abilities.charisma,
+        );
+        converted.set_identity(CharacterIdentity {
+            id: character.id,
+            code: character.code.clone(),
+            world_id: character.world_id,
+            user_id: character.user_id,
+        });
+        match theme {
+            Some(theme) => converted.apply_theme(theme),
+            None => converted.set_theme(&character.theme),
+        }
+        converted.restore(
+            character.experience as i64,
+            character.hit_points,
+            character.stamina,
+        );
+        converted.grant_class_features(class);
+        feats.iter().for_each(|feat| converted.add_feat(feat));
+        skills
+            .into_iter()
+            .for_each(|skill| converted.set_skill(skill));
+        converted.set_conditions(character.conditions.clone());
+
+        Ok(converted)
+    }
+}
+
+impl TryFrom<(&logic::Character, &CharacterWorld)> for Character {
+    type Error = Vec<ReferenceError>;
+
+    fn try_from(value: (&logic::Character, &CharacterWorld)) -> Result<Self, Self::Error> {
+        let (character, world) = value;
+        let mut errors = vec![];
+
+        if !world.races.contains_key(&character.race().code) {
+            errors.push(ReferenceError::Race(character.race().code.clone()));
+        }
+        if !world.classes.contains_key(character.class()) {
+            errors.push(ReferenceError::Class(character.class().to_string()));
+        }
+        if !character.theme().is_empty() && !world.themes.contains_key(character.theme()) {
+            errors.push(ReferenceError::Theme(character.theme().to_string()));
+        }
+
+        let mut feats = vec![];
+        for code in character.feats() {
+            match world.feats.get(code) {
+                Some(feat) => feats.push(feat.clone()),
+                None => errors.push(ReferenceError::Feat(code.clone())),
+            }
+        }
+
+        let mut skills = vec![];
+        for skill in character.skills() {
+            let id = world
+                .skills
+                .iter()
+                .find(|world_skill| world_skill.code.as_deref() == Some(skill.skill_code.as_str()))
+                .and_then(|world_skill| world_skill.id);
+            match id {
+                Some(skill_id) => skills.push(CharacterSkill {
+                    skill_id,
+                    is_class_skill: skill.is_class_skill,
+                    ranks: skill.ranks,
+                }),
+                None => errors.push(ReferenceError::Skill(skill.skill_code.clone())),
+            }
+        }
+
+        let experience = match i32::try_from(character.experience()) {
+            Ok(experience) => experience,
+            Err(_) => {
+                errors.push(ReferenceError::Value(format!(
+                    "experience {} is too large",
+                    character.experience()
+                )));
+                0
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let identity = character.identity();
+        Ok(Character {
+            id: identity.id,
+            code: identity.code.clone(),
+            world_id: identity.world_id,
+            user_id: identity.user_id,
+            name: character.name().to_string(),
+            race: character.race().code.clone(),
+            class: character.class().to_string(),
+            theme: character.theme().to_string(),
+            level: character.level(),
+            experience,
+            hit_points: character.hit_points(),
+            stamina: character.stamina(),
+            abilities: AbilityScores {
+                strength: character.get_raw_ability_score(logic::Ability::Strength),
+                dexterity: character.get_raw_ability_score(logic::Ability::Dexterity),
+                constitution: character.get_raw_ability_score(logic::Ability::Constitution),
+                intelligence: character.get_raw_ability_score(logic::Ability::Intelligence),
+                wisdom: character.get_raw_ability_score(logic::Ability::Wisdom),
+                charisma: character.get_raw_ability_score(logic::Ability::Charisma),
+            },
+            feats,
+            skills,
+            conditions: character.conditions().clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> CharacterWorld {
+        let races = logic::load_default_races();
+        let mechanic = logic::CharacterClass::new("mechanic", "Mechanic", 6, "10 + CON", "4 + INT")
+            .unwrap()
+            .with_feature(logic::ClassFeature {
+                level: 1,
+                code: "mechanic-customrig".to_string(),
+                name: "Custom Rig".to_string(),
+                description: "You have a custom rig for your tools.".to_string(),
+            });
+
+        CharacterWorld::default()
+            .with_race(races["Dwarf"].clone())
+            .with_class(mechanic)
+            .with_theme(
+                logic::Theme::new("ace-pilot", "Ace Pilot")
+                    .with_ability_bonus(logic::Ability::Dexterity, 1),
+            )
+            .with_feat(Feat {
+                id: Some(4),
+                code: Some("skill-focus".to_string()),
+                world_id: Some(1),
+                name: "Skill Focus".to_string(),
+                description: "Choose a skill.".to_string(),
+                prerequisites: None,
+                benefits: "+3 to the skill.".to_string(),
+                special: None,
+                source: "core".to_string(),
+            })
+            .with_skill(Skill {
+                id: Some(7),
+                code: Some("engineering".to_string()),
+                world_id: Some(1),
+                name: "Engineering".to_string(),
+                description: "Build and repair machines.".to_string(),
+                ability: "INT".to_string(),
+                trained_only: true,
+                armor_penalty: false,
+                source: "core".to_string(),
+            })
+    }
+
+    fn character() -> Character {
+        let world = world();
+        Character {
+            id: Some(12),
+            code: Some("kira".to_string()),
+            world_id: Some(1),
+            user_id: Some(3),
+            name: "Kira".to_string(),
+            race: "dwarf".to_string(),
+            class: "mechanic".to_string(),
+            theme: "ace-pilot".to_string(),
+            level: 2,
+            experience: 1500,
+            hit_points: 12,
+            stamina: 22,
+            abilities: AbilityScores {
+                strength: 10,
+                dexterity: 12,
+                constitution: 14,
+                intelligence: 16,
+                wisdom: 10,
+                charisma: 8,
+            },
+            feats: vec![world.feats["skill-focus"].clone()],
+            skills: vec![CharacterSkill {
+                skill_id: 7,
+                is_class_skill: true,
+                ranks: 2,
+            }],
+            conditions: logic::Conditions::default(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        let world = world();
+        let mut original = character();
+        original
+            .conditions
+            .apply(&logic::load_default_conditions()["prone"]);
+
+        let converted = world.to_logic(&original).unwrap();
+        assert_eq!(converted.race().name, "Dwarf");
+        assert!(converted.has_feat("skill-focus"));
+        assert_eq!(converted.skill("engineering").unwrap().ranks, 2);
+        assert_eq!(converted.features().len(), 1);
+        assert!(converted.conditions().has("prone"));
+        assert_eq!(converted.get_ability_score(logic::Ability::Dexterity), 13);
+
+        let back = world.to_protocol(&converted).unwrap();
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_references_are_reported() {
+        let mut missing = character();
+        missing.race = "vesk".to_string();
+        missing.class = "solarian".to_string();
+        missing.theme = "xenoseeker".to_string();
+        missing.skills[0].skill_id = 99;
+        missing.feats[0].code = Some("toughness".to_string());
+
+        let errors = match world().to_logic(&missing) {
+            Ok(_) => panic!("expected the references to be missing"),
+            Err(errors) => errors,
+        };
+        assert_eq!(
+            errors,
+            vec![
+                ReferenceError::Race("vesk".to_string()),
+                ReferenceError::Class("solarian".to_string()),
+                ReferenceError::Theme("xenoseeker".to_string()),
+                ReferenceError::Feat("toughness".to_string()),
+                ReferenceError::SkillId(99),
+            ]
+        );
+        assert_eq!(errors[0].to_string(), "race 'vesk' does not exist");
+    }
+
+    #[test]
+    fn test_unknown_codes_are_reported_going_back() {
+        let world = world();
+        let mut converted = world.to_logic(&character()).unwrap();
+        converted.add_feat("toughness");
+        converted.set_skill(logic::CharacterSkill::new("piloting", 1, false));
+
+        match world.to_protocol(&converted) {
+            Ok(_) => panic!("expected the references to be missing"),
+            Err(errors) => assert_eq!(
+                errors,
+                vec![
+                    ReferenceError::Feat("toughness".to_string()),
+                    ReferenceError::Skill("piloting".to_string()),
+                ]
+            ),
+        }
+    }
+}
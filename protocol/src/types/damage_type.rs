@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use super::{TypeSignature, Valid};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageType {
+    pub id: Option<i64>,
+    pub world_id: Option<i64>,
+    pub code: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl TypeSignature for DamageType {
+    fn signature(&self) -> Vec<u8> {
+        let mut signature = Vec::new();
+        signature.extend_from_slice(self.code.clone().unwrap_or("".to_string()).as_bytes());
+        signature.extend_from_slice(self.name.as_bytes());
+        signature.extend_from_slice(self.description.as_bytes());
+
+        Self::as_hashed(signature)
+    }
+}
+
+impl Valid for DamageType {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+
+        if self.code.clone().unwrap_or_default().is_empty() {
+            errors.push("Code is required".to_string());
+        }
+
+        if self.name.is_empty() {
+            errors.push("Name is required".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl TryFrom<&DamageType> for logic::DamageType {
+    type Error = Vec<String>;
+
+    fn try_from(damage_type: &DamageType) -> Result<Self, Self::Error> {
+        damage_type.validate()?;
+
+        Ok(logic::DamageType {
+            code: damage_type.code.clone().unwrap_or_default(),
+            name: damage_type.name.clone(),
+            description: damage_type.description.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_type_converts_to_logic() {
+        let mut energy: DamageType = serde_json::from_str(
+            r#"{
+                "code": "energy",
+                "name": "Energy",
+                "description": "Focused beams and blasts of light and heat."
+            }"#,
+        )
+        .unwrap();
+
+        let damage_type = logic::DamageType::try_from(&energy).unwrap();
+        assert_eq!(damage_type.code, "energy");
+
+        energy.code = None;
+        energy.name.clear();
+        assert_eq!(
+            logic::DamageType::try_from(&energy).unwrap_err(),
+            vec!["Code is required", "Name is required"]
+        );
+    }
+}
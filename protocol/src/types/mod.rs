@@ -1,15 +1,23 @@
 pub mod business_logic;
 pub mod character;
 pub mod character_class;
+pub mod character_world;
+pub mod damage_type;
 pub mod item;
+pub mod race;
 pub mod signature;
+pub mod theme;
 pub mod valid;
 pub mod world;
 
 pub use business_logic::*;
 pub use character::*;
 pub use character_class::*;
+pub use character_world::*;
+pub use damage_type::*;
 pub use item::*;
+pub use race::*;
 pub use signature::*;
+pub use theme::*;
 pub use valid::*;
 pub use world::*;
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, HashMap};
+
+use logic::{Ability, SpecialAbility, SpecialAbilityEffect};
+use serde::{Deserialize, Serialize};
+
+use super::{TypeSignature, Valid};
+
+fn default_speed() -> i32 {
+    30
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Race {
+    pub id: Option<i64>,
+    pub world_id: Option<i64>,
+    pub code: Option<String>,
+    pub name: String,
+    pub description: String,
+    /// The modifier to each ability, by name, e.g. `{ "constitution": 2 }`.
+    #[serde(default)]
+    pub ability_modifiers: BTreeMap<String, i32>,
+    #[serde(default = "default_speed")]
+    pub speed: i32,
+    #[serde(default)]
+    pub hit_points: i32,
+    /// Each special ability, either its name or a definition with the effects the rules apply.
+    #[serde(default)]
+    pub special_abilities: Vec<SpecialAbility>,
+}
+
+impl TypeSignature for Race {
+    fn signature(&self) -> Vec<u8> {
+        let mut signature = Vec::new();
+        signature.extend_from_slice(self.code.clone().unwrap_or("".to_string()).as_bytes());
+        signature.extend_from_slice(self.name.as_bytes());
+        signature.extend_from_slice(self.description.as_bytes());
+        for (ability, modifier) in &self.ability_modifiers {
+            signature.extend_from_slice(ability.as_bytes());
+            signature.extend_from_slice(&modifier.to_be_bytes());
+        }
+        signature.extend_from_slice(&self.speed.to_be_bytes());
+        signature.extend_from_slice(&self.hit_points.to_be_bytes());
+        for special_ability in &self.special_abilities {
+            signature.extend_from_slice(
+                serde_json::to_string(special_ability)
+                    .unwrap_or_default()
+                    .as_bytes(),
+            );
+        }
+
+        Self::as_hashed(signature)
+    }
+}
+
+impl Valid for Race {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+
+        if self.code.clone().unwrap_or_default().is_empty() {
+            errors.push("Code is required".to_string());
+        }
+
+        if self.name.is_empty() {
+            errors.push("Name is required".to_string());
+        }
+
+        if self.description.is_empty() {
+            errors.push("Description is required".to_string());
+        }
+
+        for ability in self.ability_modifiers.keys() {
+            if Ability::from_name(ability).is_none() {
+                errors.push(format!("Ability Modifiers: unknown ability '{}'", ability));
+            }
+        }
+
+        if self.speed < 0 {
+            errors.push("Speed cannot be negative".to_string());
+        }
+
+        if self.hit_points < 0 {
+            errors.push("Hit Points cannot be negative".to_string());
+        }
+
+        for special_ability in &self.special_abilities {
+            if special_ability.name.is_empty() {
+                errors.push("Special Abilities cannot be empty".to_string());
+            }
+            for effect in &special_ability.effects {
+                match effect {
+                    SpecialAbilityEffect::Sense { range, .. } if *range < 0 => {
+                        errors.push(format!(
+                            "Special Abilities: {} has a negative range",
+                            special_ability.name
+                        ))
+                    }
+                    SpecialAbilityEffect::SaveBonus { against, .. } if against.is_empty() => errors
+                        .push(format!(
+                            "Special Abilities: {} needs what its save bonus is against",
+                            special_ability.name
+                        )),
+                    SpecialAbilityEffect::SkillBonus { skill, .. } if skill.is_empty() => errors
+                        .push(format!(
+                            "Special Abilities: {} needs a skill for its bonus",
+                            special_ability.name
+                        )),
+                    SpecialAbilityEffect::Proficiency { proficiency } if proficiency.is_empty() => {
+                        errors.push(format!(
+                            "Special Abilities: {} has an empty proficiency",
+                            special_ability.name
+                        ))
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl TryFrom<&Race> for logic::Race {
+    type Error = Vec<String>;
+
+    fn try_from(race: &Race) -> Result<Self, Self::Error> {
+        race.validate()?;
+
+        let ability_modifiers: HashMap<Ability, i32> = race
+            .ability_modifiers
+            .iter()
+            .filter_map(|(ability, modifier)| {
+                Ability::from_name(ability).map(|ability| (ability, *modifier))
+            })
+            .collect();
+
+        Ok(logic::Race::new(
+            &race.name,
+            ability_modifiers,
+            race.special_abilities.clone(),
+            race.speed,
+        )
+        .with_code(race.code.as_deref().unwrap_or_default())
+        .with_hit_points(race.hit_points))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vesk() -> Race {
+        serde_json::from_str(
+            r#"{
+                "code": "vesk",
+                "name": "Vesk",
+                "description": "Reptilian conquerors from the Veskarium.",
+                "ability_modifiers": { "strength": 2, "constitution": 2, "intelligence": -2 },
+                "hit_points": 6,
+                "special_abilities": [
+                    "Armor Savant",
+                    {
+                        "name": "Low-Light Vision",
+                        "effects": [{ "effect": "sense", "sense": "low_light_vision", "range": 60 }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_race_converts_to_logic() {
+        let race = logic::Race::try_from(&vesk()).unwrap();
+
+        assert_eq!(race.code, "vesk");
+        assert_eq!(race.speed, 30);
+        assert_eq!(race.hit_points, 6);
+        assert_eq!(race.ability_modifiers[&Ability::Intelligence], -2);
+        assert_eq!(race.senses(), vec![(logic::Sense::LowLightVision, 60)]);
+    }
+
+    #[test]
+    fn test_race_with_unknown_ability() {
+        let mut race = vesk();
+        race.ability_modifiers.insert("luck".to_string(), 1);
+
+        assert_eq!(
+            race.validate().unwrap_err(),
+            vec!["Ability Modifiers: unknown ability 'luck'"]
+        );
+        assert!(logic::Race::try_from(&race).is_err());
+    }
+}
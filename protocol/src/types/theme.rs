@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashMap};
+
+use logic::Ability;
+use serde::{Deserialize, Serialize};
+
+use super::{TypeSignature, Valid};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub id: Option<i64>,
+    pub world_id: Option<i64>,
+    pub code: Option<String>,
+    pub name: String,
+    pub description: String,
+    /// The bonus to each ability, by name, e.g. `{ "dexterity": 1 }`.
+    #[serde(default)]
+    pub ability_bonuses: BTreeMap<String, i32>,
+    pub features: Option<Vec<ThemeFeature>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeFeature {
+    pub level: i32,
+    pub code: String,
+    pub name: String,
+    pub description: String,
+}
+
+impl TypeSignature for Theme {
+    fn signature(&self) -> Vec<u8> {
+        let mut signature = Vec::new();
+        signature.extend_from_slice(self.code.clone().unwrap_or("".to_string()).as_bytes());
+        signature.extend_from_slice(self.name.as_bytes());
+        signature.extend_from_slice(self.description.as_bytes());
+        for (ability, bonus) in &self.ability_bonuses {
+            signature.extend_from_slice(ability.as_bytes());
+            signature.extend_from_slice(&bonus.to_be_bytes());
+        }
+        if let Some(features) = &self.features {
+            for feature in features {
+                signature.extend_from_slice(&feature.level.to_be_bytes());
+                signature.extend_from_slice(feature.code.as_bytes());
+                signature.extend_from_slice(feature.name.as_bytes());
+                signature.extend_from_slice(feature.description.as_bytes());
+            }
+        }
+
+        Self::as_hashed(signature)
+    }
+}
+
+impl Valid for Theme {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+
+        if self.code.clone().unwrap_or_default().is_empty() {
+            errors.push("Code is required".to_string());
+        }
+
+        if self.name.is_empty() {
+            errors.push("Name is required".to_string());
+        }
+
+        if self.description.is_empty() {
+            errors.push("Description is required".to_string());
+        }
+
+        for ability in self.ability_bonuses.keys() {
+            if Ability::from_name(ability).is_none() {
+                errors.push(format!("Ability Bonuses: unknown ability '{}'", ability));
+            }
+        }
+
+        if let Some(features) = &self.features {
+            for feature in features {
+                if let Err(feature_errors) = feature.validate() {
+                    errors.push(format!(
+                        "Feature {}: {}",
+                        feature.code,
+                        feature_errors.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Valid for ThemeFeature {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = vec![];
+
+        if self.level <= 0 {
+            errors.push("Level must be greater than 0".to_string());
+        }
+
+        if self.code.is_empty() {
+            errors.push("Code is required".to_string());
+        }
+
+        if self.name.is_empty() {
+            errors.push("Name is required".to_string());
+        }
+
+        if self.description.is_empty() {
+            errors.push("Description is required".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl TryFrom<&Theme> for logic::Theme {
+    type Error = Vec<String>;
+
+    fn try_from(theme: &Theme) -> Result<Self, Self::Error> {
+        theme.validate()?;
+
+        let ability_bonuses: HashMap<Ability, i32> = theme
+            .ability_bonuses
+            .iter()
+            .filter_map(|(ability, bonus)| Ability::from_name(ability).map(|a| (a, *bonus)))
+            .collect();
+
+        Ok(logic::Theme {
+            code: theme.code.clone().unwrap_or_default(),
+            name: theme.name.clone(),
+            ability_bonuses,
+            features: theme
+                .features
+                .iter()
+                .flatten()
+                .map(|feature| logic::ThemeFeature {
+                    level: feature.level,
+                    code: feature.code.clone(),
+                    name: feature.name.clone(),
+                    description: feature.description.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ace_pilot() -> Theme {
+        serde_json::from_str(
+            r#"{
+                "code": "ace-pilot",
+                "name": "Ace Pilot",
+                "description": "You are at home behind the controls of any vehicle.",
+                "ability_bonuses": { "dexterity": 1 },
+                "features": [
+                    {
+                        "level": 1,
+                        "code": "ace-pilot-theme-knowledge",
+                        "name": "Theme Knowledge",
+                        "description": "You know starships and vehicles."
+                    },
+                    {
+                        "level": 6,
+                        "code": "ace-pilot-lone-wolf",
+                        "name": "Lone Wolf",
+                        "description": "You can pilot a starship alone."
+                    }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_theme_converts_to_logic() {
+        let theme = logic::Theme::try_from(&ace_pilot()).unwrap();
+
+        assert_eq!(theme.code, "ace-pilot");
+        assert_eq!(theme.ability_bonuses[&Ability::Dexterity], 1);
+        assert_eq!(theme.features_up_to(5).count(), 1);
+    }
+
+    #[test]
+    fn test_theme_validation() {
+        let mut theme = ace_pilot();
+        assert!(theme.validate().is_ok());
+
+        theme.ability_bonuses.insert("luck".to_string(), 1);
+        theme.features.as_mut().unwrap()[1].level = 0;
+        assert_eq!(
+            theme.validate().unwrap_err(),
+            vec![
+                "Ability Bonuses: unknown ability 'luck'",
+                "Feature ace-pilot-lone-wolf: Level must be greater than 0"
+            ]
+        );
+    }
+}
@@ -233,7 +233,9 @@ fn get_character_class_and_features(
     Ok(Some(protocol_character_class))
 }
 
-fn protocol_character_class_to_entity(character: &ProtocolCharacterClass) -> CharacterClass {
+pub(crate) fn protocol_character_class_to_entity(
+    character: &ProtocolCharacterClass,
+) -> CharacterClass {
     CharacterClass {
         id: character.id.unwrap_or(0),
         world_id: character.world_id.unwrap_or(0),
@@ -249,7 +251,7 @@ fn protocol_character_class_to_entity(character: &ProtocolCharacterClass) -> Cha
     }
 }
 
-fn protocol_character_class_feature_to_entity(
+pub(crate) fn protocol_character_class_feature_to_entity(
     feature: &ProtocolCharacterClassFeature,
 ) -> CharacterClassFeature {
     CharacterClassFeature {
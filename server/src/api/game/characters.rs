@@ -0,0 +1,744 @@
+use crate::api::{ApiResponse, Payload};
+use crate::app_state::AppState;
+use crate::game::{Feat, Race, Skill, World};
+use crate::player::{Character, NewCharacter};
+use crate::{
+    CharacterClassFeatureRepository, CharacterClassRepository, CharacterRepository, FeatRepository,
+    RaceRepository, SkillRepository, ThemeFeatureRepository, ThemeRepository, WorldRepository,
+};
+use axum::extract::{Path, State};
+use axum::Json;
+use diesel::PgConnection;
+use logic::progression::{HitPointMethod, LevelGain, Vitals};
+use logic::Ability;
+use protocol::types::{
+    AbilityScores, Character as ProtocolCharacter, CharacterSkill as ProtocolCharacterSkill,
+    CharacterWorld, Feat as ProtocolFeat, ReferenceError, Skill as ProtocolSkill,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// ## CharacterDefinition
+/// The fields a player chooses when creating a character. Hit points and stamina are
+/// derived from the class and race, never supplied by the caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterDefinition {
+    pub user_id: Uuid,
+    pub race_code: String,
+    pub name: String,
+    pub class: String,
+    pub theme: String,
+    pub level: Option<i32>,
+    pub experience: Option<i64>,
+    pub abilities: AbilityScores,
+}
+
+/// ## CharacterSheet
+/// A stored character with the maximums derived from their class, race and ability scores.
+/// The stored hit points and stamina are their current values, which may be below these.
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterSheet {
+    #[serde(flatten)]
+    pub character: Character,
+    pub vitals: Vitals,
+}
+
+pub async fn create_character(
+    State(state): State<AppState>,
+    Path(world_code): Path<String>,
+    Json(definition): Json<CharacterDefinition>,
+) -> ApiResponse<Character> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let race = match RaceRepository::find_by_code(&mut conn, world.id, &definition.race_code) {
+        Ok(race) => race,
+        Err(diesel::result::Error::NotFound) => {
+            return ApiResponse::BadRequest(vec![format!(
+                "race '{}' does not exist",
+                definition.race_code
+            )])
+        }
+        Err(e) => {
+            eprintln!("Failed to get race: {}", e);
+            return ApiResponse::Error("Failed to get race".to_string());
+        }
+    };
+
+    let (level, experience) = match starting_progression(
+        &world.experience_table(),
+        definition.level,
+        definition.experience,
+    ) {
+        Ok(progression) => progression,
+        Err(errors) => return ApiResponse::BadRequest(errors),
+    };
+
+    let abilities = match ability_scores(&world, &definition.abilities) {
+        Ok(abilities) => abilities,
+        Err(e) => return e.into(),
+    };
+
+    let character_world = match load_character_world(
+        &mut conn,
+        &world,
+        &race,
+        &definition.class,
+        &definition.theme,
+        &[],
+        &[],
+    ) {
+        Ok(character_world) => character_world,
+        Err(e) => return e.into(),
+    };
+    let wire_character = ProtocolCharacter {
+        id: None,
+        code: None,
+        world_id: Some(world.id),
+        user_id: None,
+        name: definition.name.clone(),
+        race: race.code.clone(),
+        class: definition.class.clone(),
+        theme: definition.theme.clone(),
+        level,
+        experience: match i32::try_from(experience) {
+            Ok(experience) => experience,
+            Err(_) => {
+                return ApiResponse::BadRequest(vec![format!(
+                    "experience {} is too large",
+                    experience
+                )])
+            }
+        },
+        hit_points: 0,
+        stamina: 0,
+        abilities: abilities.clone(),
+        feats: vec![],
+        skills: vec![],
+        conditions: logic::Conditions::default(),
+    };
+    let candidate = match convert_character(&character_world, &wire_character, vec![]) {
+        Ok(candidate) => candidate,
+        Err(e) => return e.into(),
+    };
+    let level_gains = match roll_level_gains(&character_world, &candidate) {
+        Ok(level_gains) => level_gains,
+        Err(e) => return e.into(),
+    };
+    let vitals = match calculate_vitals(&character_world, &candidate, &level_gains) {
+        Ok(vitals) => vitals,
+        Err(e) => return e.into(),
+    };
+
+    let new_character = NewCharacter {
+        world_id: world.id,
+        user_id: definition.user_id,
+        race_id: race.id,
+        name: definition.name.clone(),
+        class: definition.class.clone(),
+        theme: definition.theme.clone(),
+        level,
+        experience,
+        hit_points: vitals.max_hit_points.total,
+        stamina: vitals.max_stamina.total,
+        abilities: serde_json::to_value(&abilities).unwrap_or_default(),
+        feats: serde_json::json!([]),
+        skills: serde_json::json!([]),
+        level_gains: serde_json::to_value(&level_gains).unwrap_or_default(),
+        conditions: serde_json::to_value(candidate.conditions()).unwrap_or_default(),
+    };
+
+    match CharacterRepository::create(&mut conn, &new_character) {
+        Ok(character) => ApiResponse::Created(Payload { data: character }),
+        Err(e) => {
+            eprintln!("Failed to create character: {}", e);
+            ApiResponse::Error("Failed to create character".to_string())
+        }
+    }
+}
+
+pub async fn get_character(
+    State(state): State<AppState>,
+    Path((world_code, character_id)): Path<(String, i64)>,
+) -> ApiResponse<CharacterSheet> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let character = match CharacterRepository::find_by_id(&mut conn, world.id, character_id) {
+        Ok(character) => character,
+        Err(diesel::result::Error::NotFound) => {
+            return ApiResponse::NotFound("character not found".to_string())
+        }
+        Err(e) => {
+            eprintln!("Failed to get character: {}", e);
+            return ApiResponse::Error("Failed to get character".to_string());
+        }
+    };
+
+    let (candidate, character_world) = match load_character(&mut conn, &world, &character) {
+        Ok(loaded) => loaded,
+        Err(e) => return e.into(),
+    };
+    let level_gains: Vec<LevelGain> =
+        match read_column(&character, "level_gains", &character.level_gains) {
+            Ok(level_gains) => level_gains,
+            Err(e) => return e.into(),
+        };
+    let vitals = match calculate_vitals(&character_world, &candidate, &level_gains) {
+        Ok(vitals) => vitals,
+        Err(e) => return e.into(),
+    };
+
+    ApiResponse::JsonData(Payload {
+        data: CharacterSheet { character, vitals },
+    })
+}
+
+pub async fn add_character_feat(
+    State(state): State<AppState>,
+    Path((world_code, character_id, feat_code)): Path<(String, i64, String)>,
+) -> ApiResponse<Character> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let character = match CharacterRepository::find_by_id(&mut conn, world.id, character_id) {
+        Ok(character) => character,
+        Err(diesel::result::Error::NotFound) => {
+            return ApiResponse::NotFound("character not found".to_string())
+        }
+        Err(e) => {
+            eprintln!("Failed to get character: {}", e);
+            return ApiResponse::Error("Failed to get character".to_string());
+        }
+    };
+
+    let feat = match FeatRepository::find_by_code(&mut conn, world.id, &feat_code) {
+        Ok(feat) => feat,
+        Err(diesel::result::Error::NotFound) => {
+            return ApiResponse::NotFound("feat not found".to_string())
+        }
+        Err(e) => {
+            eprintln!("Failed to get feat: {}", e);
+            return ApiResponse::Error("Failed to get feat".to_string());
+        }
+    };
+
+    let (candidate, _) = match load_character(&mut conn, &world, &character) {
+        Ok(loaded) => loaded,
+        Err(e) => return e.into(),
+    };
+    if candidate.has_feat(&feat.code) {
+        return ApiResponse::NotChanged;
+    }
+
+    let prerequisites = match feat_entity_to_protocol(&feat).parsed_prerequisites() {
+        Ok(prerequisites) => prerequisites,
+        Err(e) => {
+            return ApiResponse::BadRequest(vec![format!("feat '{}' has an {}", feat.code, e)])
+        }
+    };
+
+    if let Err(reasons) = prerequisites.check(&candidate) {
+        return ApiResponse::BadRequest(
+            reasons
+                .into_iter()
+                .map(|reason| format!("{} {}", feat.name, reason))
+                .collect(),
+        );
+    }
+
+    let mut feats = candidate.feats().to_vec();
+    feats.push(feat.code.clone());
+    match CharacterRepository::update_feats(&mut conn, character.id, serde_json::json!(feats)) {
+        Ok(character) => ApiResponse::JsonData(Payload { data: character }),
+        Err(e) => {
+            eprintln!("Failed to update character feats: {}", e);
+            ApiResponse::Error("Failed to update character feats".to_string())
+        }
+    }
+}
+
+/// Check a new character's ability scores against the world's method for generating them.
+/// Rolled scores are rolled here rather than taken from the player: the rolls are handed out
+/// in the order of the submitted scores, so the ability the player scored highest gets the
+/// highest roll.
+fn ability_scores(
+    world: &World,
+    submitted: &AbilityScores,
+) -> Result<AbilityScores, CharacterError> {
+    let method = match world.ability_score_method() {
+        Some(method) => method,
+        None => {
+            eprintln!(
+                "World {} has an unknown ability score method '{}'",
+                world.code, world.ability_score_method
+            );
+            return Err(CharacterError::Error(
+                "Failed to read the world's ability score method".to_string(),
+            ));
+        }
+    };
+
+    let submitted_scores = [
+        submitted.strength,
+        submitted.dexterity,
+        submitted.constitution,
+        submitted.intelligence,
+        submitted.wisdom,
+        submitted.charisma,
+    ];
+    let scores = match method {
+        logic::AbilityScoreMethod::FourDiceDropLowest => {
+            let mut rolls = method.score_pool(&mut rand::thread_rng());
+            rolls.sort_unstable_by(|a, b| b.cmp(a));
+            let mut order: Vec<usize> = (0..submitted_scores.len()).collect();
+            order.sort_by(|a, b| submitted_scores[*b].cmp(&submitted_scores[*a]));
+            let mut scores = [0; 6];
+            for (index, roll) in order.into_iter().zip(rolls) {
+                scores[index] = roll;
+            }
+            scores
+        }
+        _ => submitted_scores,
+    };
+
+    let assigned = Ability::all().into_iter().zip(scores).collect();
+    method
+        .validate(&assigned, &scores)
+        .map_err(CharacterError::BadRequest)?;
+
+    Ok(AbilityScores {
+        strength: scores[0],
+        dexterity: scores[1],
+        constitution: scores[2],
+        intelligence: scores[3],
+        wisdom: scores[4],
+        charisma: scores[5],
+    })
+}
+
+/// Work out a new character's level and experience from the world's experience table.  Either
+/// may be left out and follows from the other; when both are given they must agree.
+fn starting_progression(
+    table: &logic::ExperienceTable,
+    level: Option<i32>,
+    experience: Option<i64>,
+) -> Result<(i32, i64), Vec<String>> {
+    if let Some(experience) = experience {
+        if experience < 0 {
+            return Err(vec!["experience cannot be negative".to_string()]);
+        }
+    }
+
+    match (level, experience) {
+        (Some(level), _) if level < 1 || level > table.max_level() => Err(vec![format!(
+            "level must be between 1 and {}",
+            table.max_level()
+        )]),
+        (Some(level), Some(experience)) if table.level_for(experience) != level => {
+            Err(vec![format!(
+                "{} experience is level {}, not level {}",
+                experience,
+                table.level_for(experience),
+                level
+            )])
+        }
+        (Some(level), Some(experience)) => Ok((level, experience)),
+        (Some(level), None) => Ok((level, table.experience_for(level).unwrap_or(0))),
+        (None, Some(experience)) => Ok((table.level_for(experience), experience)),
+        (None, None) => Ok((1, table.experience_for(1).unwrap_or(0))),
+    }
+}
+
+/// Load a stored character as the rules see it, with the parts of the world it refers to.
+fn load_character(
+    conn: &mut PgConnection,
+    world: &World,
+    character: &Character,
+) -> Result<(logic::Character, CharacterWorld), CharacterError> {
+    let race = match RaceRepository::find_by_id(conn, character.race_id) {
+        Ok(race) => race,
+        Err(e) => {
+            eprintln!("Failed to get race: {}", e);
+            return Err(CharacterError::Error("Failed to get race".to_string()));
+        }
+    };
+    let abilities: AbilityScores = read_column(character, "abilities", &character.abilities)?;
+    let feats: Vec<String> = read_column(character, "feats", &character.feats)?;
+    let skills: Vec<logic::CharacterSkill> = read_column(character, "skills", &character.skills)?;
+    let conditions: logic::Conditions =
+        read_column(character, "conditions", &character.conditions)?;
+
+    let character_world = load_character_world(
+        conn,
+        world,
+        &race,
+        &character.class,
+        &character.theme,
+        &feats,
+        &skills,
+    )?;
+
+    // feats and skills the world no longer has are reported with the other references
+    let mut errors = vec![];
+    let wire_feats = feats
+        .iter()
+        .map(|code| match character_world.feats.get(code) {
+            Some(feat) => feat.clone(),
+            None => missing_feat(code),
+        })
+        .collect();
+    let mut wire_skills = vec![];
+    for skill in &skills {
+        let skill_id = character_world
+            .skills
+            .iter()
+            .find(|world_skill| world_skill.code.as_deref() == Some(skill.skill_code.as_str()))
+            .and_then(|world_skill| world_skill.id);
+        match skill_id {
+            Some(skill_id) => wire_skills.push(ProtocolCharacterSkill {
+                skill_id,
+                is_class_skill: skill.is_class_skill,
+                ranks: skill.ranks,
+            }),
+            None => errors.push(ReferenceError::Skill(skill.skill_code.clone())),
+        }
+    }
+
+    let wire_character = ProtocolCharacter {
+        id: Some(character.id),
+        code: None,
+        world_id: Some(character.world_id),
+        user_id: None,
+        name: character.name.clone(),
+        race: race.code.clone(),
+        class: character.class.clone(),
+        theme: character.theme.clone(),
+        level: character.level,
+        experience: i32::try_from(character.experience).unwrap_or_else(|_| {
+            errors.push(ReferenceError::Value(format!(
+                "experience {} is too large",
+                character.experience
+            )));
+            0
+        }),
+        hit_points: character.hit_points,
+        stamina: character.stamina,
+        abilities,
+        feats: wire_feats,
+        skills: wire_skills,
+        conditions,
+    };
+
+    let converted = convert_character(&character_world, &wire_character, errors)?;
+    Ok((converted, character_world))
+}
+
+/// Convert a wire character with [`CharacterWorld`], reporting every reference it has that the
+/// world does not, along with `errors` already found.
+fn convert_character(
+    character_world: &CharacterWorld,
+    character: &ProtocolCharacter,
+    mut errors: Vec<ReferenceError>,
+) -> Result<logic::Character, CharacterError> {
+    match character_world.to_logic(character) {
+        Ok(converted) if errors.is_empty() => Ok(converted),
+        Ok(_) => Err(CharacterError::references(errors)),
+        Err(reference_errors) => {
+            errors.splice(0..0, reference_errors);
+            Err(CharacterError::references(errors))
+        }
+    }
+}
+
+/// Load the race, class, theme, feats and skills a character refers to.  Anything the world
+/// does not have is left out, so converting the character reports it as a [`ReferenceError`].
+fn load_character_world(
+    conn: &mut PgConnection,
+    world: &World,
+    race: &Race,
+    class_code: &str,
+    theme_code: &str,
+    feat_codes: &[String],
+    skills: &[logic::CharacterSkill],
+) -> Result<CharacterWorld, CharacterError> {
+    let race = race
+        .to_logic()
+        .map_err(|message| CharacterError::BadRequest(vec![message]))?;
+    let mut character_world = CharacterWorld::default().with_race(race);
+    if let Some(class) = find_class(conn, world, class_code)? {
+        character_world = character_world.with_class(class);
+    }
+    if let Some(theme) = find_theme(conn, world, theme_code)? {
+        character_world = character_world.with_theme(theme);
+    }
+
+    for code in feat_codes {
+        match FeatRepository::find_by_code(conn, world.id, code) {
+            Ok(feat) => character_world = character_world.with_feat(feat_entity_to_protocol(&feat)),
+            Err(diesel::result::Error::NotFound) => {}
+            Err(e) => {
+                eprintln!("Failed to get feat: {}", e);
+                return Err(CharacterError::Error("Failed to get feat".to_string()));
+            }
+        }
+    }
+    for skill in skills {
+        match SkillRepository::find_by_code(conn, world.id, &skill.skill_code) {
+            Ok(skill) => {
+                character_world = character_world.with_skill(skill_entity_to_protocol(&skill))
+            }
+            Err(diesel::result::Error::NotFound) => {}
+            Err(e) => {
+                eprintln!("Failed to get skill: {}", e);
+                return Err(CharacterError::Error("Failed to get skill".to_string()));
+            }
+        }
+    }
+
+    Ok(character_world)
+}
+
+/// Load a theme and its features from the world, or nothing when the code is empty or the
+/// world does not define it.
+fn find_theme(
+    conn: &mut PgConnection,
+    world: &World,
+    theme_code: &str,
+) -> Result<Option<logic::Theme>, CharacterError> {
+    if theme_code.is_empty() {
+        return Ok(None);
+    }
+
+    let theme = match ThemeRepository::find_by_code(conn, world.id, theme_code) {
+        Ok(theme) => theme,
+        Err(diesel::result::Error::NotFound) => return Ok(None),
+        Err(e) => {
+            eprintln!("Failed to get theme: {}", e);
+            return Err(CharacterError::Error("Failed to get theme".to_string()));
+        }
+    };
+
+    match ThemeFeatureRepository::find_by_theme(conn, theme.id) {
+        Ok(features) => Ok(Some(theme.to_logic(&features))),
+        Err(e) => {
+            eprintln!("Failed to get theme features: {}", e);
+            Err(CharacterError::Error(
+                "Failed to get theme features".to_string(),
+            ))
+        }
+    }
+}
+
+/// Load a class and its features from the world, or nothing when the world does not define it.
+fn find_class(
+    conn: &mut PgConnection,
+    world: &World,
+    class_code: &str,
+) -> Result<Option<logic::CharacterClass>, CharacterError> {
+    let class =
+        match CharacterClassRepository::find_character_class_by_code(conn, world.id, class_code) {
+            Ok(class) => class,
+            Err(diesel::result::Error::NotFound) => return Ok(None),
+            Err(e) => {
+                eprintln!("Failed to get character class: {}", e);
+                return Err(CharacterError::Error(
+                    "Failed to get character class".to_string(),
+                ));
+            }
+        };
+
+    let features = match CharacterClassFeatureRepository::find_by_class(conn, class.id) {
+        Ok(features) => features,
+        Err(e) => {
+            eprintln!("Failed to get character class features: {}", e);
+            return Err(CharacterError::Error(
+                "Failed to get character class features".to_string(),
+            ));
+        }
+    };
+
+    let mut logic_class = match logic::CharacterClass::new(
+        &class.code,
+        &class.name,
+        class.hit_points,
+        &class.stamina_expression,
+        &class.skillpoint_expression,
+    ) {
+        Ok(logic_class) => logic_class,
+        Err(e) => {
+            return Err(CharacterError::BadRequest(vec![format!(
+                "class '{}' has an invalid expression: {}",
+                class.code, e
+            )]))
+        }
+    };
+    for feature in features {
+        logic_class = logic_class.with_feature(logic::ClassFeature {
+            level: feature.level,
+            code: feature.code,
+            name: feature.name,
+            description: feature.description,
+        });
+    }
+
+    Ok(Some(logic_class))
+}
+
+/// Derive a character's vitals from what they gained at each level.  Characters stored before
+/// the gains were kept have none, and get the class's fixed maximums.
+fn calculate_vitals(
+    character_world: &CharacterWorld,
+    character: &logic::Character,
+    level_gains: &[LevelGain],
+) -> Result<Vitals, CharacterError> {
+    let class = character_class(character_world, character)?;
+    if !level_gains.is_empty() {
+        return Ok(Vitals::from_gains(character, class, level_gains));
+    }
+
+    Vitals::calculate(character, class).map_err(|e| {
+        CharacterError::BadRequest(vec![format!(
+            "class '{}' could not be evaluated: {}",
+            class.code, e
+        )])
+    })
+}
+
+/// Work out what a new character gained at each level up to their own, rolling any dice the
+/// class expressions have once so that they are kept rather than rolled on every read.
+fn roll_level_gains(
+    character_world: &CharacterWorld,
+    character: &logic::Character,
+) -> Result<Vec<LevelGain>, CharacterError> {
+    let class = character_class(character_world, character)?;
+    let mut rng = rand::thread_rng();
+
+    (1..=character.level())
+        .map(|level| {
+            LevelGain::roll(character, class, level, HitPointMethod::default(), &mut rng)
+                .map(|(gain, _)| gain)
+                .map_err(|e| {
+                    CharacterError::BadRequest(vec![format!(
+                        "class '{}' could not be evaluated: {}",
+                        class.code, e
+                    )])
+                })
+        })
+        .collect()
+}
+
+/// The character's class, which converting them has already found.
+fn character_class<'a>(
+    character_world: &'a CharacterWorld,
+    character: &logic::Character,
+) -> Result<&'a logic::CharacterClass, CharacterError> {
+    character_world
+        .classes
+        .get(character.class())
+        .ok_or_else(|| {
+            CharacterError::references(vec![ReferenceError::Class(character.class().to_string())])
+        })
+}
+
+/// Read one of the JSON columns of a stored character.
+fn read_column<T: serde::de::DeserializeOwned>(
+    character: &Character,
+    column: &str,
+    value: &serde_json::Value,
+) -> Result<T, CharacterError> {
+    serde_json::from_value(value.clone()).map_err(|e| {
+        eprintln!(
+            "Failed to read character {} {}: {}",
+            character.id, column, e
+        );
+        CharacterError::Error(format!("Failed to read character {}", column))
+    })
+}
+
+fn feat_entity_to_protocol(feat: &Feat) -> ProtocolFeat {
+    ProtocolFeat {
+        id: Some(feat.id),
+        code: Some(feat.code.clone()),
+        world_id: Some(feat.world_id),
+        name: feat.name.clone(),
+        description: feat.description.clone(),
+        prerequisites: feat.prerequisites.clone(),
+        benefits: String::new(),
+        special: None,
+        source: String::new(),
+    }
+}
+
+/// A feat the world does not define, so that converting the character reports it.
+fn missing_feat(code: &str) -> ProtocolFeat {
+    ProtocolFeat {
+        id: None,
+        code: Some(code.to_string()),
+        world_id: None,
+        name: code.to_string(),
+        description: String::new(),
+        prerequisites: None,
+        benefits: String::new(),
+        special: None,
+        source: String::new(),
+    }
+}
+
+fn skill_entity_to_protocol(skill: &Skill) -> ProtocolSkill {
+    ProtocolSkill {
+        id: Some(skill.id),
+        code: Some(skill.code.clone()),
+        world_id: Some(skill.world_id),
+        name: skill.name.clone(),
+        description: skill.description.clone(),
+        ability: skill.ability.clone(),
+        trained_only: skill.trained_only,
+        armor_penalty: skill.armor_penalty,
+        source: String::new(),
+    }
+}
+
+enum CharacterError {
+    BadRequest(Vec<String>),
+    Error(String),
+}
+
+impl CharacterError {
+    fn references(errors: Vec<ReferenceError>) -> Self {
+        CharacterError::BadRequest(errors.iter().map(|error| error.to_string()).collect())
+    }
+}
+
+impl<T> From<CharacterError> for ApiResponse<T> {
+    fn from(error: CharacterError) -> Self {
+        match error {
+            CharacterError::BadRequest(messages) => ApiResponse::BadRequest(messages),
+            CharacterError::Error(message) => ApiResponse::Error(message),
+        }
+    }
+}
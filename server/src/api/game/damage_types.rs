@@ -0,0 +1,127 @@
+use crate::api::{ApiResponse, Payload};
+use crate::app_state::AppState;
+use crate::game::DamageType;
+use crate::{DamageTypeRepository, WorldRepository};
+use axum::extract::{Path, State};
+use axum::Json;
+use diesel::{PgConnection, QueryResult};
+
+use protocol::types::Valid;
+use protocol::{DamageType as ProtocolDamageType, TypeSignature};
+
+pub async fn get_damage_type_by_code(
+    State(state): State<AppState>,
+    Path((world_code, damage_type_code)): Path<(String, String)>,
+) -> ApiResponse<ProtocolDamageType> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    match DamageTypeRepository::find_by_code(&mut conn, world.id, &damage_type_code) {
+        Ok(damage_type) => ApiResponse::JsonData(Payload {
+            data: damage_type_entity_to_protocol(&damage_type),
+        }),
+        Err(diesel::result::Error::NotFound) => {
+            ApiResponse::NotFound("damage type not found".to_string())
+        }
+        Err(e) => {
+            eprintln!("Failed to get damage type: {}", e);
+            ApiResponse::Error("Failed to get damage type".to_string())
+        }
+    }
+}
+
+pub async fn create_or_update_damage_type(
+    State(state): State<AppState>,
+    Path((world_code, damage_type_code)): Path<(String, String)>,
+    Json(damage_type): Json<ProtocolDamageType>,
+) -> ApiResponse<ProtocolDamageType> {
+    if let Err(errors) = damage_type.validate() {
+        return ApiResponse::BadRequest(errors);
+    }
+
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let existing_damage_type =
+        match DamageTypeRepository::find_by_code(&mut conn, world.id, &damage_type_code) {
+            Ok(existing_damage_type) => Some(existing_damage_type),
+            Err(diesel::result::Error::NotFound) => None,
+            Err(e) => {
+                eprintln!("Failed to get damage type: {}", e);
+                return ApiResponse::Error("Failed to get damage type".to_string());
+            }
+        };
+
+    if let Some(existing_damage_type) = &existing_damage_type {
+        if damage_type_entity_to_protocol(existing_damage_type).signature()
+            == damage_type.signature()
+        {
+            return ApiResponse::NotChanged;
+        }
+    }
+
+    let mut entity_damage_type = protocol_damage_type_to_entity(&damage_type);
+    entity_damage_type.id = existing_damage_type
+        .map(|existing_damage_type| existing_damage_type.id)
+        .unwrap_or(0);
+    entity_damage_type.world_id = world.id;
+
+    match DamageTypeRepository::create_or_update(&mut conn, &entity_damage_type) {
+        Ok(saved_damage_type) => ApiResponse::JsonData(Payload {
+            data: damage_type_entity_to_protocol(&saved_damage_type),
+        }),
+        Err(e) => {
+            eprintln!("Failed to save damage type: {}", e);
+            ApiResponse::Error("Failed to save damage type".to_string())
+        }
+    }
+}
+
+/// The damage types a world has stored, for checking the content that refers to them.
+pub(crate) fn world_damage_types(
+    conn: &mut PgConnection,
+    world_id: i64,
+) -> QueryResult<Vec<logic::DamageType>> {
+    Ok(DamageTypeRepository::find_by_world(conn, world_id)?
+        .iter()
+        .map(DamageType::to_logic)
+        .collect())
+}
+
+fn damage_type_entity_to_protocol(damage_type: &DamageType) -> ProtocolDamageType {
+    ProtocolDamageType {
+        id: Some(damage_type.id),
+        world_id: Some(damage_type.world_id),
+        code: Some(damage_type.code.clone()),
+        name: damage_type.name.clone(),
+        description: damage_type.description.clone(),
+    }
+}
+
+pub(crate) fn protocol_damage_type_to_entity(damage_type: &ProtocolDamageType) -> DamageType {
+    DamageType {
+        id: damage_type.id.unwrap_or(0),
+        world_id: damage_type.world_id.unwrap_or(0),
+        code: damage_type.code.clone().unwrap_or("".to_string()),
+        name: damage_type.name.clone(),
+        description: damage_type.description.clone(),
+        created_at: chrono::Utc::now().naive_utc(),
+        updated_at: chrono::Utc::now().naive_utc(),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::api::{ApiResponse, Payload};
+use axum::Json;
+use logic::utilities::{analyze_dice_with, ExpressionContext};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// ## DiceAnalysisRequest
+/// An expression to work out the odds of, e.g. a class's `stamina_expression`, a weapon's
+/// damage or a skill check, with the variables it reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiceAnalysisRequest {
+    pub expression: String,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// The total to beat, for the chance of a check succeeding.
+    pub difficulty_check: Option<i64>,
+}
+
+/// ## DiceAnalysis
+/// The exact odds of an expression, worked out without rolling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiceAnalysis {
+    pub expression: String,
+    pub min: i64,
+    pub median: i64,
+    pub max: i64,
+    pub mean: f64,
+    pub variance: f64,
+    pub standard_deviation: f64,
+    /// The chance of a total at least `difficulty_check`, when one was given.
+    pub probability_at_least: Option<f64>,
+    /// The chance of each total.
+    pub pmf: BTreeMap<i64, f64>,
+}
+
+pub async fn analyze_dice_expression(
+    Json(request): Json<DiceAnalysisRequest>,
+) -> ApiResponse<DiceAnalysis> {
+    let mut context = ExpressionContext::new();
+    for (name, value) in &request.variables {
+        context.set(name, value);
+    }
+
+    let distribution = match analyze_dice_with(&request.expression, &context) {
+        Ok(distribution) => distribution,
+        Err(e) => return ApiResponse::BadRequest(vec![e.to_string()]),
+    };
+
+    ApiResponse::JsonData(Payload {
+        data: DiceAnalysis {
+            expression: request.expression,
+            min: distribution.min(),
+            median: distribution.percentile(0.5),
+            max: distribution.max(),
+            mean: distribution.mean(),
+            variance: distribution.variance(),
+            standard_deviation: distribution.standard_deviation(),
+            probability_at_least: request
+                .difficulty_check
+                .map(|difficulty_check| distribution.probability_at_least(difficulty_check)),
+            pmf: distribution.pmf().clone(),
+        },
+    })
+}
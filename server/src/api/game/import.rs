@@ -0,0 +1,455 @@
+use crate::api::game::character_classes::{
+    protocol_character_class_feature_to_entity, protocol_character_class_to_entity,
+};
+use crate::api::game::damage_types::{protocol_damage_type_to_entity, world_damage_types};
+use crate::api::game::items::ItemDefinition;
+use crate::api::game::races::protocol_race_to_entity;
+use crate::api::game::themes::{protocol_theme_to_entity, save_theme};
+use crate::api::{ApiResponse, Payload};
+use crate::app_state::AppState;
+use crate::game::{Enemy, Item, Skill, World};
+use crate::{
+    CharacterClassFeatureRepository, CharacterClassRepository, DamageTypeRepository,
+    EnemyRepository, ItemCategoryRepository, ItemRepository, RaceRepository, SkillRepository,
+    ThemeRepository, WorldRepository,
+};
+use axum::extract::{Path, State};
+use axum::Json;
+use diesel::{Connection, PgConnection, QueryResult};
+use logic::game_data::{ContentKind, GameData, IntegrityCheck};
+use serde::{Deserialize, Serialize};
+
+use protocol::types::{Skill as ProtocolSkill, Valid};
+use protocol::{
+    CharacterClass as ProtocolCharacterClass, DamageType as ProtocolDamageType,
+    Race as ProtocolRace, Theme as ProtocolTheme,
+};
+
+/// ## ImportDocument
+/// A piece of world content in a bulk import, written the way world files are, e.g.
+/// `{ "kind": "race", "apiVersion": "v1", "spec": { ... } }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", content = "spec")]
+pub enum ImportDocument {
+    DamageType(ProtocolDamageType),
+    Item(ItemDefinition),
+    CharacterClass(ProtocolCharacterClass),
+    Race(ProtocolRace),
+    Theme(ProtocolTheme),
+    Enemy(logic::Enemy),
+    Skill(ProtocolSkill),
+}
+
+impl ImportDocument {
+    fn describe(&self) -> String {
+        match self {
+            ImportDocument::DamageType(damage_type) => {
+                format!(
+                    "damage type {}",
+                    damage_type.code.as_deref().unwrap_or_default()
+                )
+            }
+            ImportDocument::Item(item) => format!("item {}", item.code),
+            ImportDocument::CharacterClass(class) => {
+                format!("class {}", class.code.as_deref().unwrap_or_default())
+            }
+            ImportDocument::Race(race) => {
+                format!("race {}", race.code.as_deref().unwrap_or_default())
+            }
+            ImportDocument::Theme(theme) => {
+                format!("theme {}", theme.code.as_deref().unwrap_or_default())
+            }
+            ImportDocument::Enemy(enemy) => format!("enemy {}", enemy.code),
+            ImportDocument::Skill(skill) => {
+                format!("skill {}", skill.code.as_deref().unwrap_or_default())
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), Vec<String>> {
+        match self {
+            ImportDocument::DamageType(damage_type) => damage_type.validate(),
+            ImportDocument::Item(item) => item.is_valid(),
+            ImportDocument::CharacterClass(class) => class.validate(),
+            ImportDocument::Race(race) => race.validate(),
+            ImportDocument::Theme(theme) => theme.validate(),
+            ImportDocument::Enemy(enemy) => validate_enemy(enemy),
+            ImportDocument::Skill(skill) => validate_skill(skill),
+        }
+    }
+}
+
+/// ## ImportSummary
+/// How many of each kind of content a bulk import saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub damage_types: usize,
+    pub items: usize,
+    pub classes: usize,
+    pub races: usize,
+    pub themes: usize,
+    pub enemies: usize,
+    pub skills: usize,
+}
+
+/// Import a world's content in one transaction.  Nothing is saved unless every document is
+/// valid and the content passes the integrity check.
+pub async fn import_world_content(
+    State(state): State<AppState>,
+    Path(world_code): Path<String>,
+    Json(documents): Json<Vec<ImportDocument>>,
+) -> ApiResponse<ImportSummary> {
+    let mut errors = vec![];
+    for document in &documents {
+        if let Err(document_errors) = document.validate() {
+            for error in document_errors {
+                errors.push(format!("{}: {}", document.describe(), error));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return ApiResponse::BadRequest(errors);
+    }
+
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let categories = match ItemCategoryRepository::find_all(&mut conn) {
+        Ok(categories) => categories,
+        Err(e) => {
+            eprintln!("Failed to get item categories: {}", e);
+            return ApiResponse::Error("Failed to get item categories".to_string());
+        }
+    };
+
+    let damage_types = match world_damage_types(&mut conn, world.id) {
+        Ok(damage_types) => damage_types,
+        Err(e) => {
+            eprintln!("Failed to get damage types: {}", e);
+            return ApiResponse::Error("Failed to get damage types".to_string());
+        }
+    };
+
+    let items = match ItemRepository::find_by_world(&mut conn, world.id) {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("Failed to get items: {}", e);
+            return ApiResponse::Error("Failed to get items".to_string());
+        }
+    };
+
+    let game_data = match documents_to_game_data(&world, &damage_types, &items, &documents) {
+        Ok(game_data) => game_data,
+        Err(errors) => return ApiResponse::BadRequest(errors),
+    };
+
+    // skills the world already has are not loaded, so content may refer to any skill
+    let issues = IntegrityCheck::new(&game_data)
+        .with_item_categories(categories.iter().map(|category| category.id))
+        .with_external(ContentKind::Skill)
+        .run();
+    if !issues.is_empty() {
+        return ApiResponse::BadRequest(issues.iter().map(|issue| issue.to_string()).collect());
+    }
+
+    let txn_result = conn.transaction(|txn| {
+        let mut summary = ImportSummary::default();
+        for document in &documents {
+            match document {
+                ImportDocument::DamageType(damage_type) => {
+                    save_damage_type(txn, &world, damage_type)?;
+                    summary.damage_types += 1;
+                }
+                ImportDocument::Item(item) => {
+                    save_item(txn, &world, item)?;
+                    summary.items += 1;
+                }
+                ImportDocument::CharacterClass(class) => {
+                    save_character_class(txn, &world, class)?;
+                    summary.classes += 1;
+                }
+                ImportDocument::Race(race) => {
+                    save_race(txn, &world, race)?;
+                    summary.races += 1;
+                }
+                ImportDocument::Theme(theme) => {
+                    let mut entity_theme = protocol_theme_to_entity(theme);
+                    entity_theme.id =
+                        match ThemeRepository::find_by_code(txn, world.id, &entity_theme.code) {
+                            Ok(existing_theme) => existing_theme.id,
+                            Err(diesel::result::Error::NotFound) => 0,
+                            Err(e) => return Err(e),
+                        };
+                    entity_theme.world_id = world.id;
+                    save_theme(
+                        txn,
+                        &entity_theme,
+                        theme.features.as_deref().unwrap_or_default(),
+                    )?;
+                    summary.themes += 1;
+                }
+                ImportDocument::Enemy(enemy) => {
+                    save_enemy(txn, &world, enemy)?;
+                    summary.enemies += 1;
+                }
+                ImportDocument::Skill(skill) => {
+                    save_skill(txn, &world, skill)?;
+                    summary.skills += 1;
+                }
+            }
+        }
+        QueryResult::Ok(summary)
+    });
+
+    match txn_result {
+        Ok(summary) => ApiResponse::JsonData(Payload { data: summary }),
+        Err(e) => {
+            eprintln!("Failed to import world content: {}", e);
+            ApiResponse::Error("Failed to import world content".to_string())
+        }
+    }
+}
+
+/// The imported content as game data for the integrity check, with the world defined since it
+/// exists.  The world's stored damage types and items are included unless the import replaces
+/// them, so that imported content may refer to them.
+fn documents_to_game_data(
+    world: &World,
+    damage_types: &[logic::DamageType],
+    items: &[Item],
+    documents: &[ImportDocument],
+) -> Result<GameData, Vec<String>> {
+    let mut game_data = GameData::new();
+    game_data.define_world(&world.code);
+
+    for damage_type in damage_types {
+        let imported = documents.iter().any(|document| {
+            matches!(document, ImportDocument::DamageType(imported)
+                if imported.code.as_deref() == Some(damage_type.code.as_str()))
+        });
+        if !imported {
+            game_data
+                .damage_types_mut()
+                .insert(&world.code, damage_type.clone());
+        }
+    }
+
+    for item in items {
+        let imported = documents.iter().any(|document| {
+            matches!(document, ImportDocument::Item(imported) if imported.code == item.code)
+        });
+        if !imported {
+            game_data.items_mut().insert(&world.code, item.to_logic());
+        }
+    }
+
+    for document in documents {
+        match document {
+            ImportDocument::DamageType(damage_type) => {
+                game_data
+                    .damage_types_mut()
+                    .insert(&world.code, logic::DamageType::try_from(damage_type)?);
+            }
+            ImportDocument::Item(item) => {
+                game_data
+                    .items_mut()
+                    .insert(&world.code, item.to_logic_item());
+            }
+            ImportDocument::CharacterClass(class) => {
+                game_data
+                    .classes_mut()
+                    .insert(&world.code, logic::CharacterClass::try_from(class)?);
+            }
+            ImportDocument::Race(race) => {
+                game_data
+                    .races_mut()
+                    .insert(&world.code, logic::Race::try_from(race)?);
+            }
+            ImportDocument::Theme(theme) => {
+                game_data
+                    .themes_mut()
+                    .insert(&world.code, logic::Theme::try_from(theme)?);
+            }
+            ImportDocument::Enemy(enemy) => {
+                game_data.enemies_mut().insert(&world.code, enemy.clone());
+            }
+            ImportDocument::Skill(skill) => {
+                game_data.skills_mut().insert(
+                    &world.code,
+                    logic::Skill::try_from(skill).map_err(|e| vec![e])?,
+                );
+            }
+        }
+    }
+
+    Ok(game_data)
+}
+
+fn save_damage_type(
+    txn: &mut PgConnection,
+    world: &World,
+    damage_type: &ProtocolDamageType,
+) -> QueryResult<()> {
+    let mut entity_damage_type = protocol_damage_type_to_entity(damage_type);
+    entity_damage_type.id =
+        match DamageTypeRepository::find_by_code(txn, world.id, &entity_damage_type.code) {
+            Ok(existing_damage_type) => existing_damage_type.id,
+            Err(diesel::result::Error::NotFound) => 0,
+            Err(e) => return Err(e),
+        };
+    entity_damage_type.world_id = world.id;
+
+    DamageTypeRepository::create_or_update(txn, &entity_damage_type)?;
+    Ok(())
+}
+
+fn validate_enemy(enemy: &logic::Enemy) -> Result<(), Vec<String>> {
+    let mut errors: Vec<String> = vec![];
+
+    if enemy.code.is_empty() {
+        errors.push("Code is required".to_string());
+    }
+
+    if enemy.name.is_empty() {
+        errors.push("Name is required".to_string());
+    }
+
+    if enemy.level < 1 {
+        errors.push("Level must be at least 1".to_string());
+    }
+
+    if enemy.hit_points < 1 {
+        errors.push("Hit Points must be at least 1".to_string());
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn save_enemy(txn: &mut PgConnection, world: &World, enemy: &logic::Enemy) -> QueryResult<()> {
+    let mut entity_enemy = Enemy::from_logic(world.id, enemy);
+    entity_enemy.id = match EnemyRepository::find_by_code(txn, world.id, &enemy.code) {
+        Ok(existing_enemy) => existing_enemy.id,
+        Err(diesel::result::Error::NotFound) => 0,
+        Err(e) => return Err(e),
+    };
+
+    EnemyRepository::create_or_update(txn, &entity_enemy)?;
+    Ok(())
+}
+
+fn validate_skill(skill: &ProtocolSkill) -> Result<(), Vec<String>> {
+    let mut errors: Vec<String> = vec![];
+
+    if skill.code.as_deref().unwrap_or_default().is_empty() {
+        errors.push("Code is required".to_string());
+    }
+
+    if skill.name.is_empty() {
+        errors.push("Name is required".to_string());
+    }
+
+    if logic::Ability::from_name(&skill.ability).is_none() {
+        errors.push(format!("Unknown ability '{}'", skill.ability));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn save_skill(txn: &mut PgConnection, world: &World, skill: &ProtocolSkill) -> QueryResult<()> {
+    let skill_code = skill.code.clone().unwrap_or_default();
+    let entity_skill = Skill {
+        id: match SkillRepository::find_by_code(txn, world.id, &skill_code) {
+            Ok(existing_skill) => existing_skill.id,
+            Err(diesel::result::Error::NotFound) => 0,
+            Err(e) => return Err(e),
+        },
+        world_id: world.id,
+        code: skill_code,
+        name: skill.name.clone(),
+        description: skill.description.clone(),
+        created_at: chrono::Utc::now().naive_utc(),
+        updated_at: chrono::Utc::now().naive_utc(),
+        ability: skill.ability.clone(),
+        trained_only: skill.trained_only,
+        armor_penalty: skill.armor_penalty,
+    };
+
+    SkillRepository::create_or_update(txn, &entity_skill)?;
+    Ok(())
+}
+
+fn save_item(txn: &mut PgConnection, world: &World, item: &ItemDefinition) -> QueryResult<()> {
+    let mut entity_item = item.to_item();
+    entity_item.id = match ItemRepository::find_item_by_code(txn, world.id, &item.code) {
+        Ok(existing_item) => existing_item.id,
+        Err(diesel::result::Error::NotFound) => 0,
+        Err(e) => return Err(e),
+    };
+    entity_item.world_id = world.id;
+
+    ItemRepository::create_or_update(txn, &entity_item)?;
+    Ok(())
+}
+
+fn save_race(txn: &mut PgConnection, world: &World, race: &ProtocolRace) -> QueryResult<()> {
+    let mut entity_race = protocol_race_to_entity(race);
+    entity_race.id = match RaceRepository::find_by_code(txn, world.id, &entity_race.code) {
+        Ok(existing_race) => existing_race.id,
+        Err(diesel::result::Error::NotFound) => 0,
+        Err(e) => return Err(e),
+    };
+    entity_race.world_id = world.id;
+
+    RaceRepository::create_or_update(txn, &entity_race)?;
+    Ok(())
+}
+
+/// Save a class and replace its features as a whole.
+fn save_character_class(
+    txn: &mut PgConnection,
+    world: &World,
+    class: &ProtocolCharacterClass,
+) -> QueryResult<()> {
+    let mut entity_class = protocol_character_class_to_entity(class);
+    entity_class.id = match CharacterClassRepository::find_character_class_by_code(
+        txn,
+        world.id,
+        &entity_class.code,
+    ) {
+        Ok(existing_class) => existing_class.id,
+        Err(diesel::result::Error::NotFound) => 0,
+        Err(e) => return Err(e),
+    };
+    entity_class.world_id = world.id;
+
+    let saved_class = CharacterClassRepository::create_or_update(txn, &entity_class)?;
+    for existing_feature in CharacterClassFeatureRepository::find_by_class(txn, saved_class.id)? {
+        CharacterClassFeatureRepository::delete_by_code(
+            txn,
+            saved_class.id,
+            &existing_feature.code,
+        )?;
+    }
+    for feature in class.features.iter().flatten() {
+        let mut new_feature = protocol_character_class_feature_to_entity(feature);
+        new_feature.class_id = saved_class.id;
+        CharacterClassFeatureRepository::create_or_update_feature(txn, &new_feature)?;
+    }
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use axum::Json;
+use logic::game_data::{GameData, IntegrityCheck};
 use protocol::TypeSignature;
 
+use crate::api::game::damage_types::world_damage_types;
 use crate::api::{ApiResponse, Payload};
-use crate::{app_state::AppState, game::Item, ItemRepository, WorldRepository};
+use crate::{
+    app_state::AppState, game::Item, ItemCategoryRepository, ItemRepository, WorldRepository,
+};
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 
@@ -91,6 +95,11 @@ impl ItemDefinition {
         }
     }
 
+    /// The item as the rules engine sees it, for checking it against the rest of its world.
+    pub fn to_logic_item(&self) -> logic::Item {
+        self.to_item().to_logic()
+    }
+
     pub fn is_valid(&self) -> Result<(), Vec<String>> {
         let mut errors: Vec<String> = vec![];
 
@@ -128,7 +137,7 @@ impl ItemDefinition {
 
 pub async fn create_item(
     State(state): State<AppState>,
-    Path((world_code, item_code)): Path<(String, String)>,
+    Path((world_code, _item_code)): Path<(String, String)>,
     Json(item): Json<ItemDefinition>,
 ) -> ApiResponse<Item> {
     // todo: More information as to what is wrong with the item definition
@@ -147,6 +156,40 @@ pub async fn create_item(
         Err(_) => return ApiResponse::NotFound("World not found".to_string()),
     };
 
+    let categories = match ItemCategoryRepository::find_all(&mut conn) {
+        Ok(categories) => categories,
+        Err(e) => {
+            eprintln!("Failed to get item categories: {}", e);
+            return ApiResponse::Error("Failed to get item categories".to_string());
+        }
+    };
+
+    let damage_types = match world_damage_types(&mut conn, world.id) {
+        Ok(damage_types) => damage_types,
+        Err(e) => {
+            eprintln!("Failed to get damage types: {}", e);
+            return ApiResponse::Error("Failed to get damage types".to_string());
+        }
+    };
+
+    // a weapon's damage type and the item's damage modifiers must name the world's damage types
+    let mut game_data = GameData::new();
+    game_data.define_world(&world.code);
+    for damage_type in damage_types {
+        game_data
+            .damage_types_mut()
+            .insert(&world.code, damage_type);
+    }
+    game_data
+        .items_mut()
+        .insert(&world.code, item.to_logic_item());
+    let issues = IntegrityCheck::new(&game_data)
+        .with_item_categories(categories.iter().map(|category| category.id))
+        .run();
+    if !issues.is_empty() {
+        return ApiResponse::BadRequest(issues.iter().map(|issue| issue.to_string()).collect());
+    }
+
     // look up an item by its code and world id.  If it exists, compare the signature
     let found_item = ItemRepository::find_item_by_code(&mut conn, world.id, &item.code);
     let found_item_id = found_item.as_ref().map(|i| i.id).unwrap_or(0);
@@ -1,12 +1,24 @@
 pub mod character_classes;
+pub mod characters;
+pub mod damage_types;
+pub mod dice;
+pub mod import;
 pub mod items;
+pub mod races;
+pub mod themes;
 pub mod worlds;
 
 use crate::app_state::AppState;
 use axum::routing::{get, post, put};
 use axum::Router;
 pub use character_classes::{create_or_update_character_class, get_character_class_by_code};
+pub use characters::{add_character_feat, create_character, get_character};
+pub use damage_types::{create_or_update_damage_type, get_damage_type_by_code};
+pub use dice::analyze_dice_expression;
+pub use import::import_world_content;
 pub use items::{create_item, get_item, get_world_item_by_code};
+pub use races::{create_or_update_race, get_race_by_code};
+pub use themes::{create_or_update_theme, get_theme_by_code};
 pub use worlds::{create_new_game_world, create_or_update_game_world, get_world_by_code};
 
 pub fn game_routes() -> Router<AppState> {
@@ -14,6 +26,7 @@ pub fn game_routes() -> Router<AppState> {
         .route("/game/:world_code", get(get_world_by_code))
         .route("/game", post(create_new_game_world))
         .route("/game/:world_code", put(create_or_update_game_world))
+        .route("/game/:world_code/import", put(import_world_content))
         .route(
             "/game/:world_code/items/:item_code",
             get(get_world_item_by_code),
@@ -28,6 +41,37 @@ pub fn game_routes() -> Router<AppState> {
             "/game/:world_code/classes/:class_code",
             get(get_character_class_by_code),
         )
+        .route(
+            "/game/:world_code/races/:race_code",
+            put(create_or_update_race),
+        )
+        .route("/game/:world_code/races/:race_code", get(get_race_by_code))
+        .route(
+            "/game/:world_code/themes/:theme_code",
+            put(create_or_update_theme),
+        )
+        .route(
+            "/game/:world_code/themes/:theme_code",
+            get(get_theme_by_code),
+        )
+        .route(
+            "/game/:world_code/damage-types/:damage_type_code",
+            put(create_or_update_damage_type),
+        )
+        .route(
+            "/game/:world_code/damage-types/:damage_type_code",
+            get(get_damage_type_by_code),
+        )
+        .route("/game/dice/analysis", post(analyze_dice_expression))
+        .route("/game/:world_code/characters", post(create_character))
+        .route(
+            "/game/:world_code/characters/:character_id",
+            get(get_character),
+        )
+        .route(
+            "/game/:world_code/characters/:character_id/feats/:feat_code",
+            post(add_character_feat),
+        )
         // legacy routes
         .route("/game/items/:id", axum::routing::get(get_item))
         .route("/game/items", axum::routing::put(create_item))
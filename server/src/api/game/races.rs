@@ -0,0 +1,120 @@
+use crate::api::{ApiResponse, Payload};
+use crate::app_state::AppState;
+use crate::game::Race;
+use crate::{RaceRepository, WorldRepository};
+use axum::extract::{Path, State};
+use axum::Json;
+
+use protocol::types::Valid;
+use protocol::{Race as ProtocolRace, TypeSignature};
+
+pub async fn get_race_by_code(
+    State(state): State<AppState>,
+    Path((world_code, race_code)): Path<(String, String)>,
+) -> ApiResponse<ProtocolRace> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    match RaceRepository::find_by_code(&mut conn, world.id, &race_code) {
+        Ok(race) => ApiResponse::JsonData(Payload {
+            data: race_entity_to_protocol(&race),
+        }),
+        Err(diesel::result::Error::NotFound) => ApiResponse::NotFound("race not found".to_string()),
+        Err(e) => {
+            eprintln!("Failed to get race: {}", e);
+            ApiResponse::Error("Failed to get race".to_string())
+        }
+    }
+}
+
+pub async fn create_or_update_race(
+    State(state): State<AppState>,
+    Path((world_code, race_code)): Path<(String, String)>,
+    Json(race): Json<ProtocolRace>,
+) -> ApiResponse<ProtocolRace> {
+    if let Err(errors) = race.validate() {
+        return ApiResponse::BadRequest(errors);
+    }
+
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let existing_race = match RaceRepository::find_by_code(&mut conn, world.id, &race_code) {
+        Ok(existing_race) => Some(existing_race),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(e) => {
+            eprintln!("Failed to get race: {}", e);
+            return ApiResponse::Error("Failed to get race".to_string());
+        }
+    };
+
+    if let Some(existing_race) = &existing_race {
+        if race_entity_to_protocol(existing_race).signature() == race.signature() {
+            return ApiResponse::NotChanged;
+        }
+    }
+
+    let mut entity_race = protocol_race_to_entity(&race);
+    entity_race.id = existing_race
+        .map(|existing_race| existing_race.id)
+        .unwrap_or(0);
+    entity_race.world_id = world.id;
+
+    match RaceRepository::create_or_update(&mut conn, &entity_race) {
+        Ok(saved_race) => ApiResponse::JsonData(Payload {
+            data: race_entity_to_protocol(&saved_race),
+        }),
+        Err(e) => {
+            eprintln!("Failed to save race: {}", e);
+            ApiResponse::Error("Failed to save race".to_string())
+        }
+    }
+}
+
+fn race_entity_to_protocol(race: &Race) -> ProtocolRace {
+    ProtocolRace {
+        id: Some(race.id),
+        world_id: Some(race.world_id),
+        code: Some(race.code.clone()),
+        name: race.name.clone(),
+        description: race.description.clone(),
+        ability_modifiers: serde_json::from_value(race.ability_modifiers.clone())
+            .unwrap_or_default(),
+        speed: race.speed,
+        hit_points: race.hit_points,
+        special_abilities: serde_json::from_value(race.special_abilities.clone())
+            .unwrap_or_default(),
+    }
+}
+
+pub(crate) fn protocol_race_to_entity(race: &ProtocolRace) -> Race {
+    Race {
+        id: race.id.unwrap_or(0),
+        world_id: race.world_id.unwrap_or(0),
+        code: race.code.clone().unwrap_or("".to_string()),
+        name: race.name.clone(),
+        description: race.description.clone(),
+        created_at: chrono::Utc::now().naive_utc(),
+        updated_at: chrono::Utc::now().naive_utc(),
+        ability_modifiers: serde_json::to_value(&race.ability_modifiers).unwrap_or_default(),
+        speed: race.speed,
+        hit_points: race.hit_points,
+        special_abilities: serde_json::to_value(&race.special_abilities).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,175 @@
+use std::error::Error;
+
+use crate::api::{ApiResponse, Payload};
+use crate::app_state::AppState;
+use crate::game::{NewThemeFeature, Theme, World};
+use crate::{ThemeFeatureRepository, ThemeRepository, WorldRepository};
+use axum::extract::{Path, State};
+use axum::Json;
+use diesel::{Connection, PgConnection};
+
+use protocol::types::Valid;
+use protocol::{Theme as ProtocolTheme, ThemeFeature as ProtocolThemeFeature, TypeSignature};
+
+pub async fn get_theme_by_code(
+    State(state): State<AppState>,
+    Path((world_code, theme_code)): Path<(String, String)>,
+) -> ApiResponse<ProtocolTheme> {
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    match get_theme_and_features(&mut conn, &world, &theme_code) {
+        Ok(Some(theme)) => ApiResponse::JsonData(Payload { data: theme }),
+        Ok(None) => ApiResponse::NotFound("theme not found".to_string()),
+        Err(e) => {
+            eprintln!("Failed to get theme: {}", e);
+            ApiResponse::Error("Failed to get theme".to_string())
+        }
+    }
+}
+
+pub async fn create_or_update_theme(
+    State(state): State<AppState>,
+    Path((world_code, theme_code)): Path<(String, String)>,
+    Json(theme): Json<ProtocolTheme>,
+) -> ApiResponse<ProtocolTheme> {
+    if let Err(errors) = theme.validate() {
+        return ApiResponse::BadRequest(errors);
+    }
+
+    let pool = state.db_pool.clone();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return ApiResponse::Error("Failed to get connection".to_string()),
+    };
+
+    let world = match WorldRepository::find_by_code(&mut conn, &world_code) {
+        Ok(world) => world,
+        Err(_) => return ApiResponse::NotFound("World not found".to_string()),
+    };
+
+    let existing_theme = match get_theme_and_features(&mut conn, &world, &theme_code) {
+        Ok(existing_theme) => existing_theme,
+        Err(e) => {
+            eprintln!("Failed to get theme: {}", e);
+            return ApiResponse::Error("Failed to get theme".to_string());
+        }
+    };
+
+    if let Some(existing_theme) = &existing_theme {
+        if existing_theme.signature() == theme.signature() {
+            return ApiResponse::NotChanged;
+        }
+    }
+
+    let mut entity_theme = protocol_theme_to_entity(&theme);
+    entity_theme.id = existing_theme
+        .and_then(|existing_theme| existing_theme.id)
+        .unwrap_or(0);
+    entity_theme.world_id = world.id;
+
+    let txn_result = conn.transaction(|txn| {
+        save_theme(
+            txn,
+            &entity_theme,
+            theme.features.as_deref().unwrap_or_default(),
+        )
+    });
+
+    if let Err(e) = txn_result {
+        eprintln!("Failed to save theme: {}", e);
+        return ApiResponse::Error("Failed to save theme".to_string());
+    }
+
+    match get_theme_and_features(&mut conn, &world, &theme_code) {
+        Ok(Some(saved_theme)) => ApiResponse::JsonData(Payload { data: saved_theme }),
+        Ok(None) => {
+            eprintln!("Severe error: theme not found after saving");
+            ApiResponse::Error("theme not found".to_string())
+        }
+        Err(e) => {
+            eprintln!("Failed to get theme: {}", e);
+            ApiResponse::Error("Failed to get theme".to_string())
+        }
+    }
+}
+
+/// Save a theme and replace its features as a whole, so removed features do not linger.  Call
+/// it inside a transaction.
+pub(crate) fn save_theme(
+    txn: &mut PgConnection,
+    entity_theme: &Theme,
+    features: &[ProtocolThemeFeature],
+) -> diesel::QueryResult<Theme> {
+    let saved_theme = ThemeRepository::create_or_update(txn, entity_theme)?;
+    ThemeFeatureRepository::delete_by_theme(txn, saved_theme.id)?;
+    for feature in features {
+        ThemeFeatureRepository::create_feature(
+            txn,
+            &NewThemeFeature {
+                theme_id: saved_theme.id,
+                level: feature.level,
+                code: feature.code.clone(),
+                name: feature.name.clone(),
+                description: feature.description.clone(),
+            },
+        )?;
+    }
+    Ok(saved_theme)
+}
+
+fn get_theme_and_features(
+    conn: &mut PgConnection,
+    world: &World,
+    theme_code: &str,
+) -> Result<Option<ProtocolTheme>, Box<dyn Error>> {
+    let entity_theme = match ThemeRepository::find_by_code(conn, world.id, theme_code) {
+        Ok(theme) => theme,
+        Err(diesel::result::Error::NotFound) => return Ok(None),
+        Err(e) => return Err(Box::new(e)),
+    };
+
+    let entity_features = ThemeFeatureRepository::find_by_theme(conn, entity_theme.id)?;
+
+    Ok(Some(ProtocolTheme {
+        id: Some(entity_theme.id),
+        world_id: Some(entity_theme.world_id),
+        code: Some(entity_theme.code.clone()),
+        name: entity_theme.name.clone(),
+        description: entity_theme.description.clone(),
+        ability_bonuses: serde_json::from_value(entity_theme.ability_bonuses.clone())
+            .unwrap_or_default(),
+        features: Some(
+            entity_features
+                .into_iter()
+                .map(|feature| ProtocolThemeFeature {
+                    level: feature.level,
+                    code: feature.code,
+                    name: feature.name,
+                    description: feature.description,
+                })
+                .collect(),
+        ),
+    }))
+}
+
+pub(crate) fn protocol_theme_to_entity(theme: &ProtocolTheme) -> Theme {
+    Theme {
+        id: theme.id.unwrap_or(0),
+        world_id: theme.world_id.unwrap_or(0),
+        code: theme.code.clone().unwrap_or("".to_string()),
+        name: theme.name.clone(),
+        description: theme.description.clone(),
+        ability_bonuses: serde_json::to_value(&theme.ability_bonuses).unwrap_or_default(),
+        created_at: chrono::Utc::now().naive_utc(),
+        updated_at: chrono::Utc::now().naive_utc(),
+    }
+}
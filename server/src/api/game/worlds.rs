@@ -5,6 +5,7 @@ use crate::api::{ApiResponse, Payload};
 use crate::{app_state::AppState, game::World, WorldRepository};
 use axum::extract::{Path, State};
 
+use logic::{AbilityScoreMethod, ExperienceTable, DEFAULT_POINT_BUY_BUDGET};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +15,14 @@ pub struct WorldDefinition {
     pub name: String,
     pub description: String,
     pub created_at: Option<chrono::NaiveDateTime>,
+    /// How characters generate ability scores, e.g. `point_buy` or `4d6_drop_lowest`.
+    /// Defaults to point-buy.
+    pub ability_score_method: Option<String>,
+    /// The points a point-buy character may spend.  Defaults to 27.
+    pub point_buy_budget: Option<i32>,
+    /// The experience needed for each level, starting with level 1.  Defaults to the standard
+    /// table.
+    pub experience_table: Option<Vec<i64>>,
 }
 
 impl TypeSignature for WorldDefinition {
@@ -22,12 +31,27 @@ impl TypeSignature for WorldDefinition {
         signature.extend_from_slice(self.code.as_bytes());
         signature.extend_from_slice(self.name.as_bytes());
         signature.extend_from_slice(self.description.as_bytes());
+        signature.extend_from_slice(self.ability_score_method().as_bytes());
+        signature.extend_from_slice(&self.point_buy_budget().to_be_bytes());
+        for experience in self.experience_table.iter().flatten() {
+            signature.extend_from_slice(&experience.to_be_bytes());
+        }
 
         Self::as_hashed(signature)
     }
 }
 
 impl WorldDefinition {
+    pub fn ability_score_method(&self) -> String {
+        self.ability_score_method
+            .clone()
+            .unwrap_or_else(|| AbilityScoreMethod::default().code().to_string())
+    }
+
+    pub fn point_buy_budget(&self) -> i32 {
+        self.point_buy_budget.unwrap_or(DEFAULT_POINT_BUY_BUDGET)
+    }
+
     pub fn to_world(&self) -> World {
         World {
             id: self.id.unwrap_or(0),
@@ -38,11 +62,23 @@ impl WorldDefinition {
                 .created_at
                 .unwrap_or_else(|| chrono::Utc::now().naive_utc()),
             updated_at: chrono::Utc::now().naive_utc(),
+            ability_score_method: self.ability_score_method(),
+            point_buy_budget: self.point_buy_budget(),
+            experience_table: self.experience_table.clone(),
         }
     }
 
     pub fn is_valid(&self) -> bool {
-        !self.code.is_empty() && !self.name.is_empty() && !self.description.is_empty()
+        !self.code.is_empty()
+            && !self.name.is_empty()
+            && !self.description.is_empty()
+            && AbilityScoreMethod::from_code(&self.ability_score_method(), self.point_buy_budget())
+                .is_some()
+            && self.point_buy_budget() >= 0
+            && self
+                .experience_table
+                .clone()
+                .is_none_or(|thresholds| ExperienceTable::new(thresholds).is_ok())
     }
 }
 
@@ -77,7 +113,7 @@ pub async fn create_new_game_world(
         return ApiResponse::BadRequest(vec!["Invalid world definition".to_string()]);
     }
 
-    if let Ok(_) = WorldRepository::find_by_code(&mut conn, &world.code) {
+    if WorldRepository::find_by_code(&mut conn, &world.code).is_ok() {
         return ApiResponse::BadRequest(vec!["World already exists".to_string()]);
     }
 
@@ -118,13 +154,7 @@ pub async fn create_or_update_game_world(
 
     let world_to_create = World {
         id: found_world.map(|w| w.id).unwrap_or(0),
-        code: world.code.clone(),
-        name: world.name.clone(),
-        description: world.description.clone(),
-        created_at: world
-            .created_at
-            .unwrap_or_else(|| chrono::Utc::now().naive_utc()),
-        updated_at: chrono::Utc::now().naive_utc(),
+        ..world.to_world()
     };
 
     match WorldRepository::create_or_update(&mut conn, &world_to_create) {
@@ -24,7 +24,6 @@ impl Problem<Vec<String>> {
 }
 
 pub enum ApiResponse<T> {
-    Ok,
     NotFound(String),
     NotChanged,
     BadRequest(Vec<String>),
@@ -36,7 +35,6 @@ pub enum ApiResponse<T> {
 impl<T: Serialize> IntoResponse for ApiResponse<T> {
     fn into_response(self) -> Response {
         match self {
-            Self::Ok => (StatusCode::OK).into_response(),
             Self::NotFound(msg) => (StatusCode::NOT_FOUND, msg).into_response(),
             Self::NotChanged => (StatusCode::NOT_MODIFIED).into_response(),
             Self::BadRequest(msg) => (
@@ -54,18 +54,6 @@ impl CharacterClassFeatureRepository {
             .load(conn)
     }
 
-    pub fn find_feature_by_code(
-        conn: &mut PgConnection,
-        class_id_value: i64,
-        feature_code: &str,
-    ) -> QueryResult<CharacterClassFeature> {
-        character_class_features
-            .filter(class_id.eq(class_id_value))
-            .filter(code.eq(feature_code))
-            .select(CharacterClassFeature::as_select())
-            .first(conn)
-    }
-
     pub fn delete_by_code(
         conn: &mut PgConnection,
         class_id_value: i64,
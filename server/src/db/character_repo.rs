@@ -0,0 +1,38 @@
+use crate::db::models::player::{Character, NewCharacter};
+use crate::db::player_schema::player::characters::dsl::*;
+use diesel::prelude::*;
+
+pub struct CharacterRepository;
+
+impl CharacterRepository {
+    pub fn find_by_id(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        character_id: i64,
+    ) -> QueryResult<Character> {
+        characters
+            .filter(world_id.eq(world_id_value))
+            .filter(id.eq(character_id))
+            .select(Character::as_select())
+            .first(conn)
+    }
+
+    pub fn create(conn: &mut PgConnection, new_character: &NewCharacter) -> QueryResult<Character> {
+        diesel::insert_into(characters)
+            .values(new_character)
+            .returning(Character::as_select())
+            .get_result(conn)
+    }
+
+    pub fn update_feats(
+        conn: &mut PgConnection,
+        character_id: i64,
+        feat_codes: serde_json::Value,
+    ) -> QueryResult<Character> {
+        diesel::update(characters)
+            .filter(id.eq(character_id))
+            .set(feats.eq(feat_codes))
+            .returning(Character::as_select())
+            .get_result(conn)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::db::game_schema::game::damage_types::dsl::*;
+use crate::db::models::game::{DamageType, NewDamageType};
+use diesel::prelude::*;
+
+pub struct DamageTypeRepository;
+
+impl DamageTypeRepository {
+    pub fn find_by_code(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        damage_type_code: &str,
+    ) -> QueryResult<DamageType> {
+        damage_types
+            .filter(world_id.eq(world_id_value))
+            .filter(code.eq(damage_type_code))
+            .select(DamageType::as_select())
+            .first(conn)
+    }
+
+    pub fn find_by_world(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+    ) -> QueryResult<Vec<DamageType>> {
+        damage_types
+            .filter(world_id.eq(world_id_value))
+            .select(DamageType::as_select())
+            .load(conn)
+    }
+
+    pub fn create(
+        conn: &mut PgConnection,
+        new_damage_type: &NewDamageType,
+    ) -> QueryResult<DamageType> {
+        diesel::insert_into(damage_types)
+            .values(new_damage_type)
+            .returning(DamageType::as_select())
+            .get_result(conn)
+    }
+
+    pub fn update(conn: &mut PgConnection, damage_type: &DamageType) -> QueryResult<DamageType> {
+        diesel::update(damage_types)
+            .filter(id.eq(&damage_type.id))
+            .set((
+                name.eq(&damage_type.name),
+                description.eq(&damage_type.description),
+                updated_at.eq(&damage_type.updated_at),
+            ))
+            .returning(DamageType::as_select())
+            .get_result(conn)
+    }
+
+    pub fn create_or_update(
+        conn: &mut PgConnection,
+        damage_type: &DamageType,
+    ) -> QueryResult<DamageType> {
+        if damage_type.id == 0 {
+            DamageTypeRepository::create(conn, &damage_type.as_new_damage_type())
+        } else {
+            DamageTypeRepository::update(conn, damage_type)
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::db::game_schema::game::feats::dsl::*;
+use crate::db::models::game::Feat;
+use diesel::prelude::*;
+
+pub struct FeatRepository;
+
+impl FeatRepository {
+    pub fn find_by_code(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        feat_code: &str,
+    ) -> QueryResult<Feat> {
+        feats
+            .filter(world_id.eq(world_id_value))
+            .filter(code.eq(feat_code))
+            .select(Feat::as_select())
+            .first(conn)
+    }
+}
@@ -232,6 +232,58 @@ pub mod game {
         }
     }
 
+    diesel::table! {
+        /// Representation of the `game.damage_types` table.
+        ///
+        /// (Automatically generated by Diesel.)
+        game.damage_types (id) {
+            /// The `id` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            id -> Int8,
+            /// The `world_id` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            world_id -> Int8,
+            /// The `code` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            code -> Varchar,
+            /// The `name` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            name -> Varchar,
+            /// The `description` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Text`.
+            ///
+            /// (Automatically generated by Diesel.)
+            description -> Text,
+            /// The `created_at` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            created_at -> Timestamp,
+            /// The `updated_at` column of the `game.damage_types` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            updated_at -> Timestamp,
+        }
+    }
+
     diesel::table! {
         /// Representation of the `game.enemies` table.
         ///
@@ -348,6 +400,18 @@ pub mod game {
             ///
             /// (Automatically generated by Diesel.)
             updated_at -> Timestamp,
+            /// The `charisma` column of the `game.enemies` table.
+            ///
+            /// Its SQL type is `Int4`.
+            ///
+            /// (Automatically generated by Diesel.)
+            charisma -> Int4,
+            /// The `damage_modifiers` column of the `game.enemies` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            damage_modifiers -> Jsonb,
         }
     }
 
@@ -388,6 +452,12 @@ pub mod game {
             ///
             /// (Automatically generated by Diesel.)
             description -> Text,
+            /// The `prerequisites` column of the `game.feats` table.
+            ///
+            /// Its SQL type is `Nullable<Text>`.
+            ///
+            /// (Automatically generated by Diesel.)
+            prerequisites -> Nullable<Text>,
             /// The `created_at` column of the `game.feats` table.
             ///
             /// Its SQL type is `Timestamp`.
@@ -681,6 +751,30 @@ pub mod game {
             ///
             /// (Automatically generated by Diesel.)
             created_at -> Timestamp,
+            /// The `ability_modifiers` column of the `game.races` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            ability_modifiers -> Jsonb,
+            /// The `speed` column of the `game.races` table.
+            ///
+            /// Its SQL type is `Int4`.
+            ///
+            /// (Automatically generated by Diesel.)
+            speed -> Int4,
+            /// The `hit_points` column of the `game.races` table.
+            ///
+            /// Its SQL type is `Int4`.
+            ///
+            /// (Automatically generated by Diesel.)
+            hit_points -> Int4,
+            /// The `special_abilities` column of the `game.races` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            special_abilities -> Jsonb,
             /// The `updated_at` column of the `game.races` table.
             ///
             /// Its SQL type is `Timestamp`.
@@ -739,6 +833,141 @@ pub mod game {
             ///
             /// (Automatically generated by Diesel.)
             updated_at -> Timestamp,
+            /// The `ability` column of the `game.skills` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 16]
+            ability -> Varchar,
+            /// The `trained_only` column of the `game.skills` table.
+            ///
+            /// Its SQL type is `Bool`.
+            ///
+            /// (Automatically generated by Diesel.)
+            trained_only -> Bool,
+            /// The `armor_penalty` column of the `game.skills` table.
+            ///
+            /// Its SQL type is `Bool`.
+            ///
+            /// (Automatically generated by Diesel.)
+            armor_penalty -> Bool,
+        }
+    }
+
+    diesel::table! {
+        /// Representation of the `game.theme_features` table.
+        ///
+        /// (Automatically generated by Diesel.)
+        game.theme_features (id) {
+            /// The `id` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            id -> Int8,
+            /// The `theme_id` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            theme_id -> Int8,
+            /// The `level` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Int4`.
+            ///
+            /// (Automatically generated by Diesel.)
+            level -> Int4,
+            /// The `code` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            code -> Varchar,
+            /// The `name` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            name -> Varchar,
+            /// The `description` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Text`.
+            ///
+            /// (Automatically generated by Diesel.)
+            description -> Text,
+            /// The `created_at` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            created_at -> Timestamp,
+            /// The `updated_at` column of the `game.theme_features` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            updated_at -> Timestamp,
+        }
+    }
+
+    diesel::table! {
+        /// Representation of the `game.themes` table.
+        ///
+        /// (Automatically generated by Diesel.)
+        game.themes (id) {
+            /// The `id` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            id -> Int8,
+            /// The `world_id` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Int8`.
+            ///
+            /// (Automatically generated by Diesel.)
+            world_id -> Int8,
+            /// The `code` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            code -> Varchar,
+            /// The `name` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            name -> Varchar,
+            /// The `description` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Text`.
+            ///
+            /// (Automatically generated by Diesel.)
+            description -> Text,
+            /// The `ability_bonuses` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            ability_bonuses -> Jsonb,
+            /// The `created_at` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            created_at -> Timestamp,
+            /// The `updated_at` column of the `game.themes` table.
+            ///
+            /// Its SQL type is `Timestamp`.
+            ///
+            /// (Automatically generated by Diesel.)
+            updated_at -> Timestamp,
         }
     }
 
@@ -887,12 +1116,32 @@ pub mod game {
             /// (Automatically generated by Diesel.)
             #[max_length = 32]
             code -> Varchar,
+            /// The `ability_score_method` column of the `game.worlds` table.
+            ///
+            /// Its SQL type is `Varchar`.
+            ///
+            /// (Automatically generated by Diesel.)
+            #[max_length = 32]
+            ability_score_method -> Varchar,
+            /// The `point_buy_budget` column of the `game.worlds` table.
+            ///
+            /// Its SQL type is `Int4`.
+            ///
+            /// (Automatically generated by Diesel.)
+            point_buy_budget -> Int4,
+            /// The `experience_table` column of the `game.worlds` table.
+            ///
+            /// Its SQL type is `Nullable<Array<Int8>>`.
+            ///
+            /// (Automatically generated by Diesel.)
+            experience_table -> Nullable<Array<Int8>>,
         }
     }
 
     diesel::joinable!(character_class_features -> character_classes (class_id));
     diesel::joinable!(character_classes -> worlds (world_id));
     diesel::joinable!(currency -> worlds (world_id));
+    diesel::joinable!(damage_types -> worlds (world_id));
     diesel::joinable!(enemies -> worlds (world_id));
     diesel::joinable!(feats -> worlds (world_id));
     diesel::joinable!(items -> item_categories (category_id));
@@ -901,6 +1150,8 @@ pub mod game {
     diesel::joinable!(npc_spawn_rules -> world_nodes (world_node_id));
     diesel::joinable!(races -> worlds (world_id));
     diesel::joinable!(skills -> worlds (world_id));
+    diesel::joinable!(theme_features -> themes (theme_id));
+    diesel::joinable!(themes -> worlds (world_id));
     diesel::joinable!(world_node_features -> world_nodes (world_node_id));
     diesel::joinable!(world_nodes -> worlds (world_id));
 
@@ -909,6 +1160,7 @@ pub mod game {
         character_class_features,
         character_classes,
         currency,
+        damage_types,
         enemies,
         feats,
         item_categories,
@@ -917,6 +1169,8 @@ pub mod game {
         npc_templates,
         races,
         skills,
+        theme_features,
+        themes,
         world_node_features,
         world_nodes,
         worlds,
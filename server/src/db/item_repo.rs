@@ -24,6 +24,13 @@ impl ItemRepository {
             .first(conn)
     }
 
+    pub fn find_by_world(conn: &mut PgConnection, item_world_id: i64) -> QueryResult<Vec<Item>> {
+        items
+            .filter(world_id.eq(item_world_id))
+            .select(Item::as_select())
+            .load(conn)
+    }
+
     pub fn create(conn: &mut PgConnection, new_item: &NewItem) -> QueryResult<Item> {
         diesel::insert_into(items)
             .values(new_item)
@@ -1,16 +1,32 @@
 pub mod character_class_feature_repo;
 pub mod character_class_repo;
-pub mod connection;
+pub mod character_repo;
+pub mod damage_type_repo;
+pub mod enemy_repo;
+pub mod feat_repo;
 pub mod game_schema;
 pub mod item_category_repo;
 pub mod item_repo;
 pub mod models;
 pub mod player_schema;
+pub mod race_repo;
+pub mod skill_repo;
 pub mod system_schema;
+pub mod theme_feature_repo;
+pub mod theme_repo;
 pub mod world_repo;
 
 pub use character_class_feature_repo::*;
 pub use character_class_repo::*;
+pub use character_repo::*;
+pub use damage_type_repo::*;
+pub use enemy_repo::*;
+pub use feat_repo::*;
+pub use item_category_repo::*;
 pub use item_repo::*;
 pub use models::*;
+pub use race_repo::*;
+pub use skill_repo::*;
+pub use theme_feature_repo::*;
+pub use theme_repo::*;
 pub use world_repo::*;
@@ -71,6 +71,9 @@ pub mod game {
         pub description: String,
         pub created_at: NaiveDateTime,
         pub updated_at: NaiveDateTime,
+        pub ability_score_method: String,
+        pub point_buy_budget: i32,
+        pub experience_table: Option<Vec<i64>>,
     }
 
     impl World {
@@ -86,8 +89,25 @@ pub mod game {
                 description: self.description.clone(),
                 created_at: self.created_at,
                 updated_at: self.updated_at,
+                ability_score_method: self.ability_score_method.clone(),
+                point_buy_budget: self.point_buy_budget,
+                experience_table: self.experience_table.clone(),
             }
         }
+
+        /// The way characters in this world generate their ability scores.
+        pub fn ability_score_method(&self) -> Option<logic::AbilityScoreMethod> {
+            logic::AbilityScoreMethod::from_code(&self.ability_score_method, self.point_buy_budget)
+        }
+
+        /// The experience needed for each level in this world, or the default table when the
+        /// world does not have its own.
+        pub fn experience_table(&self) -> logic::ExperienceTable {
+            self.experience_table
+                .clone()
+                .and_then(|thresholds| logic::ExperienceTable::new(thresholds).ok())
+                .unwrap_or_default()
+        }
     }
 
     impl TypeSignature for World {
@@ -96,6 +116,11 @@ pub mod game {
             signature.extend_from_slice(self.code.as_bytes());
             signature.extend_from_slice(self.name.as_bytes());
             signature.extend_from_slice(self.description.as_bytes());
+            signature.extend_from_slice(self.ability_score_method.as_bytes());
+            signature.extend_from_slice(&self.point_buy_budget.to_be_bytes());
+            for experience in self.experience_table.iter().flatten() {
+                signature.extend_from_slice(&experience.to_be_bytes());
+            }
 
             Self::as_hashed(signature)
         }
@@ -111,6 +136,9 @@ pub mod game {
         pub description: String,
         pub created_at: NaiveDateTime,
         pub updated_at: NaiveDateTime,
+        pub ability_score_method: String,
+        pub point_buy_budget: i32,
+        pub experience_table: Option<Vec<i64>>,
     }
 
     impl NewWorld {
@@ -126,6 +154,11 @@ pub mod game {
             signature.extend_from_slice(self.code.as_bytes());
             signature.extend_from_slice(self.name.as_bytes());
             signature.extend_from_slice(self.description.as_bytes());
+            signature.extend_from_slice(self.ability_score_method.as_bytes());
+            signature.extend_from_slice(&self.point_buy_budget.to_be_bytes());
+            for experience in self.experience_table.iter().flatten() {
+                signature.extend_from_slice(&experience.to_be_bytes());
+            }
 
             Self::as_hashed(signature)
         }
@@ -311,6 +344,143 @@ pub mod game {
         }
     }
 
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::themes)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Theme {
+        pub id: i64,
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub ability_bonuses: serde_json::Value,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+    }
+
+    impl Theme {
+        // as_json returns a serialized json string of the Setting struct.
+        pub fn as_json(&self) -> String {
+            serde_json::to_string(self).unwrap()
+        }
+
+        pub fn as_new_theme(&self) -> NewTheme {
+            NewTheme {
+                world_id: self.world_id,
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+                ability_bonuses: self.ability_bonuses.clone(),
+            }
+        }
+
+        /// The theme the rules use, with its features.  Bonuses to abilities the rules do not
+        /// know are ignored.
+        pub fn to_logic(&self, features: &[ThemeFeature]) -> logic::Theme {
+            let mut theme = logic::Theme::new(&self.code, &self.name);
+            if let Some(bonuses) = self.ability_bonuses.as_object() {
+                for (ability, bonus) in bonuses {
+                    if let (Some(ability), Some(bonus)) =
+                        (logic::Ability::from_name(ability), bonus.as_i64())
+                    {
+                        theme = theme.with_ability_bonus(ability, bonus as i32);
+                    }
+                }
+            }
+            for feature in features {
+                theme = theme.with_feature(logic::ThemeFeature {
+                    level: feature.level,
+                    code: feature.code.clone(),
+                    name: feature.name.clone(),
+                    description: feature.description.clone(),
+                });
+            }
+            theme
+        }
+    }
+
+    #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
+    #[diesel(table_name = crate::game_schema::game::themes)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    pub struct NewTheme {
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub ability_bonuses: serde_json::Value,
+    }
+
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::theme_features)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct ThemeFeature {
+        pub id: i64,
+        pub theme_id: i64,
+        pub level: i32,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+    }
+
+    #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
+    #[diesel(table_name = crate::game_schema::game::theme_features)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    pub struct NewThemeFeature {
+        pub theme_id: i64,
+        pub level: i32,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+    }
+
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::damage_types)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct DamageType {
+        pub id: i64,
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+    }
+
+    impl DamageType {
+        pub fn as_new_damage_type(&self) -> NewDamageType {
+            NewDamageType {
+                world_id: self.world_id,
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+            }
+        }
+
+        /// The damage type the rules use.
+        pub fn to_logic(&self) -> logic::DamageType {
+            logic::DamageType {
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+            }
+        }
+    }
+
+    #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
+    #[diesel(table_name = crate::game_schema::game::damage_types)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    pub struct NewDamageType {
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+    }
+
     #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
     #[diesel(table_name = crate::game_schema::game::world_nodes)]
     #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -442,6 +612,16 @@ pub mod game {
                 base_price: self.base_price,
             }
         }
+
+        /// The item the rules use.
+        pub fn to_logic(&self) -> logic::Item {
+            let mut item = logic::Item::new(&self.code, &self.item_type, &self.name)
+                .with_category(self.category_id);
+            item.description = self.description.clone();
+            item.item_properties = self.item_properties.clone();
+            item.base_price = self.base_price;
+            item
+        }
     }
 
     impl TypeSignature for Item {
@@ -540,6 +720,10 @@ pub mod game {
         pub description: String,
         pub created_at: NaiveDateTime,
         pub updated_at: NaiveDateTime,
+        pub ability_modifiers: serde_json::Value,
+        pub speed: i32,
+        pub hit_points: i32,
+        pub special_abilities: serde_json::Value,
     }
 
     impl Race {
@@ -547,6 +731,213 @@ pub mod game {
         pub fn as_json(&self) -> String {
             serde_json::to_string(self).unwrap()
         }
+
+        pub fn as_new_race(&self) -> NewRace {
+            NewRace {
+                world_id: self.world_id,
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+                ability_modifiers: self.ability_modifiers.clone(),
+                speed: self.speed,
+                hit_points: self.hit_points,
+                special_abilities: self.special_abilities.clone(),
+            }
+        }
+
+        /// The race the rules use, or why its stored ability modifiers or special abilities
+        /// cannot be read.
+        pub fn to_logic(&self) -> Result<logic::Race, String> {
+            let ability_modifiers = serde_json::from_value(self.ability_modifiers.clone())
+                .map_err(|e| {
+                    format!("race '{}' has invalid ability modifiers: {}", self.code, e)
+                })?;
+            let special_abilities = serde_json::from_value(self.special_abilities.clone())
+                .map_err(|e| {
+                    format!("race '{}' has invalid special abilities: {}", self.code, e)
+                })?;
+
+            Ok(
+                logic::Race::new(&self.name, ability_modifiers, special_abilities, self.speed)
+                    .with_code(&self.code)
+                    .with_hit_points(self.hit_points),
+            )
+        }
+    }
+
+    #[derive(Insertable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::races)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct NewRace {
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub ability_modifiers: serde_json::Value,
+        pub speed: i32,
+        pub hit_points: i32,
+        pub special_abilities: serde_json::Value,
+    }
+
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::enemies)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Enemy {
+        pub id: i64,
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub class: String,
+        pub level: i32,
+        pub hit_points: i32,
+        pub stamina: i32,
+        pub strength: i32,
+        pub dexterity: i32,
+        pub constitution: i32,
+        pub intelligence: i32,
+        pub wisdom: i32,
+        pub charisma: i32,
+        pub weapons: serde_json::Value,
+        pub armor: serde_json::Value,
+        pub damage_modifiers: serde_json::Value,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+    }
+
+    impl Enemy {
+        /// The stored enemy for one the rules use.  It is new until given the id of the
+        /// stored enemy it replaces.
+        pub fn from_logic(world_id: i64, enemy: &logic::Enemy) -> Enemy {
+            Enemy {
+                id: 0,
+                world_id,
+                code: enemy.code.clone(),
+                name: enemy.name.clone(),
+                description: enemy.description.clone(),
+                class: enemy.class.clone(),
+                level: enemy.level,
+                hit_points: enemy.hit_points,
+                stamina: enemy.stamina,
+                strength: enemy.strength,
+                dexterity: enemy.dexterity,
+                constitution: enemy.constitution,
+                intelligence: enemy.intelligence,
+                wisdom: enemy.wisdom,
+                charisma: enemy.charisma,
+                weapons: serde_json::to_value(&enemy.weapons).unwrap_or_default(),
+                armor: serde_json::to_value(&enemy.armor).unwrap_or_default(),
+                damage_modifiers: serde_json::to_value(&enemy.damage_modifiers).unwrap_or_default(),
+                created_at: chrono::Utc::now().naive_utc(),
+                updated_at: chrono::Utc::now().naive_utc(),
+            }
+        }
+
+        pub fn as_new_enemy(&self) -> NewEnemy {
+            NewEnemy {
+                world_id: self.world_id,
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+                class: self.class.clone(),
+                level: self.level,
+                hit_points: self.hit_points,
+                stamina: self.stamina,
+                strength: self.strength,
+                dexterity: self.dexterity,
+                constitution: self.constitution,
+                intelligence: self.intelligence,
+                wisdom: self.wisdom,
+                charisma: self.charisma,
+                weapons: self.weapons.clone(),
+                armor: self.armor.clone(),
+                damage_modifiers: self.damage_modifiers.clone(),
+            }
+        }
+    }
+
+    #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
+    #[diesel(table_name = crate::game_schema::game::enemies)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    pub struct NewEnemy {
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub class: String,
+        pub level: i32,
+        pub hit_points: i32,
+        pub stamina: i32,
+        pub strength: i32,
+        pub dexterity: i32,
+        pub constitution: i32,
+        pub intelligence: i32,
+        pub wisdom: i32,
+        pub charisma: i32,
+        pub weapons: serde_json::Value,
+        pub armor: serde_json::Value,
+        pub damage_modifiers: serde_json::Value,
+    }
+
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::feats)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Feat {
+        pub id: i64,
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub prerequisites: Option<String>,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+    }
+
+    #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
+    #[diesel(table_name = crate::game_schema::game::skills)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct Skill {
+        pub id: i64,
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub created_at: NaiveDateTime,
+        pub updated_at: NaiveDateTime,
+        pub ability: String,
+        pub trained_only: bool,
+        pub armor_penalty: bool,
+    }
+
+    impl Skill {
+        pub fn as_new_skill(&self) -> NewSkill {
+            NewSkill {
+                world_id: self.world_id,
+                code: self.code.clone(),
+                name: self.name.clone(),
+                description: self.description.clone(),
+                ability: self.ability.clone(),
+                trained_only: self.trained_only,
+                armor_penalty: self.armor_penalty,
+            }
+        }
+    }
+
+    #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
+    #[diesel(table_name = crate::game_schema::game::skills)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    pub struct NewSkill {
+        pub world_id: i64,
+        pub code: String,
+        pub name: String,
+        pub description: String,
+        pub ability: String,
+        pub trained_only: bool,
+        pub armor_penalty: bool,
     }
 
     #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
@@ -592,6 +983,10 @@ pub mod player {
         pub abilities: serde_json::Value,
         pub feats: serde_json::Value,
         pub skills: serde_json::Value,
+        /// What the character gained at each level, see [`logic::progression::LevelGain`].
+        pub level_gains: serde_json::Value,
+        /// The character's active conditions, see [`logic::Conditions`].
+        pub conditions: serde_json::Value,
     }
 
     impl Character {
@@ -601,6 +996,30 @@ pub mod player {
         }
     }
 
+    #[derive(Insertable, Debug, Clone)]
+    #[diesel(table_name = crate::player_schema::player::characters)]
+    #[diesel(check_for_backend(diesel::pg::Pg))]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct NewCharacter {
+        pub world_id: i64,
+        pub user_id: Uuid,
+        pub race_id: i64,
+        pub name: String,
+        pub class: String,
+        pub theme: String,
+        pub level: i32,
+        pub experience: i64,
+        pub hit_points: i32,
+        pub stamina: i32,
+        pub abilities: serde_json::Value,
+        pub feats: serde_json::Value,
+        pub skills: serde_json::Value,
+        /// What the character gained at each level, see [`logic::progression::LevelGain`].
+        pub level_gains: serde_json::Value,
+        /// The character's active conditions, see [`logic::Conditions`].
+        pub conditions: serde_json::Value,
+    }
+
     #[derive(Insertable, Queryable, QueryableByName, Selectable, Identifiable, Debug, Clone)]
     #[diesel(table_name = crate::player_schema::player::character_inventory)]
     #[diesel(check_for_backend(diesel::pg::Pg))]
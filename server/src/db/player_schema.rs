@@ -176,6 +176,18 @@ pub mod player {
             ///
             /// (Automatically generated by Diesel.)
             skills -> Jsonb,
+            /// The `level_gains` column of the `player.characters` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            level_gains -> Jsonb,
+            /// The `conditions` column of the `player.characters` table.
+            ///
+            /// Its SQL type is `Jsonb`.
+            ///
+            /// (Automatically generated by Diesel.)
+            conditions -> Jsonb,
         }
     }
 
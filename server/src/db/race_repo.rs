@@ -0,0 +1,57 @@
+use crate::db::game_schema::game::races::dsl::*;
+use crate::db::models::game::{NewRace, Race};
+use diesel::prelude::*;
+
+pub struct RaceRepository;
+
+impl RaceRepository {
+    pub fn find_by_id(conn: &mut PgConnection, race_id: i64) -> QueryResult<Race> {
+        races
+            .filter(id.eq(race_id))
+            .select(Race::as_select())
+            .first(conn)
+    }
+
+    pub fn find_by_code(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        race_code: &str,
+    ) -> QueryResult<Race> {
+        races
+            .filter(world_id.eq(world_id_value))
+            .filter(code.eq(race_code))
+            .select(Race::as_select())
+            .first(conn)
+    }
+
+    pub fn create(conn: &mut PgConnection, new_race: &NewRace) -> QueryResult<Race> {
+        diesel::insert_into(races)
+            .values(new_race)
+            .returning(Race::as_select())
+            .get_result(conn)
+    }
+
+    pub fn update(conn: &mut PgConnection, race: &Race) -> QueryResult<Race> {
+        diesel::update(races)
+            .filter(id.eq(&race.id))
+            .set((
+                name.eq(&race.name),
+                description.eq(&race.description),
+                updated_at.eq(&race.updated_at),
+                ability_modifiers.eq(&race.ability_modifiers),
+                speed.eq(&race.speed),
+                hit_points.eq(&race.hit_points),
+                special_abilities.eq(&race.special_abilities),
+            ))
+            .returning(Race::as_select())
+            .get_result(conn)
+    }
+
+    pub fn create_or_update(conn: &mut PgConnection, race: &Race) -> QueryResult<Race> {
+        if race.id == 0 {
+            RaceRepository::create(conn, &race.as_new_race())
+        } else {
+            RaceRepository::update(conn, race)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::db::game_schema::game::skills::dsl::*;
+use crate::db::models::game::{NewSkill, Skill};
+use diesel::prelude::*;
+
+pub struct SkillRepository;
+
+impl SkillRepository {
+    pub fn find_by_code(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        skill_code: &str,
+    ) -> QueryResult<Skill> {
+        skills
+            .filter(world_id.eq(world_id_value))
+            .filter(code.eq(skill_code))
+            .select(Skill::as_select())
+            .first(conn)
+    }
+
+    pub fn create(conn: &mut PgConnection, new_skill: &NewSkill) -> QueryResult<Skill> {
+        diesel::insert_into(skills)
+            .values(new_skill)
+            .returning(Skill::as_select())
+            .get_result(conn)
+    }
+
+    pub fn update(conn: &mut PgConnection, skill: &Skill) -> QueryResult<Skill> {
+        diesel::update(skills)
+            .filter(id.eq(&skill.id))
+            .set((
+                name.eq(&skill.name),
+                description.eq(&skill.description),
+                ability.eq(&skill.ability),
+                trained_only.eq(&skill.trained_only),
+                armor_penalty.eq(&skill.armor_penalty),
+                updated_at.eq(&skill.updated_at),
+            ))
+            .returning(Skill::as_select())
+            .get_result(conn)
+    }
+
+    pub fn create_or_update(conn: &mut PgConnection, skill: &Skill) -> QueryResult<Skill> {
+        if skill.id == 0 {
+            SkillRepository::create(conn, &skill.as_new_skill())
+        } else {
+            SkillRepository::update(conn, skill)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::db::game_schema::game::theme_features::dsl::*;
+use crate::db::models::game::{NewThemeFeature, ThemeFeature};
+use diesel::prelude::*;
+
+pub struct ThemeFeatureRepository;
+
+impl ThemeFeatureRepository {
+    pub fn create_feature(
+        conn: &mut PgConnection,
+        new_feature: &NewThemeFeature,
+    ) -> QueryResult<ThemeFeature> {
+        diesel::insert_into(theme_features)
+            .values(new_feature)
+            .returning(ThemeFeature::as_select())
+            .get_result(conn)
+    }
+
+    pub fn find_by_theme(
+        conn: &mut PgConnection,
+        theme_id_value: i64,
+    ) -> QueryResult<Vec<ThemeFeature>> {
+        theme_features
+            .filter(theme_id.eq(theme_id_value))
+            .order((level.asc(), id.asc()))
+            .select(ThemeFeature::as_select())
+            .load(conn)
+    }
+
+    pub fn delete_by_theme(conn: &mut PgConnection, theme_id_value: i64) -> QueryResult<usize> {
+        diesel::delete(theme_features.filter(theme_id.eq(theme_id_value))).execute(conn)
+    }
+}
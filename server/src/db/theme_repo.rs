@@ -0,0 +1,47 @@
+use crate::db::game_schema::game::themes::dsl::*;
+use crate::db::models::game::{NewTheme, Theme};
+use diesel::prelude::*;
+
+pub struct ThemeRepository;
+
+impl ThemeRepository {
+    pub fn find_by_code(
+        conn: &mut PgConnection,
+        world_id_value: i64,
+        theme_code: &str,
+    ) -> QueryResult<Theme> {
+        themes
+            .filter(world_id.eq(world_id_value))
+            .filter(code.eq(theme_code))
+            .select(Theme::as_select())
+            .first(conn)
+    }
+
+    pub fn create(conn: &mut PgConnection, new_theme: &NewTheme) -> QueryResult<Theme> {
+        diesel::insert_into(themes)
+            .values(new_theme)
+            .returning(Theme::as_select())
+            .get_result(conn)
+    }
+
+    pub fn update(conn: &mut PgConnection, theme: &Theme) -> QueryResult<Theme> {
+        diesel::update(themes)
+            .filter(id.eq(&theme.id))
+            .set((
+                name.eq(&theme.name),
+                description.eq(&theme.description),
+                ability_bonuses.eq(&theme.ability_bonuses),
+                updated_at.eq(&theme.updated_at),
+            ))
+            .returning(Theme::as_select())
+            .get_result(conn)
+    }
+
+    pub fn create_or_update(conn: &mut PgConnection, theme: &Theme) -> QueryResult<Theme> {
+        if theme.id == 0 {
+            ThemeRepository::create(conn, &theme.as_new_theme())
+        } else {
+            ThemeRepository::update(conn, theme)
+        }
+    }
+}
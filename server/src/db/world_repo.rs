@@ -25,6 +25,9 @@ impl WorldRepository {
             .set((
                 name.eq(&world.name),
                 description.eq(&world.description),
+                ability_score_method.eq(&world.ability_score_method),
+                point_buy_budget.eq(&world.point_buy_budget),
+                experience_table.eq(&world.experience_table),
                 updated_at.eq(&world.updated_at),
             ))
             .returning(World::as_select())
@@ -1,12 +0,0 @@
-pub mod character_class;
-pub mod enemy;
-pub mod game_object;
-pub mod item;
-pub mod spec;
-pub mod world;
-
-pub use character_class::CharacterClassSpec;
-pub use enemy::EnemySpec;
-pub use item::ItemSpec;
-pub use spec::Spec;
-pub use world::WorldSpec;
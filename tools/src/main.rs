@@ -1,15 +1,21 @@
-use std::collections::HashMap;
-
-mod game;
 use clap::{arg, command, Command};
-use game::game_object::GameObject;
-use game::{world, CharacterClassSpec, ItemSpec, Spec, WorldSpec};
-use walkdir::WalkDir;
-
-use serde_json::{self, Value};
-use std::fs;
-use std::path::Path;
-use tokio;
+use logic::game_data::{
+    load_world_into, read_world_documents, ContentKind, GameData, IntegrityCheck,
+};
+use logic::utilities::{analyze_dice_with, ExpressionContext};
+use serde_json::Value;
+
+/// The kinds of content the server imports, in the order they are sent.  Other content, such
+/// as conditions, is only checked.
+const IMPORTS: [&str; 7] = [
+    "skill",
+    "damagetype",
+    "race",
+    "theme",
+    "characterclass",
+    "item",
+    "enemy",
+];
 
 #[tokio::main]
 async fn main() {
@@ -23,218 +29,168 @@ async fn main() {
                     ))
                     .arg(arg!(--server <VALUE>).default_value("http://localhost:2900")),
             )
+            .subcommand(
+                Command::new("analyze-dice")
+                    .about("Show the odds of an expression such as \"roll(1d6) + CON\"")
+                    .arg(arg!(<EXPRESSION>))
+                    .arg(arg!(--set <VARIABLE> ... "A variable the expression reads, e.g. CON=2")),
+            )
             .get_matches();
 
     match matches.subcommand() {
         Some(("load-resources", sub_m)) => {
             let data = sub_m.get_one::<String>("data").unwrap();
             let server = sub_m.get_one::<String>("server").unwrap();
-            let hash_map = match load_all_game_objects(data) {
-                Ok(o) => o,
-                Err(e) => {
-                    println!("Error loading game objects: {}", e);
-                    HashMap::new()
+            println!("Loading resources from {}", data);
+
+            // the rules engine's loader checks every document before anything is uploaded
+            let mut game_data = GameData::new();
+            let world_code = match load_world_into(&mut game_data, data) {
+                Ok(world_code) => world_code,
+                Err(errors) => {
+                    for error in errors {
+                        println!("INVALID {}", error);
+                    }
+                    return;
                 }
             };
-
-            // convert our hash map to a vector of game objects
-            let mut game_objects: Vec<GameObject> = Vec::new();
-            for (_, objects) in hash_map {
-                for object in objects {
-                    game_objects.push(object);
-                }
-            }
-
-            let client = reqwest::Client::new();
-            let assets = GameAssets::from_object_array(game_objects.clone());
-
-            if assets.world.is_none() {
+            if !game_data.is_world_defined(&world_code) {
                 println!("No world found in assets");
                 return;
             }
+            // skills are not stored by the server, so races may refer to any skill
+            let issues = IntegrityCheck::new(&game_data)
+                .with_external(ContentKind::Skill)
+                .run();
+            if !issues.is_empty() {
+                for issue in issues {
+                    println!("INVALID {}", issue);
+                }
+                return;
+            }
 
-            let world_code = assets.world.clone().unwrap().code.unwrap();
-            if assets.world.is_some() {
-                let url = format!("{}/game/{}", server, world_code);
-                match client
-                    .put(url)
-                    .body(serde_json::to_string(&assets.world).unwrap())
-                    .header("Content-Type", "application/json")
-                    .send()
-                    .await
-                {
-                    Ok(response) => {
-                        if response.status().as_u16() != 304 {
-                            println!(
-                                "CREATED World: {} - {}",
-                                world_code,
-                                assets.world.clone().unwrap().description
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        println!("Error: {}", e);
+            let documents = match read_world_documents(data) {
+                Ok(documents) => documents,
+                Err(errors) => {
+                    for error in errors {
+                        println!("INVALID {}", error);
                     }
+                    return;
                 }
+            };
+
+            let client = reqwest::Client::new();
+            for document in documents.iter().filter(|d| d.kind() == Ok("world")) {
+                let url = format!("{}/game/{}", server, world_code);
+                upload(&client, &url, "World", &world_code, document.spec_value()).await;
             }
 
-            if !assets.character_classes.is_empty() {
-                for character_class in assets.character_classes {
-                    let character_class_code = character_class.clone().code.unwrap();
-                    let url = format!(
-                        "{}/game/{}/classes/{}",
-                        server, world_code, character_class_code
-                    );
-                    match client
-                        .put(url)
-                        .body(serde_json::to_string(&character_class).unwrap())
-                        .header("Content-Type", "application/json")
-                        .send()
-                        .await
-                    {
-                        Ok(response) => {
-                            if response.status().as_u16() != 304 {
-                                println!(
-                                    "CREATED Character Class: {} - {}",
-                                    character_class_code,
-                                    character_class.clone().description
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            println!("Error: {}", e);
-                        }
+            // the rest is imported as one batch, which the server checks again before saving
+            let batch: Vec<&Value> = IMPORTS
+                .iter()
+                .flat_map(|kind| documents.iter().filter(move |d| d.kind() == Ok(*kind)))
+                .map(|document| &document.value)
+                .collect();
+            let url = format!("{}/game/{}/import", server, world_code);
+            import(&client, &url, &batch).await;
+        }
+        Some(("analyze-dice", sub_m)) => {
+            let expression = sub_m.get_one::<String>("EXPRESSION").unwrap();
+            let mut context = ExpressionContext::new();
+            for variable in sub_m.get_many::<String>("set").into_iter().flatten() {
+                match variable.split_once('=') {
+                    Some((name, value)) => context.set(name.trim(), value.trim()),
+                    None => {
+                        println!("INVALID {}: expected NAME=VALUE", variable);
+                        return;
                     }
                 }
             }
 
-            if !assets.items.is_empty() {
-                for item in assets.items {
-                    let item_code = item.clone().code.unwrap();
-                    let url = format!("{}/game/{}/items/{}", server, world_code, item_code);
-                    match client
-                        .put(url)
-                        .body(serde_json::to_string(&item).unwrap())
-                        .header("Content-Type", "application/json")
-                        .send()
-                        .await
-                    {
-                        Ok(response) => {
-                            if response.status().as_u16() != 304 {
-                                println!(
-                                    "CREATED Item: {} - {}",
-                                    item_code,
-                                    item.clone().description
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            println!("Error: {}", e);
-                        }
-                    }
+            let distribution = match analyze_dice_with(expression, &context) {
+                Ok(distribution) => distribution,
+                Err(error) => {
+                    println!("INVALID {}", error);
+                    return;
                 }
+            };
+            println!(
+                "{}: min {}, median {}, max {}, mean {:.2}, standard deviation {:.2}",
+                expression,
+                distribution.min(),
+                distribution.percentile(0.5),
+                distribution.max(),
+                distribution.mean(),
+                distribution.standard_deviation()
+            );
+            for (total, probability) in distribution.pmf() {
+                println!("{:>6} {:>7.3}%", total, probability * 100.0);
             }
         }
         _ => println!("No subcommand was used"),
     }
 }
 
-fn load_all_game_objects(
-    path: &str,
-) -> Result<HashMap<String, Vec<GameObject>>, Box<dyn std::error::Error>> {
-    println!("Loading resources from {}", path);
-    let mut game_objects: HashMap<String, Vec<GameObject>> = HashMap::new();
-
-    // Recursively traverse the directory structure
-    for entry in WalkDir::new(path).into_iter().filter_map(Result::ok) {
-        if entry.path().is_file()
-            && entry.path().extension().and_then(|ext| ext.to_str()) == Some("json")
-        {
-            // Process JSON file
-            process_json_file(entry.path(), &mut game_objects)?;
-        }
-    }
-
-    Ok(game_objects)
-}
-
-fn process_json_file(
-    file_path: &Path,
-    game_objects: &mut HashMap<String, Vec<GameObject>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let file_content = fs::read_to_string(file_path)?;
-    let v: Value = serde_json::from_str(&file_content)?;
-    if v.is_array() {
-        for item in v.as_array().unwrap() {
-            match debug_parse_single_value(item) {
-                Ok(o) => {
-                    game_objects.entry(o.kind.clone()).or_default().push(o);
-                }
-                Err(e) => {
-                    return Err(format!("Error parsing file {:?}: {}", file_path, e).into());
-                }
-            }
+/// PUT a batch of documents to the import endpoint, and report what was saved or why nothing
+/// was.
+async fn import(client: &reqwest::Client, url: &str, documents: &[&Value]) {
+    let response = match client
+        .put(url)
+        .body(serde_json::to_string(documents).unwrap())
+        .header("Content-Type", "application/json")
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
         }
+    };
+
+    let status = response.status();
+    let body: Value = response
+        .text()
+        .await
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    if status.is_success() {
+        println!("IMPORTED {}", body["data"]);
     } else {
-        match debug_parse_single_value(&v) {
-            Ok(o) => {
-                game_objects.entry(o.kind.clone()).or_default().push(o);
-            }
-            Err(e) => {
-                return Err(format!("Error parsing file {:?}: {}", file_path, e).into());
+        match body["reasons"].as_array() {
+            Some(reasons) => {
+                for reason in reasons {
+                    println!("INVALID {}", reason.as_str().unwrap_or_default());
+                }
             }
+            None => println!("Error: import failed with {}", status),
         }
     }
-
-    Ok(())
-}
-
-fn debug_parse_single_value(item_value: &Value) -> Result<GameObject, Box<dyn std::error::Error>> {
-    let spec = serde_json::from_value::<Spec>(item_value.clone())?;
-    Ok(GameObject {
-        kind: item_value["kind"].as_str().unwrap().to_string(),
-        api_version: item_value["apiVersion"].as_str().unwrap().to_string(),
-        spec,
-    })
 }
 
-#[derive(Debug, Clone)]
-struct GameAssets {
-    world: Option<WorldSpec>,
-    items: Vec<ItemSpec>,
-    enemies: Vec<GameObject>,
-    character_classes: Vec<CharacterClassSpec>,
-}
-
-impl GameAssets {
-    fn from_object_array(data: Vec<GameObject>) -> GameAssets {
-        let mut m_world: Option<WorldSpec> = None;
-        let mut m_items: Vec<ItemSpec> = Vec::new();
-        let mut m_enemies = Vec::new();
-        let mut m_character_classes = Vec::new();
-
-        for object in data {
-            match object.clone().spec {
-                Spec::World(w) => {
-                    m_world = Some(w);
-                }
-                Spec::Item(i) => {
-                    m_items.push(i);
-                }
-                Spec::Enemy(e) => {
-                    m_enemies.push(object);
-                }
-                Spec::CharacterClass(c) => {
-                    m_character_classes.push(c);
-                }
+/// PUT a spec as it was written, and report it unless the server already had it.
+async fn upload(client: &reqwest::Client, url: &str, name: &str, code: &str, spec: Option<&Value>) {
+    let spec = spec.cloned().unwrap_or_default();
+    match client
+        .put(url)
+        .body(serde_json::to_string(&spec).unwrap())
+        .header("Content-Type", "application/json")
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status().as_u16() != 304 {
+                println!(
+                    "CREATED {}: {} - {}",
+                    name,
+                    code,
+                    spec["description"].as_str().unwrap_or_default()
+                );
             }
         }
-
-        GameAssets {
-            world: m_world,
-            items: m_items,
-            enemies: m_enemies,
-            character_classes: m_character_classes,
+        Err(e) => {
+            println!("Error: {}", e);
         }
     }
 }